@@ -4,10 +4,13 @@ use conv::{NoError, ValueFrom};
 use conv::errors::UnwrapOk;
 
 use std::ops::{Add, Mul};
+use std::str::{self, FromStr};
+use std::time::Duration;
 
 use {Input, U8Result};
-use combinators::option;
-use parsers::{take_while, take_while1, satisfy};
+use primitives::{InputBuffer, InputClone, IntoInner, State};
+use combinators::{bounded, option, recognize, skip_many};
+use parsers::{Error, any, peek_next, take_while, take_while1, satisfy, satisfy_map, string, token};
 
 /// Lowercase ASCII predicate.
 #[inline]
@@ -54,101 +57,1540 @@ pub fn is_digit(c: u8) -> bool {
     b'0' <= c && c <= b'9'
 }
 
+/// ASCII hexadecimal digit predicate, accepts both lowercase and uppercase letters.
+#[inline]
+pub fn is_hex_digit(c: u8) -> bool {
+    is_digit(c) || b'a' <= c && c <= b'f' || b'A' <= c && c <= b'F'
+}
+
+/// ASCII octal digit predicate.
+#[inline]
+pub fn is_oct_digit(c: u8) -> bool {
+    b'0' <= c && c <= b'7'
+}
+
+/// ASCII binary digit predicate.
+#[inline]
+pub fn is_bin_digit(c: u8) -> bool {
+    c == b'0' || c == b'1'
+}
+
 /// ASCII alphabetic predicate.
 #[inline]
-pub fn is_alpha(c: u8) -> bool {
-    is_lowercase(c) || is_uppercase(c)
+pub fn is_alpha(c: u8) -> bool {
+    is_lowercase(c) || is_uppercase(c)
+}
+
+/// ASCII alphanumeric predicate.
+#[inline]
+pub fn is_alphanumeric(c: u8) -> bool {
+    is_alpha(c) || is_digit(c)
+}
+
+/// ASCII character classes matching the predicates of this module, used with `take_while_class`
+/// and `take_while1_class`.
+#[derive(Copy, Clone, Debug, Eq, PartialEq, Hash)]
+pub enum CharClass {
+    /// See `is_digit`.
+    Digit,
+    /// See `is_hex_digit`.
+    HexDigit,
+    /// See `is_oct_digit`.
+    OctDigit,
+    /// See `is_bin_digit`.
+    BinDigit,
+    /// See `is_lowercase`.
+    Lowercase,
+    /// See `is_uppercase`.
+    Uppercase,
+    /// See `is_alpha`.
+    Alpha,
+    /// See `is_alphanumeric`.
+    Alphanumeric,
+    /// See `is_whitespace`.
+    Whitespace,
+    /// See `is_horizontal_space`.
+    HorizontalSpace,
+}
+
+/// Character class flags for every byte value, indexed by the byte.
+static CLASS_TABLE: [u8; 256] = [
+    0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0xc0, 0x40, 0x40, 0x40, 0x40, 0x00, 0x00,
+    0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00,
+    0xc0, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00,
+    0x0f, 0x0f, 0x07, 0x07, 0x07, 0x07, 0x07, 0x07, 0x03, 0x03, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00,
+    0x00, 0x22, 0x22, 0x22, 0x22, 0x22, 0x22, 0x20, 0x20, 0x20, 0x20, 0x20, 0x20, 0x20, 0x20, 0x20,
+    0x20, 0x20, 0x20, 0x20, 0x20, 0x20, 0x20, 0x20, 0x20, 0x20, 0x20, 0x00, 0x00, 0x00, 0x00, 0x00,
+    0x00, 0x12, 0x12, 0x12, 0x12, 0x12, 0x12, 0x10, 0x10, 0x10, 0x10, 0x10, 0x10, 0x10, 0x10, 0x10,
+    0x10, 0x10, 0x10, 0x10, 0x10, 0x10, 0x10, 0x10, 0x10, 0x10, 0x10, 0x00, 0x00, 0x00, 0x00, 0x00,
+    0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00,
+    0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00,
+    0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00,
+    0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00,
+    0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00,
+    0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00,
+    0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00,
+    0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00,
+];
+
+impl CharClass {
+    /// Returns true if `c` is a member of this character class.
+    #[inline]
+    pub fn contains(self, c: u8) -> bool {
+        CLASS_TABLE[c as usize] & self.flags() != 0
+    }
+
+    /// Flags of this class in `CLASS_TABLE`.
+    #[inline]
+    fn flags(self) -> u8 {
+        match self {
+            CharClass::Digit           => 0x01,
+            CharClass::HexDigit        => 0x02,
+            CharClass::OctDigit        => 0x04,
+            CharClass::BinDigit        => 0x08,
+            CharClass::Lowercase       => 0x10,
+            CharClass::Uppercase       => 0x20,
+            CharClass::Alpha           => 0x30,
+            CharClass::Alphanumeric    => 0x31,
+            CharClass::Whitespace      => 0x40,
+            CharClass::HorizontalSpace => 0x80,
+        }
+    }
+
+    /// Inclusive byte ranges making up this class.
+    #[cfg(all(feature = "simd", target_arch = "x86_64"))]
+    #[inline]
+    fn ranges(self) -> &'static [(u8, u8)] {
+        match self {
+            CharClass::Digit           => &[(b'0', b'9')],
+            CharClass::HexDigit        => &[(b'0', b'9'), (b'a', b'f'), (b'A', b'F')],
+            CharClass::OctDigit        => &[(b'0', b'7')],
+            CharClass::BinDigit        => &[(b'0', b'1')],
+            CharClass::Lowercase       => &[(b'a', b'z')],
+            CharClass::Uppercase       => &[(b'A', b'Z')],
+            CharClass::Alpha           => &[(b'a', b'z'), (b'A', b'Z')],
+            CharClass::Alphanumeric    => &[(b'a', b'z'), (b'A', b'Z'), (b'0', b'9')],
+            CharClass::Whitespace      => &[(9, 13), (b' ', b' ')],
+            CharClass::HorizontalSpace => &[(b'\t', b'\t'), (b' ', b' ')],
+        }
+    }
+}
+
+/// Returns the number of leading bytes of `b` which are members of `class`.
+#[cfg(not(all(feature = "simd", target_arch = "x86_64")))]
+#[inline]
+fn class_len(class: CharClass, b: &[u8]) -> usize {
+    b.iter().position(|&c| !class.contains(c)).unwrap_or(b.len())
+}
+
+/// Returns the number of leading bytes of `b` which are members of `class`, comparing 16 bytes at
+/// a time using SSE2.
+#[cfg(all(feature = "simd", target_arch = "x86_64"))]
+#[inline]
+fn class_len(class: CharClass, b: &[u8]) -> usize {
+    use std::arch::x86_64::*;
+
+    let ranges = class.ranges();
+    let mut n  = 0;
+
+    // SSE2 is always available on x86_64
+    unsafe {
+        while n + 16 <= b.len() {
+            let v     = _mm_loadu_si128(b[n..].as_ptr() as *const __m128i);
+            let mut m = _mm_setzero_si128();
+
+            for &(lo, hi) in ranges {
+                // Unsigned c - lo <= hi - lo, offset by 0x80 since SSE2 only has signed comparison
+                let d  = _mm_xor_si128(_mm_sub_epi8(v, _mm_set1_epi8(lo as i8)), _mm_set1_epi8(-128));
+                let gt = _mm_cmpgt_epi8(d, _mm_set1_epi8(((hi - lo) ^ 0x80) as i8));
+
+                m = _mm_or_si128(m, _mm_andnot_si128(gt, _mm_set1_epi8(-1)));
+            }
+
+            let mask = _mm_movemask_epi8(m) as u32;
+
+            if mask != 0xffff {
+                return n + (!mask).trailing_zeros() as usize;
+            }
+
+            n += 16;
+        }
+    }
+
+    n + b[n..].iter().position(|&c| !class.contains(c)).unwrap_or(b.len() - n)
+}
+
+/// Matches all bytes which are members of `class`, like `take_while` with the corresponding
+/// predicate.
+///
+/// The bytes are classified using a lookup table, or 16 at a time using SSE2 on x86_64 if the
+/// `simd` feature is enabled.
+///
+/// ```
+/// use chomp::parse_only;
+/// use chomp::ascii::{CharClass, take_while_class};
+///
+/// assert_eq!(parse_only(|i| take_while_class(i, CharClass::Digit), b"123abc"), Ok(&b"123"[..]));
+/// ```
+#[inline]
+pub fn take_while_class<'a>(i: Input<'a, u8>, class: CharClass) -> U8Result<'a, &'a [u8]> {
+    let b = i.buffer();
+    let n = class_len(class, b);
+
+    if n < b.len() {
+        i.replace(&b[n..]).ret(&b[..n])
+    } else if i.is_last_slice() {
+        // See take_while for the zero-sized slice
+        i.replace(&b[b.len()..]).ret(b)
+    } else {
+        i.incomplete(1)
+    }
+}
+
+/// Matches all bytes which are members of `class`, like `take_while1` with the corresponding
+/// predicate.
+///
+/// See `take_while_class`.
+///
+/// ```
+/// use chomp::parse_only;
+/// use chomp::ascii::{CharClass, take_while1_class};
+///
+/// assert_eq!(parse_only(|i| take_while1_class(i, CharClass::Alpha), b"abc123"), Ok(&b"abc"[..]));
+/// assert!(parse_only(|i| take_while1_class(i, CharClass::Alpha), b"123abc").is_err());
+/// ```
+#[inline]
+pub fn take_while1_class<'a>(i: Input<'a, u8>, class: CharClass) -> U8Result<'a, &'a [u8]> {
+    let b = i.buffer();
+    let n = class_len(class, b);
+
+    if n == 0 && b.len() > 0 {
+        i.err(Error::unexpected())
+    } else if n < b.len() {
+        i.replace(&b[n..]).ret(&b[..n])
+    } else if b.len() > 0 && i.is_last_slice() {
+        // See take_while for the zero-sized slice
+        i.replace(&b[b.len()..]).ret(b)
+    } else {
+        i.incomplete(1)
+    }
+}
+
+/// Skips over whitespace.
+///
+/// Matches zero-length.
+///
+/// # Example
+///
+/// ```
+/// use chomp::parse_only;
+/// use chomp::ascii::skip_whitespace;
+///
+/// assert_eq!(parse_only(skip_whitespace, b" \t "), Ok(()));
+/// ```
+#[inline]
+pub fn skip_whitespace(i: Input<u8>) -> U8Result<()> {
+    take_while(i, is_whitespace).map(|_| ())
+}
+
+/// Runs the parser `f` and then skips any trailing whitespace, yielding the result of `f`.
+///
+/// This allows grammars to be written in terms of tokens without having to explicitly skip the
+/// whitespace between each of them.
+///
+/// # Example
+///
+/// ```
+/// # #[macro_use] extern crate chomp;
+/// # fn main() {
+/// use chomp::{Input, U8Result, parse_only, token};
+/// use chomp::ascii::{decimal, lexeme};
+///
+/// fn sum(i: Input<u8>) -> U8Result<u32> {
+///     parse!{i;
+///         let a: u32 = lexeme(decimal);
+///                      lexeme(|i| token(i, b'+'));
+///         let b: u32 = lexeme(decimal);
+///
+///         ret a + b
+///     }
+/// }
+///
+/// assert_eq!(parse_only(sum, b"1 +\t2  "), Ok(3));
+/// # }
+/// ```
+#[inline]
+pub fn lexeme<'a, T, F>(i: Input<'a, u8>, f: F) -> U8Result<'a, T>
+  where F: FnOnce(Input<'a, u8>) -> U8Result<'a, T> {
+    lexeme_with(i, f, skip_whitespace)
+}
+
+/// Runs the parser `f` and then the parser `s`, yielding the result of `f`.
+///
+/// Like `lexeme` but with a configurable parser for the trailing data to skip, eg. to also skip
+/// comments.
+///
+/// # Example
+///
+/// ```
+/// use chomp::{parse_only, token, take_while};
+/// use chomp::ascii::{decimal, lexeme_with, skip_whitespace};
+/// use chomp::combinators::skip_many;
+///
+/// let comment = |i| token(i, b'#').then(|i| take_while(i, |c| c != b'\n')).then(skip_whitespace);
+/// let p       = |i| lexeme_with(i, decimal::<u32>, |i| skip_whitespace(i).then(|i| skip_many(i, &comment)));
+///
+/// assert_eq!(parse_only(p, b"12 # comment\n# more\n"), Ok(12));
+/// ```
+#[inline]
+pub fn lexeme_with<'a, T, F, S, U>(i: Input<'a, u8>, f: F, s: S) -> U8Result<'a, T>
+  where U: 'a,
+        F: FnOnce(Input<'a, u8>) -> U8Result<'a, T>,
+        S: FnOnce(Input<'a, u8>) -> U8Result<'a, U> {
+    f(i).skip(s)
+}
+
+/// Matches the keyword `s` only if it is not directly followed by an identifier character, ie. an
+/// ASCII alphanumeric or `'_'`.
+///
+/// If the keyword is followed by an identifier character an unexpected error is reported at that
+/// character. If the keyword is at the end of the buffer and the buffer is not the last slice
+/// this parser is considered incomplete since the following character is still unknown.
+///
+/// # Example
+///
+/// ```
+/// use chomp::{parse_only, ParseError, Error};
+/// use chomp::ascii::keyword;
+///
+/// assert_eq!(parse_only(|i| keyword(i, b"let"), b"let x"), Ok(&b"let"[..]));
+/// assert_eq!(parse_only(|i| keyword(i, b"let"), b"letter"), Err(ParseError::Error(&b"ter"[..], Error::unexpected())));
+/// ```
+#[inline]
+pub fn keyword<'a>(i: Input<'a, u8>, s: &[u8]) -> U8Result<'a, &'a [u8]> {
+    string(i, s).bind(|i, m| {
+        let b = i.buffer();
+
+        match b.first() {
+            None                                        => if i.is_last_slice() {
+                i.ret(m)
+            } else {
+                i.incomplete(1)
+            },
+            Some(&c) if is_alphanumeric(c) || c == b'_' => i.err(Error::unexpected()),
+            Some(_)                                     => i.ret(m),
+        }
+    })
+}
+
+/// Parses a string literal delimited by `quote`, returning the raw contents between the quotes.
+///
+/// A backslash escapes the following character, which means that an escaped quote does not end
+/// the string. No unescaping is performed, see `quoted_string_unescaped` for that.
+///
+/// If the closing quote has not been found yet this parser is considered incomplete, even if it
+/// is the last slice.
+///
+/// # Example
+///
+/// ```
+/// use chomp::parse_only;
+/// use chomp::ascii::quoted_string;
+///
+/// assert_eq!(parse_only(|i| quoted_string(i, b'"'), br#""foo \"bar\"""#), Ok(&br#"foo \"bar\""#[..]));
+/// ```
+#[inline]
+pub fn quoted_string<'a>(i: Input<'a, u8>, quote: u8) -> U8Result<'a, &'a [u8]> {
+    token(i, quote).bind(|i, _| {
+        let b     = i.buffer();
+        let mut n = 0;
+
+        while n < b.len() {
+            match b[n] {
+                c if c == quote => return i.replace(&b[n + 1..]).ret(&b[..n]),
+                b'\\'           => n += 2,
+                _               => n += 1,
+            }
+        }
+
+        // If the buffer ended in the middle of an escape we need one more character before the
+        // closing quote
+        i.incomplete(n - b.len() + 1)
+    })
+}
+
+/// Parses a string literal delimited by `quote`, returning the contents with all escape sequences
+/// decoded.
+///
+/// Escape sequences are decoded using `escape`, and the resulting characters are encoded as
+/// UTF-8.
+///
+/// If the closing quote has not been found yet this parser is considered incomplete, even if it
+/// is the last slice.
+///
+/// # Example
+///
+/// ```
+/// use chomp::parse_only;
+/// use chomp::ascii::quoted_string_unescaped;
+///
+/// assert_eq!(parse_only(|i| quoted_string_unescaped(i, b'"'), br#""a\tb\"""#), Ok(b"a\tb\"".to_vec()));
+/// ```
+#[inline]
+pub fn quoted_string_unescaped(i: Input<u8>, quote: u8) -> U8Result<Vec<u8>> {
+    token(i, quote).bind(|mut i, _| {
+        let mut buf = Vec::new();
+
+        loop {
+            let b = i.buffer();
+
+            match b.iter().position(|&c| c == quote || c == b'\\') {
+                None    => return i.incomplete(1),
+                Some(n) => {
+                    buf.extend(&b[..n]);
+
+                    if b[n] == quote {
+                        return i.replace(&b[n + 1..]).ret(buf);
+                    }
+
+                    match escape(i.clone().replace(&b[n..])).into_inner() {
+                        State::Data(j, c)    => {
+                            let mut utf8 = [0; 4];
+
+                            buf.extend(c.encode_utf8(&mut utf8).as_bytes());
+
+                            i = j;
+                        },
+                        State::Error(b, e)   => return i.replace(b).err(e),
+                        State::Incomplete(n) => return i.incomplete(n),
+                    }
+                },
+            }
+        }
+    })
+}
+
+/// Parses a backslash escape sequence, returning the decoded character.
+///
+/// The following escape sequences are recognized:
+///
+/// * `\n`: line feed
+/// * `\r`: carriage return
+/// * `\t`: horizontal tab
+/// * `\0`: null
+/// * `\\`: backslash
+/// * `\'` and `\"`: single and double quote
+/// * `\xNN`: a character given as exactly two hexadecimal digits, at most `7F`
+/// * `\u{N}`: a unicode scalar value given as one to six hexadecimal digits
+///
+/// An unknown escape results in an unexpected error at the character following the backslash.
+/// Non-hexadecimal digits in a `\x` or `\u` escape result in an error at the offending
+/// character. A value outside of the allowed range, or a surrogate, results in an unexpected
+/// error at the end of the escape sequence.
+///
+/// # Example
+///
+/// ```
+/// use chomp::parse_only;
+/// use chomp::ascii::escape;
+///
+/// assert_eq!(parse_only(escape, b"\\n"), Ok('\n'));
+/// assert_eq!(parse_only(escape, b"\\x41"), Ok('A'));
+/// assert_eq!(parse_only(escape, b"\\u{e5}"), Ok('å'));
+/// ```
+#[inline]
+pub fn escape(i: Input<u8>) -> U8Result<char> {
+    token(i, b'\\').then(peek_next).bind(|i, c| match c {
+        b'x' => any(i).then(hex_digit_value)
+            .bind(|i, h| hex_digit_value(i).map(|l| h * 16 + l))
+            .bind(|i, n| if n <= 0x7F {
+                i.ret(char::from(n))
+            } else {
+                i.err(Error::unexpected())
+            }),
+        b'u' => any(i).then(|i| token(i, b'{'))
+            .then(|i| bounded::fold_many(i, 1..7, 0, hex_digit_value, |a, d| a * 16 + d as u32))
+            .skip(|i| token(i, b'}'))
+            .bind(|i, n| match char::from_u32(n) {
+                Some(c) => i.ret(c),
+                None    => i.err(Error::unexpected()),
+            }),
+        _    => satisfy_map(i, |c| match c {
+            b'n'                 => Some('\n'),
+            b'r'                 => Some('\r'),
+            b't'                 => Some('\t'),
+            b'0'                 => Some('\0'),
+            b'\\' | b'\'' | b'"' => Some(char::from(c)),
+            _                    => None,
+        }),
+    })
+}
+
+/// Matches a line ending, either a line feed (`"\n"`) or a carriage return followed by a line feed
+/// (`"\r\n"`), returning the matched slice.
+///
+/// A carriage return at the end of the buffer is considered incomplete, even if it is the last
+/// slice, since it has to be followed by a line feed.
+///
+/// # Example
+///
+/// ```
+/// use chomp::parse_only;
+/// use chomp::ascii::line_ending;
+///
+/// assert_eq!(parse_only(line_ending, b"\n"), Ok(&b"\n"[..]));
+/// assert_eq!(parse_only(line_ending, b"\r\n"), Ok(&b"\r\n"[..]));
+/// ```
+#[inline]
+pub fn line_ending<'a>(i: Input<'a, u8>) -> U8Result<'a, &'a [u8]> {
+    let b = i.buffer();
+
+    match (b.first(), b.get(1)) {
+        (None, _)                     => i.incomplete(1),
+        (Some(&b'\n'), _)             => i.replace(&b[1..]).ret(&b[..1]),
+        (Some(&b'\r'), None)          => i.incomplete(1),
+        (Some(&b'\r'), Some(&b'\n'))  => i.replace(&b[2..]).ret(&b[..2]),
+        (Some(&b'\r'), Some(_))       => i.replace(&b[1..]).err(Error::expected(b'\n')),
+        (Some(_), _)                  => i.err(Error::expected(b'\n')),
+    }
+}
+
+/// Matches a line ending like `line_ending` but also accepts a bare carriage return (`"\r"`).
+///
+/// A carriage return at the end of the buffer is considered incomplete unless it is the last
+/// slice, since it could be the first part of a `"\r\n"` line ending.
+///
+/// # Example
+///
+/// ```
+/// use chomp::parse_only;
+/// use chomp::ascii::any_line_ending;
+///
+/// assert_eq!(parse_only(any_line_ending, b"\r\n"), Ok(&b"\r\n"[..]));
+/// assert_eq!(parse_only(any_line_ending, b"\r"), Ok(&b"\r"[..]));
+/// ```
+#[inline]
+pub fn any_line_ending<'a>(i: Input<'a, u8>) -> U8Result<'a, &'a [u8]> {
+    let b = i.buffer();
+
+    match (b.first(), b.get(1)) {
+        (None, _)                     => i.incomplete(1),
+        (Some(&b'\n'), _)             => i.replace(&b[1..]).ret(&b[..1]),
+        (Some(&b'\r'), None)          => if i.is_last_slice() {
+            i.replace(&b[1..]).ret(&b[..1])
+        } else {
+            i.incomplete(1)
+        },
+        (Some(&b'\r'), Some(&b'\n'))  => i.replace(&b[2..]).ret(&b[..2]),
+        (Some(&b'\r'), Some(_))       => i.replace(&b[1..]).ret(&b[..1]),
+        (Some(_), _)                  => i.err(Error::expected(b'\n')),
+    }
+}
+
+/// Skips over any number of line endings, as matched by `any_line_ending`.
+///
+/// Matches zero-length.
+///
+/// # Example
+///
+/// ```
+/// use chomp::parse_only;
+/// use chomp::ascii::skip_line_endings;
+///
+/// assert_eq!(parse_only(skip_line_endings, b"\r\n\n\r\r\n"), Ok(()));
+/// ```
+#[inline]
+pub fn skip_line_endings(i: Input<u8>) -> U8Result<()> {
+    skip_many(i, any_line_ending)
+}
+
+/// Matches the rest of the current line, returning it without the line ending and leaving the
+/// input positioned after the line ending.
+///
+/// Both `"\n"` and `"\r\n"` line endings are accepted. If the last slice ends without a line
+/// ending the remainder of the input is returned as the last line. An empty last slice is
+/// considered incomplete, since there is no line left.
+///
+/// # Example
+///
+/// ```
+/// use chomp::parse_only;
+/// use chomp::ascii::rest_of_line;
+/// use chomp::combinators::many;
+///
+/// let r: Result<Vec<_>, _> = parse_only(|i| many(i, rest_of_line), b"foo\r\nbar\nbaz");
+///
+/// assert_eq!(r, Ok(vec![&b"foo"[..], &b"bar"[..], &b"baz"[..]]));
+/// ```
+#[inline]
+pub fn rest_of_line<'a>(i: Input<'a, u8>) -> U8Result<'a, &'a [u8]> {
+    let b = i.buffer();
+
+    match memchr(b'\n', b) {
+        Some(n) => {
+            let line = if n > 0 && b[n - 1] == b'\r' { &b[..n - 1] } else { &b[..n] };
+
+            i.replace(&b[n + 1..]).ret(line)
+        },
+        None    => if b.len() > 0 && i.is_last_slice() {
+            // Last line without a line ending, see take_while for the zero-sized slice
+            i.replace(&b[b.len()..]).ret(b)
+        } else {
+            i.incomplete(1)
+        },
+    }
+}
+
+/// Matches all bytes until `a` is found, all bytes to that point will be returned as a slice upon
+/// success.
+///
+/// Equivalent to `take_till(i, |c| c == a)`, but uses `memchr` to scan the input if the `memchr`
+/// feature is enabled.
+///
+/// ```
+/// use chomp::parse_only;
+/// use chomp::ascii::take_till_byte;
+///
+/// assert_eq!(parse_only(|i| take_till_byte(i, b';'), b"abc;def"), Ok(&b"abc"[..]));
+/// ```
+#[inline]
+pub fn take_till_byte<'a>(i: Input<'a, u8>, a: u8) -> U8Result<'a, &'a [u8]> {
+    let b = i.buffer();
+
+    match memchr(a, b) {
+        Some(n) => i.replace(&b[n..]).ret(&b[..n]),
+        None    => i.incomplete(1),
+    }
+}
+
+/// Matches all bytes until either `a` or `b` is found, see `take_till_byte`.
+///
+/// ```
+/// use chomp::parse_only;
+/// use chomp::ascii::take_till_byte2;
+///
+/// assert_eq!(parse_only(|i| take_till_byte2(i, b'\r', b'\n'), b"abc\ndef"), Ok(&b"abc"[..]));
+/// ```
+#[inline]
+pub fn take_till_byte2<'a>(i: Input<'a, u8>, a: u8, b: u8) -> U8Result<'a, &'a [u8]> {
+    let buf = i.buffer();
+
+    match memchr2(a, b, buf) {
+        Some(n) => i.replace(&buf[n..]).ret(&buf[..n]),
+        None    => i.incomplete(1),
+    }
+}
+
+/// Matches all bytes until either `a`, `b` or `c` is found, see `take_till_byte`.
+///
+/// ```
+/// use chomp::parse_only;
+/// use chomp::ascii::take_till_byte3;
+///
+/// assert_eq!(parse_only(|i| take_till_byte3(i, b'"', b'\\', b'\n'), b"abc\\\""), Ok(&b"abc"[..]));
+/// ```
+#[inline]
+pub fn take_till_byte3<'a>(i: Input<'a, u8>, a: u8, b: u8, c: u8) -> U8Result<'a, &'a [u8]> {
+    let buf = i.buffer();
+
+    match memchr3(a, b, c, buf) {
+        Some(n) => i.replace(&buf[n..]).ret(&buf[..n]),
+        None    => i.incomplete(1),
+    }
+}
+
+/// Matches all bytes until the sequence `s` is found, all bytes up to (but not including) `s` will
+/// be returned as a slice upon success.
+///
+/// Equivalent to `parsers::take_until`, but uses `memchr` to find candidates for the first byte of
+/// `s` if the `memchr` feature is enabled.
+///
+/// ```
+/// use chomp::parse_only;
+/// use chomp::ascii::take_until_bytes;
+///
+/// let r = parse_only(|i| take_until_bytes(i, b"\r\n\r\n"), b"Host: example.com\r\n\r\nbody");
+///
+/// assert_eq!(r, Ok(&b"Host: example.com"[..]));
+/// ```
+#[inline]
+pub fn take_until_bytes<'a>(i: Input<'a, u8>, s: &[u8]) -> U8Result<'a, &'a [u8]> {
+    let b = i.buffer();
+
+    if s.is_empty() {
+        return i.ret(&b[..0]);
+    }
+
+    let mut n = 0;
+
+    while let Some(m) = memchr(s[0], &b[n..]) {
+        n += m;
+
+        if b[n..].starts_with(s) {
+            return i.replace(&b[n..]).ret(&b[..n]);
+        }
+
+        if b.len() - n < s.len() && s.starts_with(&b[n..]) {
+            // Partial match at the end of the buffer
+            return i.incomplete(s.len() - (b.len() - n));
+        }
+
+        n += 1;
+    }
+
+    i.incomplete(s.len())
+}
+
+#[cfg(feature = "memchr")]
+#[inline]
+fn memchr(a: u8, b: &[u8]) -> Option<usize> {
+    ::memchr::memchr(a, b)
+}
+
+#[cfg(not(feature = "memchr"))]
+#[inline]
+fn memchr(a: u8, b: &[u8]) -> Option<usize> {
+    b.iter().position(|&c| c == a)
+}
+
+#[cfg(feature = "memchr")]
+#[inline]
+fn memchr2(a: u8, b: u8, buf: &[u8]) -> Option<usize> {
+    ::memchr::memchr2(a, b, buf)
+}
+
+#[cfg(not(feature = "memchr"))]
+#[inline]
+fn memchr2(a: u8, b: u8, buf: &[u8]) -> Option<usize> {
+    buf.iter().position(|&c| c == a || c == b)
+}
+
+#[cfg(feature = "memchr")]
+#[inline]
+fn memchr3(a: u8, b: u8, c: u8, buf: &[u8]) -> Option<usize> {
+    ::memchr::memchr3(a, b, c, buf)
+}
+
+#[cfg(not(feature = "memchr"))]
+#[inline]
+fn memchr3(a: u8, b: u8, c: u8, buf: &[u8]) -> Option<usize> {
+    buf.iter().position(|&d| d == a || d == b || d == c)
+}
+
+/// Skips a line comment starting with `prefix`, up to but not including the line ending.
+///
+/// A comment which is not followed by a line ending is terminated by the end of the input.
+///
+/// # Example
+///
+/// ```
+/// use chomp::{parse_only, token};
+/// use chomp::ascii::skip_line_comment;
+///
+/// let p = |i| skip_line_comment(i, b"//").then(|i| token(i, b'\n'));
+///
+/// assert_eq!(parse_only(p, b"// foo\n"), Ok(b'\n'));
+/// ```
+#[inline]
+pub fn skip_line_comment<'a>(i: Input<'a, u8>, prefix: &[u8]) -> U8Result<'a, ()> {
+    string(i, prefix).then(|i| take_while(i, |c| c != b'\n')).map(|_| ())
+}
+
+/// Skips a block comment delimited by `open` and `close`.
+///
+/// If `nested` is true, block comments may be nested and each `open` has to be matched by a
+/// `close`, otherwise the comment ends at the first `close`.
+///
+/// If the closing delimiter has not been found yet this parser is considered incomplete, even if
+/// it is the last slice.
+///
+/// # Example
+///
+/// ```
+/// use chomp::{parse_only, token};
+/// use chomp::ascii::skip_block_comment;
+///
+/// let p = |i| skip_block_comment(i, b"/*", b"*/", true).then(|i| token(i, b'd'));
+///
+/// assert_eq!(parse_only(p, b"/* a /* b */ c */d"), Ok(b'd'));
+/// ```
+#[inline]
+pub fn skip_block_comment<'a>(i: Input<'a, u8>, open: &[u8], close: &[u8], nested: bool) -> U8Result<'a, ()> {
+    string(i, open).bind(|i, _| {
+        let b         = i.buffer();
+        let mut depth = 1;
+        let mut n     = 0;
+
+        while n < b.len() {
+            if b[n..].starts_with(close) {
+                n     += close.len();
+                depth -= 1;
+
+                if depth == 0 {
+                    return i.replace(&b[n..]).ret(());
+                }
+            } else if nested && b[n..].starts_with(open) {
+                n     += open.len();
+                depth += 1;
+            } else {
+                n += 1;
+            }
+        }
+
+        i.incomplete(1)
+    })
+}
+
+/// Parses a single digit.
+///
+/// # Note
+///
+/// The result is the code of the digit, no conversion takes place.
+///
+/// # Example
+///
+/// ```
+/// use chomp::parse_only;
+/// use chomp::ascii::digit;
+///
+/// assert_eq!(parse_only(digit, b"1"), Ok(b'1'));
+/// ```
+#[inline]
+pub fn digit(i: Input<u8>) -> U8Result<u8> {
+    satisfy(i, is_digit)
+}
+
+/// Parses a single digit and returns its numeric value.
+///
+/// # Example
+///
+/// ```
+/// use chomp::parse_only;
+/// use chomp::ascii::digit_value;
+///
+/// assert_eq!(parse_only(digit_value, b"7"), Ok(7));
+/// ```
+#[inline]
+pub fn digit_value(i: Input<u8>) -> U8Result<u8> {
+    satisfy_map(i, |c| if is_digit(c) { Some(c - b'0') } else { None })
+}
+
+/// Parses a single hexadecimal digit, either lowercase or uppercase, and returns its numeric
+/// value.
+///
+/// # Example
+///
+/// ```
+/// use chomp::parse_only;
+/// use chomp::ascii::hex_digit_value;
+///
+/// assert_eq!(parse_only(hex_digit_value, b"f"), Ok(15));
+/// assert_eq!(parse_only(hex_digit_value, b"A"), Ok(10));
+/// assert_eq!(parse_only(hex_digit_value, b"3"), Ok(3));
+/// ```
+#[inline]
+pub fn hex_digit_value(i: Input<u8>) -> U8Result<u8> {
+    satisfy_map(i, |c| match c {
+        c if is_digit(c)            => Some(c - b'0'),
+        c if b'a' <= c && c <= b'f' => Some(c - b'a' + 10),
+        c if b'A' <= c && c <= b'F' => Some(c - b'A' + 10),
+        _                           => None,
+    })
+}
+
+/// Parses a number with an optional leading '+' or '-'.
+///
+/// # Note
+///
+/// The from `i8` bound here is usually smaller than the number parser requirement for signed
+/// integers (usually the smallest possible signed is `i16`).
+///
+/// # Example
+///
+/// ```
+/// use chomp::parse_only;
+/// use chomp::ascii::{decimal, signed};
+///
+/// let r: Result<i16, _> = parse_only(|i| signed(i, decimal), b"-123");
+///
+/// assert_eq!(r, Ok(-123i16));
+/// ```
+#[inline]
+pub fn signed<T, F>(i: Input<u8>, f: F) -> U8Result<T>
+  where T: Copy + ValueFrom<i8, Err=NoError> + Add<Output=T> + Mul<Output=T>,
+        F: FnOnce(Input<u8>) -> U8Result<T> {
+    option(i,
+           |i| satisfy(i, |c| c == b'-' || c == b'+')
+               .map(|s| T::value_from(if s == b'+' { 1 } else { -1 }).unwrap_ok()),
+           T::value_from(1).unwrap_ok())
+        .bind(|i, sign| f(i).map(|num| sign * num))
+}
+
+/// Parses a decimal integer with an optional leading '+' or '-', failing if the value does not
+/// fit in `T`.
+///
+/// Unlike `signed(i, decimal)` the sign is taken into account while converting, which means that
+/// the most negative value of a signed type (eg. `-128` for `i8`) can be parsed. Values out of
+/// range for `T`, as well as negative numbers for unsigned types, result in an unexpected error
+/// reported at the end of the number.
+///
+/// # Example
+///
+/// ```
+/// use chomp::{parse_only, ParseError, Error};
+/// use chomp::ascii::signed_checked;
+///
+/// assert_eq!(parse_only(signed_checked::<i8>, b"-128"), Ok(-128i8));
+/// assert_eq!(parse_only(signed_checked::<i8>, b"+127"), Ok(127i8));
+/// assert_eq!(parse_only(signed_checked::<i8>, b"128"), Err(ParseError::Error(&b""[..], Error::unexpected())));
+/// ```
+#[inline]
+pub fn signed_checked<T: FromStr>(i: Input<u8>) -> U8Result<T> {
+    recognize(i, |i| sign(i).then(|i| take_while1(i, is_digit)))
+        .bind(|i, buf| from_ascii(i, buf))
+}
+
+/// Parses a series of digits and converts them to an integer.
+///
+/// # Note
+///
+/// The `T` type must be larger than `u8` if it is signed.
+///
+/// # Example
+///
+/// ```
+/// use chomp::parse_only;
+/// use chomp::ascii::decimal;
+///
+/// let r = parse_only(decimal::<u8>, b"123");
+///
+/// assert_eq!(r, Ok(123u8));
+/// ```
+#[inline]
+pub fn decimal<T: Copy + ValueFrom<u8, Err=NoError> + Add<Output=T> + Mul<Output=T>>(i: Input<u8>) -> U8Result<T> {
+    take_while1(i, is_digit).map(to_decimal)
+}
+
+/// Parses a series of octal digits and converts them to an integer.
+///
+/// # Note
+///
+/// The `T` type must be larger than `u8` if it is signed.
+///
+/// # Example
+///
+/// ```
+/// use chomp::parse_only;
+/// use chomp::ascii::octal;
+///
+/// let r = parse_only(octal::<u16>, b"755");
+///
+/// assert_eq!(r, Ok(0o755u16));
+/// ```
+#[inline]
+pub fn octal<T: Copy + ValueFrom<u8, Err=NoError> + Add<Output=T> + Mul<Output=T>>(i: Input<u8>) -> U8Result<T> {
+    take_while1(i, is_oct_digit).map(|b| to_radix(b, 8))
+}
+
+/// Parses a series of binary digits and converts them to an integer.
+///
+/// # Note
+///
+/// The `T` type must be larger than `u8` if it is signed.
+///
+/// # Example
+///
+/// ```
+/// use chomp::parse_only;
+/// use chomp::ascii::binary;
+///
+/// let r = parse_only(binary::<u8>, b"1011");
+///
+/// assert_eq!(r, Ok(0b1011u8));
+/// ```
+#[inline]
+pub fn binary<T: Copy + ValueFrom<u8, Err=NoError> + Add<Output=T> + Mul<Output=T>>(i: Input<u8>) -> U8Result<T> {
+    take_while1(i, is_bin_digit).map(|b| to_radix(b, 2))
+}
+
+/// Parses a series of hexadecimal digits, either lowercase or uppercase, and converts them to an
+/// integer.
+///
+/// # Note
+///
+/// The `T` type must be larger than `u8` if it is signed.
+///
+/// # Example
+///
+/// ```
+/// use chomp::parse_only;
+/// use chomp::ascii::hexadecimal;
+///
+/// let r = parse_only(hexadecimal::<u32>, b"fF01");
+///
+/// assert_eq!(r, Ok(0xff01u32));
+/// ```
+#[inline]
+pub fn hexadecimal<T: Copy + ValueFrom<u8, Err=NoError> + Add<Output=T> + Mul<Output=T>>(i: Input<u8>) -> U8Result<T> {
+    take_while1(i, is_hex_digit).map(|b| to_radix(b, 16))
+}
+
+/// Parses a series of digits which may be separated by `sep` and converts them to an integer.
+///
+/// A separator must be placed between two digits, a leading separator is not matched while a
+/// trailing or doubled separator results in an unexpected error at the character following the
+/// separator.
+///
+/// # Note
+///
+/// The `T` type must be larger than `u8` if it is signed.
+///
+/// # Example
+///
+/// ```
+/// use chomp::parse_only;
+/// use chomp::ascii::decimal_with_separator;
+///
+/// let r = parse_only(|i| decimal_with_separator::<u32>(i, b'_'), b"1_000_000");
+///
+/// assert_eq!(r, Ok(1000000u32));
+/// ```
+#[inline]
+pub fn decimal_with_separator<T: Copy + ValueFrom<u8, Err=NoError> + Add<Output=T> + Mul<Output=T>>(i: Input<u8>, sep: u8) -> U8Result<T> {
+    separated_digits(i, sep, is_digit).map(|b| to_radix_separated(b, 10, sep))
+}
+
+/// Parses a series of hexadecimal digits which may be separated by `sep` and converts them to an
+/// integer.
+///
+/// Separators are handled like in `decimal_with_separator`.
+///
+/// # Note
+///
+/// The `T` type must be larger than `u8` if it is signed.
+///
+/// # Example
+///
+/// ```
+/// use chomp::parse_only;
+/// use chomp::ascii::hexadecimal_with_separator;
+///
+/// let r = parse_only(|i| hexadecimal_with_separator::<u32>(i, b'_'), b"dead_BEEF");
+///
+/// assert_eq!(r, Ok(0xdeadbeefu32));
+/// ```
+#[inline]
+pub fn hexadecimal_with_separator<T: Copy + ValueFrom<u8, Err=NoError> + Add<Output=T> + Mul<Output=T>>(i: Input<u8>, sep: u8) -> U8Result<T> {
+    separated_digits(i, sep, is_hex_digit).map(|b| to_radix_separated(b, 16, sep))
+}
+
+/// Internal parser matching a non-empty series of digits matching `f` where each pair of digits
+/// may be separated by a single `sep`.
+#[inline]
+fn separated_digits<'a, F>(i: Input<'a, u8>, sep: u8, f: F) -> U8Result<'a, &'a [u8]>
+  where F: Fn(u8) -> bool {
+    let b = i.buffer();
+
+    match b.first() {
+        None              => return i.incomplete(1),
+        Some(&c) if !f(c) => return i.err(Error::unexpected()),
+        Some(_)           => (),
+    }
+
+    let mut n = 1;
+
+    loop {
+        match (b.get(n), b.get(n + 1)) {
+            (None, _)                                => return if i.is_last_slice() {
+                i.replace(&b[n..]).ret(b)
+            } else {
+                i.incomplete(1)
+            },
+            (Some(&c), _) if f(c)                    => n += 1,
+            (Some(&c), Some(&d)) if c == sep && f(d) => n += 2,
+            (Some(&c), Some(_)) if c == sep          => return i.replace(&b[n + 1..]).err(Error::unexpected()),
+            (Some(&c), None) if c == sep             => return if i.is_last_slice() {
+                i.replace(&b[n + 1..]).err(Error::unexpected())
+            } else {
+                i.incomplete(1)
+            },
+            (Some(_), _)                             => return i.replace(&b[n..]).ret(&b[..n]),
+        }
+    }
+}
+
+/// Parses a floating point number in the form `[+-]?digits[.digits]` and converts it using
+/// `FromStr`.
+///
+/// The matched part of the input is converted directly, without copying. If the conversion fails
+/// an unexpected error is reported at the end of the number.
+///
+/// # Note
+///
+/// A trailing `'.'` without any following digits is not considered part of the number.
+///
+/// # Example
+///
+/// ```
+/// use chomp::parse_only;
+/// use chomp::ascii::float;
+///
+/// assert_eq!(parse_only(float::<f64>, b"-12.5"), Ok(-12.5));
+/// assert_eq!(parse_only(float::<f32>, b"3"), Ok(3.0));
+/// ```
+#[inline]
+pub fn float<T: FromStr>(i: Input<u8>) -> U8Result<T> {
+    recognize(i, mantissa).bind(|i, buf| from_ascii(i, buf))
+}
+
+/// Parses a floating point number which may be written in scientific notation, in the form
+/// `[+-]?digits[.digits][(e|E)[+-]?digits]`, and converts it using `FromStr`.
+///
+/// Behaves like `float` but also accepts an exponent, with or without a fractional part.
+///
+/// # Note
+///
+/// An exponent marker without any following digits is not considered part of the number.
+///
+/// # Example
+///
+/// ```
+/// use chomp::parse_only;
+/// use chomp::ascii::float_exp;
+///
+/// assert_eq!(parse_only(float_exp::<f64>, b"1.5e-3"), Ok(1.5e-3));
+/// assert_eq!(parse_only(float_exp::<f64>, b"2E+10"), Ok(2e10));
+/// assert_eq!(parse_only(float_exp::<f64>, b"1e5"), Ok(1e5));
+/// ```
+#[inline]
+pub fn float_exp<T: FromStr>(i: Input<u8>) -> U8Result<T> {
+    recognize(i, |i| mantissa(i)
+        .then(|i| option(i, |i| satisfy(i, |c| c == b'e' || c == b'E')
+                  .then(sign)
+                  .then(|i| take_while1(i, is_digit))
+                  .map(|_| ()), ())))
+        .bind(|i, buf| from_ascii(i, buf))
+}
+
+/// Internal parser matching an optional sign.
+#[inline]
+fn sign(i: Input<u8>) -> U8Result<()> {
+    option(i, |i| satisfy(i, |c| c == b'-' || c == b'+').map(|_| ()), ())
+}
+
+/// Internal parser matching `[+-]?digits[.digits]`.
+#[inline]
+fn mantissa(i: Input<u8>) -> U8Result<()> {
+    sign(i).then(|i| take_while1(i, is_digit))
+        .then(|i| option(i, |i| token(i, b'.').then(|i| take_while1(i, is_digit)).map(|_| ()), ()))
+}
+
+/// Internal function converting a matched slice of ASCII characters into `T` using `FromStr`.
+#[inline]
+fn from_ascii<'a, T: FromStr>(i: Input<'a, u8>, buf: &'a [u8]) -> U8Result<'a, T> {
+    // Only ASCII has been matched, so this is always valid UTF-8
+    match str::from_utf8(buf).ok().and_then(|s| s.parse().ok()) {
+        Some(n) => i.ret(n),
+        None    => i.err(Error::unexpected()),
+    }
+}
+
+/// Parses a duration made up of one or more components in the form `digits[.digits]unit`, eg.
+/// `1h30m`, `250ms` or `2.5s`.
+///
+/// The following units are recognized:
+///
+/// * `h`: hours
+/// * `m`: minutes
+/// * `s`: seconds
+/// * `ms`: milliseconds
+/// * `us` or `µs`: microseconds
+/// * `ns`: nanoseconds
+///
+/// Fractions smaller than a nanosecond are truncated. A duration which does not fit in
+/// `Duration` results in an unexpected error at the end of the duration.
+///
+/// # Example
+///
+/// ```
+/// use std::time::Duration;
+///
+/// use chomp::parse_only;
+/// use chomp::ascii::duration;
+///
+/// assert_eq!(parse_only(duration, b"1h30m"), Ok(Duration::from_secs(5400)));
+/// assert_eq!(parse_only(duration, b"250ms"), Ok(Duration::from_millis(250)));
+/// assert_eq!(parse_only(duration, b"2.5s"), Ok(Duration::from_millis(2500)));
+/// ```
+#[inline]
+pub fn duration(i: Input<u8>) -> U8Result<Duration> {
+    bounded::fold_many(i, 1.., 0, duration_part, |a: u128, n| a.saturating_add(n))
+        .bind(|i, n| if n / NANOS_PER_SEC <= u64::max_value() as u128 {
+            i.ret(Duration::new((n / NANOS_PER_SEC) as u64, (n % NANOS_PER_SEC) as u32))
+        } else {
+            i.err(Error::unexpected())
+        })
+}
+
+/// Number of nanoseconds per second.
+const NANOS_PER_SEC: u128 = 1_000_000_000;
+
+/// Units accepted by `duration` together with their length in nanoseconds, longer units have to
+/// be placed before any of their prefixes.
+const DURATION_UNITS: &'static [(&'static [u8], u64)] = &[
+    (b"h",         3_600_000_000_000),
+    (b"ms",        1_000_000),
+    (b"m",         60_000_000_000),
+    (b"s",         1_000_000_000),
+    (b"us",        1_000),
+    (b"\xC2\xB5s", 1_000),
+    (b"ns",        1),
+];
+
+/// Internal parser matching a single duration component, yielding its length in nanoseconds.
+#[inline]
+fn duration_part(i: Input<u8>) -> U8Result<u128> {
+    take_while1(i, is_digit).bind(|i, int|
+        option(i, |i| token(i, b'.').then(|i| take_while1(i, is_digit)), &b""[..]).bind(|i, frac|
+            duration_unit(i).bind(|i, unit| match to_nanos(int, frac, unit) {
+                Some(n) => i.ret(n),
+                None    => i.err(Error::unexpected()),
+            })))
 }
 
-/// ASCII alphanumeric predicate.
+/// Internal parser matching one of the `DURATION_UNITS`, yielding its length in nanoseconds.
 #[inline]
-pub fn is_alphanumeric(c: u8) -> bool {
-    is_alpha(c) || is_digit(c)
+fn duration_unit(i: Input<u8>) -> U8Result<u64> {
+    let b = i.buffer();
+
+    for &(name, n) in DURATION_UNITS {
+        if b.starts_with(name) {
+            return i.replace(&b[name.len()..]).ret(n);
+        }
+
+        // The buffer could be the start of this unit, or a longer unit sharing the same prefix,
+        // we need more data to decide
+        if name.starts_with(b) && !i.is_last_slice() {
+            return i.incomplete(name.len() - b.len());
+        }
+    }
+
+    if b.is_empty() {
+        i.incomplete(1)
+    } else {
+        i.err(Error::unexpected())
+    }
 }
 
-/// Skips over whitespace.
+/// Internal function converting the integer and fraction digits of a duration component into
+/// nanoseconds, returns `None` on overflow.
+#[inline]
+fn to_nanos(int: &[u8], frac: &[u8], unit: u64) -> Option<u128> {
+    let unit    = unit as u128;
+    let n       = int.iter().fold(Some(0u128), |n, &c|
+        n.and_then(|n| n.checked_mul(10)).and_then(|n| n.checked_add((c - b'0') as u128)));
+    let mut f   = 0u128;
+    let mut div = 1u128;
+
+    // Digits beyond the 20th are always below a nanosecond for the available units
+    for &c in frac.iter().take(20) {
+        f    = f * 10 + (c - b'0') as u128;
+        div *= 10;
+    }
+
+    n.and_then(|n| n.checked_mul(unit)).and_then(|n| n.checked_add(f * unit / div))
+}
+
+/// Parses an even-length run of hexadecimal digits, either lowercase or uppercase, and decodes
+/// each pair of digits into a byte.
 ///
-/// Matches zero-length.
+/// A run of odd length results in an unexpected error at the end of the run.
 ///
 /// # Example
 ///
 /// ```
 /// use chomp::parse_only;
-/// use chomp::ascii::skip_whitespace;
+/// use chomp::ascii::hex_bytes;
 ///
-/// assert_eq!(parse_only(skip_whitespace, b" \t "), Ok(()));
+/// assert_eq!(parse_only(hex_bytes, b"00fF7a"), Ok(vec![0x00, 0xff, 0x7a]));
 /// ```
 #[inline]
-pub fn skip_whitespace(i: Input<u8>) -> U8Result<()> {
-    take_while(i, is_whitespace).map(|_| ())
+pub fn hex_bytes(i: Input<u8>) -> U8Result<Vec<u8>> {
+    take_while1(i, is_hex_digit).bind(|i, b| if b.len() % 2 == 0 {
+        i.ret(b.chunks(2).map(|d| to_radix(d, 16)).collect())
+    } else {
+        i.err(Error::unexpected())
+    })
 }
 
-/// Parses a single digit.
-///
-/// # Note
+/// Parses exactly two hexadecimal digits for each byte in `A` and decodes them into `A`, eg. a
+/// `[u8; 20]` for a SHA-1 hash.
 ///
-/// The result is the code of the digit, no conversion takes place.
+/// A character which is not a hexadecimal digit results in an unexpected error at that
+/// character.
 ///
 /// # Example
 ///
 /// ```
 /// use chomp::parse_only;
-/// use chomp::ascii::digit;
+/// use chomp::ascii::hex_bytes_fixed;
 ///
-/// assert_eq!(parse_only(digit, b"1"), Ok(b'1'));
+/// let r: Result<[u8; 4], _> = parse_only(hex_bytes_fixed, b"deadBEEF");
+///
+/// assert_eq!(r, Ok([0xde, 0xad, 0xbe, 0xef]));
 /// ```
 #[inline]
-pub fn digit(i: Input<u8>) -> U8Result<u8> {
-    satisfy(i, is_digit)
+pub fn hex_bytes_fixed<A: Default + AsMut<[u8]>>(i: Input<u8>) -> U8Result<A> {
+    let b     = i.buffer();
+    let mut a = A::default();
+    let n     = a.as_mut().len() * 2;
+
+    if let Some(p) = b.iter().take(n).position(|&c| !is_hex_digit(c)) {
+        return i.replace(&b[p..]).err(Error::unexpected());
+    }
+
+    if b.len() < n {
+        return i.incomplete(n - b.len());
+    }
+
+    for (t, d) in a.as_mut().iter_mut().zip(b[..n].chunks(2)) {
+        *t = to_radix(d, 16);
+    }
+
+    i.replace(&b[n..]).ret(a)
 }
 
-/// Parses a number with an optional leading '+' or '-'.
+/// Decodes quoted-printable encoded text (RFC 2045) into bytes, eg. the body of a MIME part.
 ///
-/// # Note
+/// An `=` followed by two hexadecimal digits is decoded into the byte they represent, and soft
+/// line breaks, `=` followed by `"\r\n"` or `"\n"`, are removed. Printable ASCII characters,
+/// spaces, tabs and line endings are kept as is; decoding stops at the first character outside of
+/// that set.
 ///
-/// The from `i8` bound here is usually smaller than the number parser requirement for signed
-/// integers (usually the smallest possible signed is `i16`).
+/// An `=` which is followed by neither a soft line break nor two hexadecimal digits results in an
+/// unexpected error at the `=`. If the end of the buffer is reached and it is not the last slice
+/// this parser is considered incomplete, including when an `=` sequence is split across the end
+/// of the buffer.
 ///
 /// # Example
 ///
 /// ```
 /// use chomp::parse_only;
-/// use chomp::ascii::{decimal, signed};
-///
-/// let r: Result<i16, _> = parse_only(|i| signed(i, decimal), b"-123");
+/// use chomp::ascii::quoted_printable;
 ///
-/// assert_eq!(r, Ok(-123i16));
+/// assert_eq!(parse_only(quoted_printable, b"caf=C3=A9 au=\r\nlait"), Ok("café aulait".as_bytes().to_vec()));
 /// ```
-#[inline]
-pub fn signed<T, F>(i: Input<u8>, f: F) -> U8Result<T>
-  where T: Copy + ValueFrom<i8, Err=NoError> + Add<Output=T> + Mul<Output=T>,
-        F: FnOnce(Input<u8>) -> U8Result<T> {
-    option(i,
-           |i| satisfy(i, |c| c == b'-' || c == b'+')
-               .map(|s| T::value_from(if s == b'+' { 1 } else { -1 }).unwrap_ok()),
-           T::value_from(1).unwrap_ok())
-        .bind(|i, sign| f(i).map(|num| sign * num))
+pub fn quoted_printable(i: Input<u8>) -> U8Result<Vec<u8>> {
+    let b     = i.buffer();
+    let last  = i.is_last_slice();
+    let mut v = Vec::with_capacity(b.len());
+    let mut n = 0;
+
+    while n < b.len() {
+        match b[n] {
+            b'=' => match (b.get(n + 1).cloned(), b.get(n + 2).cloned()) {
+                (Some(b'\n'), _)                                            => n += 2,
+                (Some(b'\r'), Some(b'\n'))                                  => n += 3,
+                (Some(h), Some(l)) if is_hex_digit(h) && is_hex_digit(l)    => {
+                    v.push(to_radix(&b[n + 1..n + 3], 16));
+
+                    n += 3;
+                },
+                (Some(h), None) if !last && (h == b'\r' || is_hex_digit(h)) => return i.incomplete(1),
+                (None, _) if !last                                          => return i.incomplete(2),
+                _                                                           => return i.replace(&b[n..]).err(Error::unexpected()),
+            },
+            c if c == b'\t' || c == b'\r' || c == b'\n' || (b' ' <= c && c <= b'~') => {
+                v.push(c);
+
+                n += 1;
+            },
+            _ => return i.replace(&b[n..]).ret(v),
+        }
+    }
+
+    if last {
+        i.replace(&b[n..]).ret(v)
+    } else {
+        i.incomplete(1)
+    }
 }
 
-/// Parses a series of digits and converts them to an integer.
+/// Line endings recognized by `LineIndex`.
+#[derive(Copy, Clone, Debug, Eq, PartialEq, Hash)]
+pub enum LineEnding {
+    /// Lines end with `"\n"`, a preceding `"\r"` is counted as the last column of the line.
+    Lf,
+    /// Lines end with `"\r"`.
+    Cr,
+    /// Lines end with any of `"\r\n"`, `"\n"` or `"\r"`.
+    Any,
+}
+
+/// Position in the input.
+#[derive(Copy, Clone, Debug, Eq, PartialEq, Hash)]
+pub struct Position {
+    /// Line number, starting at 1.
+    pub line:   usize,
+    /// Column in bytes, starting at 1.
+    pub column: usize,
+    /// Offset in bytes from the start of the input.
+    pub offset: usize,
+}
+
+/// Index of the line starts of an input, used to convert offsets to line and column positions.
 ///
-/// # Note
+/// Parsers only see the remainder of the input, so the index is built from the input outside of
+/// the parser, either from the complete input up front using `new` or incrementally using
+/// `extend` as data is read. Offsets can be recorded while parsing using `parsers::offset` or the
+/// `position` parser.
 ///
-/// The `T` type must be larger than `u8` if it is signed.
+/// ```
+/// use chomp::ascii::{LineEnding, LineIndex, Position};
 ///
-/// # Example
+/// let index = LineIndex::new(b"foo\nbar\r\nbaz", LineEnding::Any);
 ///
+/// assert_eq!(index.position(9), Position { line: 3, column: 1, offset: 9 });
 /// ```
-/// use chomp::parse_only;
-/// use chomp::ascii::decimal;
 ///
-/// let r = parse_only(decimal::<u8>, b"123");
+/// When parsing from a `buffer::Source` the index is extended with the data added to the buffer
+/// by every fill, which requires automatic fill to be disabled:
 ///
-/// assert_eq!(r, Ok(123u8));
+/// ```
+/// use std::io;
+///
+/// use chomp::take;
+/// use chomp::ascii::{LineEnding, LineIndex, Position, position};
+/// use chomp::buffer::{FixedSizeBuffer, Source, Stream, StreamError};
+///
+/// let mut s     = Source::from_read(io::Cursor::new(&b"ab\ncd\nef\n"[..]), FixedSizeBuffer::with_size(4));
+/// let mut index = LineIndex::with_ending(LineEnding::Lf);
+/// let mut lines = Vec::new();
+///
+/// s.set_autofill(false);
+///
+/// loop {
+///     match s.parse(|i| position(i, &index).skip(|i| take(i, 3))) {
+///         Ok(p)                        => lines.push(p.line),
+///         Err(StreamError::Retry)      => {
+///             s.fill().unwrap();
+///
+///             // The buffer starts at the offset of the source, only index the new data
+///             let n = index.len() - s.offset();
+///
+///             index.extend(&s.buffer()[n..]);
+///         },
+///         Err(StreamError::EndOfInput) => break,
+///         Err(e)                       => panic!("{:?}", e),
+///     }
+/// }
+///
+/// assert_eq!(lines, vec![1, 2, 3]);
+/// ```
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct LineIndex {
+    /// Offsets of the first byte of every line
+    starts: Vec<usize>,
+    /// Line ending separating the lines
+    ending: LineEnding,
+    /// Number of bytes added to the index
+    len:    usize,
+    /// If the last byte added to the index is a `"\r"`
+    cr:     bool,
+}
+
+impl LineIndex {
+    /// Creates an empty index, data is added using `extend`.
+    pub fn with_ending(ending: LineEnding) -> Self {
+        LineIndex {
+            starts: vec![0],
+            ending: ending,
+            len:    0,
+            cr:     false,
+        }
+    }
+
+    /// Creates an index of the lines of `input`.
+    pub fn new(input: &[u8], ending: LineEnding) -> Self {
+        let mut index = LineIndex::with_ending(ending);
+
+        index.extend(input);
+
+        index
+    }
+
+    /// Adds `data` to the index, `data` directly follows the data previously added.
+    ///
+    /// Using `LineEnding::Any` a `"\r"` at the end of `data` is treated as a line ending until a
+    /// following `"\n"` is added.
+    pub fn extend(&mut self, data: &[u8]) {
+        for &c in data {
+            self.len += 1;
+
+            match (self.ending, c) {
+                (LineEnding::Lf,  b'\n') => self.starts.push(self.len),
+                (LineEnding::Cr,  b'\r') => self.starts.push(self.len),
+                (LineEnding::Any, b'\r') => self.starts.push(self.len),
+                // The line started by the preceding "\r" starts after the "\n" instead
+                (LineEnding::Any, b'\n') => if self.cr {
+                    *self.starts.last_mut().unwrap() = self.len
+                } else {
+                    self.starts.push(self.len)
+                },
+                _                        => (),
+            }
+
+            self.cr = c == b'\r';
+        }
+    }
+
+    /// Returns the number of bytes added to the index.
+    #[inline]
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    /// Returns true if no data has been added to the index.
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    /// Returns the position of `offset`. Offsets past the end of the indexed data are positioned
+    /// on the last line.
+    pub fn position(&self, offset: usize) -> Position {
+        let line = match self.starts.binary_search(&offset) {
+            Ok(n)  => n,
+            Err(n) => n - 1,
+        };
+
+        Position {
+            line:   line + 1,
+            column: offset - self.starts[line] + 1,
+            offset: offset,
+        }
+    }
+}
+
+/// Returns the current position in the input without consuming anything, using `index` which
+/// has to contain the input up to at least the current position.
+///
+/// ```
+/// use chomp::{parse_only, take};
+/// use chomp::ascii::{LineEnding, LineIndex, Position, position};
+///
+/// let input = b"foo\nbar";
+/// let index = LineIndex::new(input, LineEnding::Lf);
+///
+/// let r = parse_only(|i| take(i, 5).then(|i| position(i, &index)), input);
+///
+/// assert_eq!(r, Ok(Position { line: 2, column: 2, offset: 5 }));
 /// ```
 #[inline]
-pub fn decimal<T: Copy + ValueFrom<u8, Err=NoError> + Add<Output=T> + Mul<Output=T>>(i: Input<u8>) -> U8Result<T> {
-    take_while1(i, is_digit).map(to_decimal)
+pub fn position<'a>(i: Input<'a, u8>, index: &LineIndex) -> U8Result<'a, Position> {
+    let p = index.position(i.offset());
+
+    i.ret(p)
 }
 
 /// Internal function converting a `[u8]` to the given integer type `T`.
@@ -159,12 +1601,70 @@ pub fn decimal<T: Copy + ValueFrom<u8, Err=NoError> + Add<Output=T> + Mul<Output
 /// * The `T` type must be larger than `u8` if it is signed.
 #[inline]
 fn to_decimal<T: Copy + ValueFrom<u8, Err=NoError> + Add<Output=T> + Mul<Output=T>>(buf: &[u8]) -> T {
-    buf.iter().fold(T::value_from(0).unwrap_ok(), |a, n| a * T::value_from(10).unwrap_ok() + T::value_from(n - b'0').unwrap_ok())
+    to_radix(buf, 10)
+}
+
+/// Internal function converting a `[u8]` of digits in the given radix to the integer type `T`.
+///
+/// # Notes
+///
+/// * The slice must only contain digits from 0 up to, but not including, `radix`, where digits
+///   above 9 are either lowercase or uppercase letters.
+/// * The `radix` must be at most 16.
+/// * The `T` type must be larger than `u8` if it is signed.
+#[inline]
+fn to_radix<T: Copy + ValueFrom<u8, Err=NoError> + Add<Output=T> + Mul<Output=T>>(buf: &[u8], radix: u8) -> T {
+    fold_digits(buf.iter().cloned(), radix)
+}
+
+/// Internal function converting a `[u8]` of digits separated by `sep` in the given radix to the
+/// integer type `T`.
+///
+/// # Notes
+///
+/// * Same as for `to_radix`, except that the slice may also contain `sep`.
+#[inline]
+fn to_radix_separated<T: Copy + ValueFrom<u8, Err=NoError> + Add<Output=T> + Mul<Output=T>>(buf: &[u8], radix: u8, sep: u8) -> T {
+    fold_digits(buf.iter().cloned().filter(|&c| c != sep), radix)
+}
+
+/// Internal function folding the digits in the given radix into the integer type `T`.
+#[inline]
+fn fold_digits<T, D>(digits: D, radix: u8) -> T
+  where T: Copy + ValueFrom<u8, Err=NoError> + Add<Output=T> + Mul<Output=T>,
+        D: Iterator<Item=u8> {
+    // Convert the radix once instead of for every digit
+    let radix = T::value_from(radix).unwrap_ok();
+    let mut n = T::value_from(0).unwrap_ok();
+
+    for c in digits {
+        // Letters are folded to lowercase by setting the 0x20 bit
+        let d = if c <= b'9' { c - b'0' } else { (c | 0x20) - b'a' + 10 };
+
+        n = n * radix + T::value_from(d).unwrap_ok();
+    }
+
+    n
 }
 
 #[cfg(test)]
 mod test {
-    use super::to_decimal;
+    use super::{to_decimal, to_radix, hexadecimal, decimal_with_separator,
+                hexadecimal_with_separator, skip_line_comment, skip_block_comment, rest_of_line,
+                line_ending, any_line_ending, skip_line_endings, escape, keyword, quoted_string,
+                quoted_string_unescaped, lexeme, lexeme_with, skip_whitespace, digit_value,
+                hex_digit_value, signed_checked, octal, binary, float, float_exp, duration,
+                hex_bytes, hex_bytes_fixed, quoted_printable, take_till_byte, take_till_byte2,
+                take_till_byte3, take_until_bytes, take_while_class, take_while1_class, CharClass,
+                is_digit, is_hex_digit, is_oct_digit, is_bin_digit, is_lowercase, is_uppercase,
+                is_alpha, is_alphanumeric, is_whitespace, is_horizontal_space, LineEnding,
+                LineIndex, Position, position};
+
+    use std::time::Duration;
+
+    use primitives::input::{new, new_at, DEFAULT, END_OF_INPUT};
+    use primitives::{IntoInner, State};
+    use parsers::Error;
 
     macro_rules! test_to_decimal {
         ( $($n:ty),+ ) => { $(
@@ -182,4 +1682,496 @@ mod test {
     fn test_to_decimal_u8() {
         test_to_decimal!(u8, u16, u32, u64, i16, i32, i64);
     }
+
+    #[test]
+    fn test_to_radix() {
+        assert_eq!(to_radix::<u32>(b"", 2), 0);
+        assert_eq!(to_radix::<u32>(b"101", 2), 5);
+        assert_eq!(to_radix::<u32>(b"101", 8), 65);
+        assert_eq!(to_radix::<u32>(b"101", 10), 101);
+        assert_eq!(to_radix::<i64>(b"777", 8), 511);
+        assert_eq!(to_radix::<u32>(b"ff", 16), 255);
+        assert_eq!(to_radix::<u32>(b"aF09", 16), 0xaf09);
+    }
+
+    #[test]
+    fn lexeme_test() {
+        use parsers::{any, token};
+
+        assert_eq!(lexeme(new(END_OF_INPUT, b"a"), any).into_inner(), State::Data(new(END_OF_INPUT, b""), b'a'));
+        assert_eq!(lexeme(new(END_OF_INPUT, b"a \t\nb"), any).into_inner(), State::Data(new(END_OF_INPUT, b"b"), b'a'));
+        assert_eq!(lexeme(new(END_OF_INPUT, b"a b"), |i| token(i, b'b')).into_inner(), State::Error(&b"a b"[..], Error::expected(b'b')));
+        assert_eq!(lexeme(new(DEFAULT, b"a  "), any).into_inner(), State::Incomplete(1));
+        assert_eq!(lexeme(new(DEFAULT, b"a  b"), any).into_inner(), State::Data(new(DEFAULT, b"b"), b'a'));
+
+        assert_eq!(lexeme_with(new(END_OF_INPUT, b"a;;b"), any, |i| token(i, b';')).into_inner(), State::Data(new(END_OF_INPUT, b";b"), b'a'));
+        assert_eq!(lexeme_with(new(END_OF_INPUT, b"ab"), any, |i| token(i, b';')).into_inner(), State::Error(&b"b"[..], Error::expected(b';')));
+        assert_eq!(lexeme_with(new(END_OF_INPUT, b"a "), any, skip_whitespace).into_inner(), State::Data(new(END_OF_INPUT, b""), b'a'));
+    }
+
+    #[test]
+    fn keyword_test() {
+        assert_eq!(keyword(new(END_OF_INPUT, b"let"), b"let").into_inner(), State::Data(new(END_OF_INPUT, b""), &b"let"[..]));
+        assert_eq!(keyword(new(END_OF_INPUT, b"let x"), b"let").into_inner(), State::Data(new(END_OF_INPUT, b" x"), &b"let"[..]));
+        assert_eq!(keyword(new(END_OF_INPUT, b"let("), b"let").into_inner(), State::Data(new(END_OF_INPUT, b"("), &b"let"[..]));
+        assert_eq!(keyword(new(END_OF_INPUT, b"letter"), b"let").into_inner(), State::Error(&b"ter"[..], Error::unexpected()));
+        assert_eq!(keyword(new(END_OF_INPUT, b"let_"), b"let").into_inner(), State::Error(&b"_"[..], Error::unexpected()));
+        assert_eq!(keyword(new(END_OF_INPUT, b"let1"), b"let").into_inner(), State::Error(&b"1"[..], Error::unexpected()));
+        assert_eq!(keyword(new(END_OF_INPUT, b"lex"), b"let").into_inner(), State::Error(&b"x"[..], Error::expected(b'x')));
+
+        assert_eq!(keyword(new(DEFAULT, b"le"), b"let").into_inner(), State::Incomplete(1));
+        assert_eq!(keyword(new(DEFAULT, b"let"), b"let").into_inner(), State::Incomplete(1));
+        assert_eq!(keyword(new(DEFAULT, b"let "), b"let").into_inner(), State::Data(new(DEFAULT, b" "), &b"let"[..]));
+        assert_eq!(keyword(new(DEFAULT, b"lett"), b"let").into_inner(), State::Error(&b"t"[..], Error::unexpected()));
+    }
+
+    #[test]
+    fn line_ending_test() {
+        assert_eq!(line_ending(new(END_OF_INPUT, b"\n")).into_inner(), State::Data(new(END_OF_INPUT, b""), &b"\n"[..]));
+        assert_eq!(line_ending(new(END_OF_INPUT, b"\r\na")).into_inner(), State::Data(new(END_OF_INPUT, b"a"), &b"\r\n"[..]));
+        assert_eq!(line_ending(new(END_OF_INPUT, b"\n\r\n")).into_inner(), State::Data(new(END_OF_INPUT, b"\r\n"), &b"\n"[..]));
+        assert_eq!(line_ending(new(END_OF_INPUT, b"\ra")).into_inner(), State::Error(&b"a"[..], Error::expected(b'\n')));
+        assert_eq!(line_ending(new(END_OF_INPUT, b"a")).into_inner(), State::Error(&b"a"[..], Error::expected(b'\n')));
+        assert_eq!(line_ending(new(END_OF_INPUT, b"\r")).into_inner(), State::Incomplete(1));
+        assert_eq!(line_ending(new(DEFAULT, b"")).into_inner(), State::Incomplete(1));
+        assert_eq!(line_ending(new(DEFAULT, b"\r")).into_inner(), State::Incomplete(1));
+        assert_eq!(line_ending(new(DEFAULT, b"\n")).into_inner(), State::Data(new(DEFAULT, b""), &b"\n"[..]));
+    }
+
+    #[test]
+    fn any_line_ending_test() {
+        assert_eq!(any_line_ending(new(END_OF_INPUT, b"\n")).into_inner(), State::Data(new(END_OF_INPUT, b""), &b"\n"[..]));
+        assert_eq!(any_line_ending(new(END_OF_INPUT, b"\r\na")).into_inner(), State::Data(new(END_OF_INPUT, b"a"), &b"\r\n"[..]));
+        assert_eq!(any_line_ending(new(END_OF_INPUT, b"\ra")).into_inner(), State::Data(new(END_OF_INPUT, b"a"), &b"\r"[..]));
+        assert_eq!(any_line_ending(new(END_OF_INPUT, b"\r")).into_inner(), State::Data(new(END_OF_INPUT, b""), &b"\r"[..]));
+        assert_eq!(any_line_ending(new(END_OF_INPUT, b"\r\r\n")).into_inner(), State::Data(new(END_OF_INPUT, b"\r\n"), &b"\r"[..]));
+        assert_eq!(any_line_ending(new(END_OF_INPUT, b"a")).into_inner(), State::Error(&b"a"[..], Error::expected(b'\n')));
+        assert_eq!(any_line_ending(new(DEFAULT, b"")).into_inner(), State::Incomplete(1));
+        assert_eq!(any_line_ending(new(DEFAULT, b"\r")).into_inner(), State::Incomplete(1));
+
+        assert_eq!(skip_line_endings(new(END_OF_INPUT, b"")).into_inner(), State::Data(new(END_OF_INPUT, b""), ()));
+        assert_eq!(skip_line_endings(new(END_OF_INPUT, b"\n\r\n\ra")).into_inner(), State::Data(new(END_OF_INPUT, b"a"), ()));
+        assert_eq!(skip_line_endings(new(END_OF_INPUT, b"\n\r")).into_inner(), State::Data(new(END_OF_INPUT, b""), ()));
+        assert_eq!(skip_line_endings(new(DEFAULT, b"\n\r")).into_inner(), State::Incomplete(1));
+    }
+
+    #[test]
+    fn rest_of_line_test() {
+        assert_eq!(rest_of_line(new(END_OF_INPUT, b"\n")).into_inner(), State::Data(new(END_OF_INPUT, b""), &b""[..]));
+        assert_eq!(rest_of_line(new(END_OF_INPUT, b"\r\n")).into_inner(), State::Data(new(END_OF_INPUT, b""), &b""[..]));
+        assert_eq!(rest_of_line(new(END_OF_INPUT, b"abc\ndef")).into_inner(), State::Data(new(END_OF_INPUT, b"def"), &b"abc"[..]));
+        assert_eq!(rest_of_line(new(END_OF_INPUT, b"abc\r\ndef")).into_inner(), State::Data(new(END_OF_INPUT, b"def"), &b"abc"[..]));
+        assert_eq!(rest_of_line(new(END_OF_INPUT, b"a\rb\n")).into_inner(), State::Data(new(END_OF_INPUT, b""), &b"a\rb"[..]));
+        assert_eq!(rest_of_line(new(END_OF_INPUT, b"abc")).into_inner(), State::Data(new(END_OF_INPUT, b""), &b"abc"[..]));
+        assert_eq!(rest_of_line(new(END_OF_INPUT, b"")).into_inner(), State::Incomplete(1));
+
+        assert_eq!(rest_of_line(new(DEFAULT, b"")).into_inner(), State::Incomplete(1));
+        assert_eq!(rest_of_line(new(DEFAULT, b"abc")).into_inner(), State::Incomplete(1));
+        assert_eq!(rest_of_line(new(DEFAULT, b"abc\r")).into_inner(), State::Incomplete(1));
+        assert_eq!(rest_of_line(new(DEFAULT, b"abc\r\n")).into_inner(), State::Data(new(DEFAULT, b""), &b"abc"[..]));
+    }
+
+    #[test]
+    fn take_till_byte_test() {
+        assert_eq!(take_till_byte(new(DEFAULT, b"abc"), b'b').into_inner(), State::Data(new(DEFAULT, b"bc"), &b"a"[..]));
+        assert_eq!(take_till_byte(new(DEFAULT, b"abc"), b'a').into_inner(), State::Data(new(DEFAULT, b"abc"), &b""[..]));
+        assert_eq!(take_till_byte(new(DEFAULT, b"abc"), b'd').into_inner(), State::Incomplete(1));
+        assert_eq!(take_till_byte(new(END_OF_INPUT, b""), b'd').into_inner(), State::Incomplete(1));
+
+        assert_eq!(take_till_byte2(new(DEFAULT, b"abc"), b'c', b'b').into_inner(), State::Data(new(DEFAULT, b"bc"), &b"a"[..]));
+        assert_eq!(take_till_byte2(new(DEFAULT, b"abc"), b'd', b'e').into_inner(), State::Incomplete(1));
+
+        assert_eq!(take_till_byte3(new(DEFAULT, b"abcd"), b'd', b'e', b'c').into_inner(), State::Data(new(DEFAULT, b"cd"), &b"ab"[..]));
+        assert_eq!(take_till_byte3(new(DEFAULT, b"abc"), b'd', b'e', b'f').into_inner(), State::Incomplete(1));
+    }
+
+    #[test]
+    fn char_class_test() {
+        let classes: [(CharClass, fn(u8) -> bool); 10] = [
+            (CharClass::Digit,           is_digit),
+            (CharClass::HexDigit,        is_hex_digit),
+            (CharClass::OctDigit,        is_oct_digit),
+            (CharClass::BinDigit,        is_bin_digit),
+            (CharClass::Lowercase,       is_lowercase),
+            (CharClass::Uppercase,       is_uppercase),
+            (CharClass::Alpha,           is_alpha),
+            (CharClass::Alphanumeric,    is_alphanumeric),
+            (CharClass::Whitespace,      is_whitespace),
+            (CharClass::HorizontalSpace, is_horizontal_space),
+        ];
+
+        for &(class, f) in classes.iter() {
+            let all: Vec<u8> = (0..256).map(|c| c as u8).collect();
+
+            for &c in all.iter() {
+                assert_eq!(class.contains(c), f(c), "{:?} {}", class, c);
+            }
+
+            let prefix: Vec<u8> = all.iter().cloned().filter(|&d| f(d)).cycle().take(33).collect();
+
+            // Place every byte around the vector widths to exercise both the vectorized and scalar
+            // paths
+            for &c in all.iter() {
+                for &n in [0, 1, 15, 16, 17, 31, 32, 33].iter() {
+                    let mut b = prefix[..n].to_vec();
+
+                    b.push(c);
+
+                    let m = if f(c) { n + 1 } else { n };
+
+                    assert_eq!(take_while_class(new(DEFAULT, &b), class).into_inner(),
+                               if m < b.len() { State::Data(new(DEFAULT, &b[m..]), &b[..m]) } else { State::Incomplete(1) });
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn take_while_class_test() {
+        assert_eq!(take_while_class(new(DEFAULT, b"123a"), CharClass::Digit).into_inner(), State::Data(new(DEFAULT, b"a"), &b"123"[..]));
+        assert_eq!(take_while_class(new(DEFAULT, b"a"), CharClass::Digit).into_inner(), State::Data(new(DEFAULT, b"a"), &b""[..]));
+        assert_eq!(take_while_class(new(DEFAULT, b"123"), CharClass::Digit).into_inner(), State::Incomplete(1));
+        assert_eq!(take_while_class(new(END_OF_INPUT, b"123"), CharClass::Digit).into_inner(), State::Data(new(END_OF_INPUT, b""), &b"123"[..]));
+        assert_eq!(take_while_class(new(END_OF_INPUT, b""), CharClass::Digit).into_inner(), State::Data(new(END_OF_INPUT, b""), &b""[..]));
+
+        assert_eq!(take_while1_class(new(DEFAULT, b" \t a"), CharClass::Whitespace).into_inner(), State::Data(new(DEFAULT, b"a"), &b" \t "[..]));
+        assert_eq!(take_while1_class(new(DEFAULT, b"a"), CharClass::Whitespace).into_inner(), State::Error(&b"a"[..], Error::unexpected()));
+        assert_eq!(take_while1_class(new(DEFAULT, b"  "), CharClass::Whitespace).into_inner(), State::Incomplete(1));
+        assert_eq!(take_while1_class(new(END_OF_INPUT, b"  "), CharClass::Whitespace).into_inner(), State::Data(new(END_OF_INPUT, b""), &b"  "[..]));
+        assert_eq!(take_while1_class(new(END_OF_INPUT, b""), CharClass::Whitespace).into_inner(), State::Incomplete(1));
+    }
+
+    #[test]
+    fn line_index_test() {
+        let p = |line, column, offset| Position { line: line, column: column, offset: offset };
+        let b = b"a\nb\r\nc\rd";
+
+        let index = LineIndex::new(b, LineEnding::Lf);
+        assert_eq!(index.position(0), p(1, 1, 0));
+        assert_eq!(index.position(1), p(1, 2, 1));
+        assert_eq!(index.position(2), p(2, 1, 2));
+        assert_eq!(index.position(4), p(2, 3, 4));
+        assert_eq!(index.position(5), p(3, 1, 5));
+        assert_eq!(index.position(8), p(3, 4, 8));
+        assert_eq!(index.position(20), p(3, 16, 20));
+
+        let index = LineIndex::new(b, LineEnding::Cr);
+        assert_eq!(index.position(2), p(1, 3, 2));
+        assert_eq!(index.position(4), p(2, 1, 4));
+        assert_eq!(index.position(7), p(3, 1, 7));
+
+        let index = LineIndex::new(b, LineEnding::Any);
+        assert_eq!(index.position(3), p(2, 2, 3));
+        assert_eq!(index.position(5), p(3, 1, 5));
+        assert_eq!(index.position(6), p(3, 2, 6));
+        assert_eq!(index.position(7), p(4, 1, 7));
+
+        let index = LineIndex::new(b"", LineEnding::Any);
+        assert_eq!(index.position(0), p(1, 1, 0));
+
+        // Incrementally with "\r\n" split across two chunks
+        let mut index = LineIndex::with_ending(LineEnding::Any);
+        assert!(index.is_empty());
+        index.extend(&b[..4]);
+        assert_eq!(index.position(4), p(3, 1, 4));
+        index.extend(&b[4..]);
+        assert_eq!(index.len(), b.len());
+        assert_eq!(index, LineIndex::new(b, LineEnding::Any));
+        assert_eq!(index.position(4), p(2, 3, 4));
+
+        let index = LineIndex::new(b, LineEnding::Lf);
+        assert_eq!(position(new_at(END_OF_INPUT, 5, b"c"), &index).into_inner(), State::Data(new(END_OF_INPUT, b"c"), p(3, 1, 5)));
+    }
+
+    #[test]
+    fn take_until_bytes_test() {
+        assert_eq!(take_until_bytes(new(DEFAULT, b"abcd"), b"cd").into_inner(), State::Data(new(DEFAULT, b"cd"), &b"ab"[..]));
+        assert_eq!(take_until_bytes(new(DEFAULT, b"cdab"), b"cd").into_inner(), State::Data(new(DEFAULT, b"cdab"), &b""[..]));
+        assert_eq!(take_until_bytes(new(DEFAULT, b"abcd"), b"").into_inner(), State::Data(new(DEFAULT, b"abcd"), &b""[..]));
+        assert_eq!(take_until_bytes(new(DEFAULT, b"a\r\r\n\r\n"), b"\r\n\r\n").into_inner(), State::Data(new(DEFAULT, b"\r\n\r\n"), &b"a\r"[..]));
+        assert_eq!(take_until_bytes(new(DEFAULT, b"abc"), b"cd").into_inner(), State::Incomplete(1));
+        assert_eq!(take_until_bytes(new(DEFAULT, b"abc"), b"de").into_inner(), State::Incomplete(2));
+        assert_eq!(take_until_bytes(new(DEFAULT, b""), b"de").into_inner(), State::Incomplete(2));
+        assert_eq!(take_until_bytes(new(DEFAULT, b"a\r\n\r"), b"\r\n\r\n").into_inner(), State::Incomplete(1));
+        assert_eq!(take_until_bytes(new(DEFAULT, b"a\r\n"), b"\r\n\r\n").into_inner(), State::Incomplete(2));
+        assert_eq!(take_until_bytes(new(END_OF_INPUT, b"abc"), b"cd").into_inner(), State::Incomplete(1));
+    }
+
+    #[test]
+    fn skip_line_comment_test() {
+        assert_eq!(skip_line_comment(new(END_OF_INPUT, b"#"), b"#").into_inner(), State::Data(new(END_OF_INPUT, b""), ()));
+        assert_eq!(skip_line_comment(new(END_OF_INPUT, b"# foo"), b"#").into_inner(), State::Data(new(END_OF_INPUT, b""), ()));
+        assert_eq!(skip_line_comment(new(END_OF_INPUT, b"// foo\r\nbar"), b"//").into_inner(), State::Data(new(END_OF_INPUT, b"\nbar"), ()));
+        assert_eq!(skip_line_comment(new(END_OF_INPUT, b"/ foo"), b"//").into_inner(), State::Error(&b" foo"[..], Error::expected(b' ')));
+
+        assert_eq!(skip_line_comment(new(DEFAULT, b"/"), b"//").into_inner(), State::Incomplete(1));
+        assert_eq!(skip_line_comment(new(DEFAULT, b"// foo"), b"//").into_inner(), State::Incomplete(1));
+        assert_eq!(skip_line_comment(new(DEFAULT, b"// foo\n"), b"//").into_inner(), State::Data(new(DEFAULT, b"\n"), ()));
+    }
+
+    #[test]
+    fn skip_block_comment_test() {
+        assert_eq!(skip_block_comment(new(END_OF_INPUT, b"/**/"), b"/*", b"*/", false).into_inner(), State::Data(new(END_OF_INPUT, b""), ()));
+        assert_eq!(skip_block_comment(new(END_OF_INPUT, b"/* a */b"), b"/*", b"*/", false).into_inner(), State::Data(new(END_OF_INPUT, b"b"), ()));
+        assert_eq!(skip_block_comment(new(END_OF_INPUT, b"/* /* a */ */b"), b"/*", b"*/", false).into_inner(), State::Data(new(END_OF_INPUT, b" */b"), ()));
+        assert_eq!(skip_block_comment(new(END_OF_INPUT, b"/* /* a */ */b"), b"/*", b"*/", true).into_inner(), State::Data(new(END_OF_INPUT, b"b"), ()));
+        assert_eq!(skip_block_comment(new(END_OF_INPUT, b"/*/ */"), b"/*", b"*/", true).into_inner(), State::Data(new(END_OF_INPUT, b""), ()));
+        assert_eq!(skip_block_comment(new(END_OF_INPUT, b"{- {- -} -}"), b"{-", b"-}", true).into_inner(), State::Data(new(END_OF_INPUT, b""), ()));
+        assert_eq!(skip_block_comment(new(END_OF_INPUT, b"/ a */"), b"/*", b"*/", true).into_inner(), State::Error(&b" a */"[..], Error::expected(b' ')));
+        assert_eq!(skip_block_comment(new(END_OF_INPUT, b"/* a "), b"/*", b"*/", false).into_inner(), State::Incomplete(1));
+        assert_eq!(skip_block_comment(new(END_OF_INPUT, b"/* /* a */"), b"/*", b"*/", true).into_inner(), State::Incomplete(1));
+
+        assert_eq!(skip_block_comment(new(DEFAULT, b"/* a *"), b"/*", b"*/", false).into_inner(), State::Incomplete(1));
+        assert_eq!(skip_block_comment(new(DEFAULT, b"/* a */"), b"/*", b"*/", false).into_inner(), State::Data(new(DEFAULT, b""), ()));
+    }
+
+    #[test]
+    fn escape_test() {
+        assert_eq!(escape(new(END_OF_INPUT, b"\\n")).into_inner(), State::Data(new(END_OF_INPUT, b""), '\n'));
+        assert_eq!(escape(new(END_OF_INPUT, b"\\r")).into_inner(), State::Data(new(END_OF_INPUT, b""), '\r'));
+        assert_eq!(escape(new(END_OF_INPUT, b"\\tb")).into_inner(), State::Data(new(END_OF_INPUT, b"b"), '\t'));
+        assert_eq!(escape(new(END_OF_INPUT, b"\\0")).into_inner(), State::Data(new(END_OF_INPUT, b""), '\0'));
+        assert_eq!(escape(new(END_OF_INPUT, b"\\\\")).into_inner(), State::Data(new(END_OF_INPUT, b""), '\\'));
+        assert_eq!(escape(new(END_OF_INPUT, b"\\'")).into_inner(), State::Data(new(END_OF_INPUT, b""), '\''));
+        assert_eq!(escape(new(END_OF_INPUT, b"\\\"")).into_inner(), State::Data(new(END_OF_INPUT, b""), '"'));
+        assert_eq!(escape(new(END_OF_INPUT, b"\\q")).into_inner(), State::Error(&b"q"[..], Error::unexpected()));
+        assert_eq!(escape(new(END_OF_INPUT, b"n")).into_inner(), State::Error(&b"n"[..], Error::expected(b'\\')));
+
+        assert_eq!(escape(new(END_OF_INPUT, b"\\x41")).into_inner(), State::Data(new(END_OF_INPUT, b""), 'A'));
+        assert_eq!(escape(new(END_OF_INPUT, b"\\x7f1")).into_inner(), State::Data(new(END_OF_INPUT, b"1"), '\x7f'));
+        assert_eq!(escape(new(END_OF_INPUT, b"\\x80")).into_inner(), State::Error(&b""[..], Error::unexpected()));
+        assert_eq!(escape(new(END_OF_INPUT, b"\\x4g")).into_inner(), State::Error(&b"g"[..], Error::unexpected()));
+        assert_eq!(escape(new(END_OF_INPUT, b"\\xg4")).into_inner(), State::Error(&b"g4"[..], Error::unexpected()));
+
+        assert_eq!(escape(new(END_OF_INPUT, b"\\u{41}")).into_inner(), State::Data(new(END_OF_INPUT, b""), 'A'));
+        assert_eq!(escape(new(END_OF_INPUT, b"\\u{10FFFF}")).into_inner(), State::Data(new(END_OF_INPUT, b""), '\u{10FFFF}'));
+        assert_eq!(escape(new(END_OF_INPUT, b"\\u{110000}")).into_inner(), State::Error(&b""[..], Error::unexpected()));
+        assert_eq!(escape(new(END_OF_INPUT, b"\\u{d800}")).into_inner(), State::Error(&b""[..], Error::unexpected()));
+        assert_eq!(escape(new(END_OF_INPUT, b"\\u{0000041}")).into_inner(), State::Error(&b"1}"[..], Error::expected(b'}')));
+        assert_eq!(escape(new(END_OF_INPUT, b"\\u{}")).into_inner(), State::Error(&b"}"[..], Error::unexpected()));
+        assert_eq!(escape(new(END_OF_INPUT, b"\\u{4g}")).into_inner(), State::Error(&b"g}"[..], Error::expected(b'}')));
+        assert_eq!(escape(new(END_OF_INPUT, b"\\u41")).into_inner(), State::Error(&b"41"[..], Error::expected(b'{')));
+
+        assert_eq!(escape(new(DEFAULT, b"\\")).into_inner(), State::Incomplete(1));
+        assert_eq!(escape(new(DEFAULT, b"\\x4")).into_inner(), State::Incomplete(1));
+        assert_eq!(escape(new(DEFAULT, b"\\u{41")).into_inner(), State::Incomplete(1));
+    }
+
+    #[test]
+    fn quoted_string_test() {
+        assert_eq!(quoted_string(new(END_OF_INPUT, b"\"\""), b'"').into_inner(), State::Data(new(END_OF_INPUT, b""), &b""[..]));
+        assert_eq!(quoted_string(new(END_OF_INPUT, b"\"abc\" "), b'"').into_inner(), State::Data(new(END_OF_INPUT, b" "), &b"abc"[..]));
+        assert_eq!(quoted_string(new(END_OF_INPUT, b"'a\\'b'"), b'\'').into_inner(), State::Data(new(END_OF_INPUT, b""), &b"a\\'b"[..]));
+        assert_eq!(quoted_string(new(END_OF_INPUT, b"\"a\\\\\"b"), b'"').into_inner(), State::Data(new(END_OF_INPUT, b"b"), &b"a\\\\"[..]));
+        assert_eq!(quoted_string(new(END_OF_INPUT, b"abc"), b'"').into_inner(), State::Error(&b"abc"[..], Error::expected(b'"')));
+
+        assert_eq!(quoted_string(new(DEFAULT, b""), b'"').into_inner(), State::Incomplete(1));
+        assert_eq!(quoted_string(new(DEFAULT, b"\"ab"), b'"').into_inner(), State::Incomplete(1));
+        assert_eq!(quoted_string(new(DEFAULT, b"\"ab\\"), b'"').into_inner(), State::Incomplete(2));
+        assert_eq!(quoted_string(new(END_OF_INPUT, b"\"ab\\\""), b'"').into_inner(), State::Incomplete(1));
+    }
+
+    #[test]
+    fn quoted_string_unescaped_test() {
+        assert_eq!(quoted_string_unescaped(new(END_OF_INPUT, b"\"\""), b'"').into_inner(), State::Data(new(END_OF_INPUT, b""), vec![]));
+        assert_eq!(quoted_string_unescaped(new(END_OF_INPUT, b"\"abc\" "), b'"').into_inner(), State::Data(new(END_OF_INPUT, b" "), b"abc".to_vec()));
+        assert_eq!(quoted_string_unescaped(new(END_OF_INPUT, b"\"a\\n\\r\\t\\0\\\\\\'\\\"b\""), b'"').into_inner(), State::Data(new(END_OF_INPUT, b""), b"a\n\r\t\0\\'\"b".to_vec()));
+        assert_eq!(quoted_string_unescaped(new(END_OF_INPUT, b"'\\''"), b'\'').into_inner(), State::Data(new(END_OF_INPUT, b""), b"'".to_vec()));
+        assert_eq!(quoted_string_unescaped(new(END_OF_INPUT, b"\"\\x41\\u{e5}\\u{1F600}\""), b'"').into_inner(), State::Data(new(END_OF_INPUT, b""), "A\u{e5}\u{1F600}".as_bytes().to_vec()));
+        assert_eq!(quoted_string_unescaped(new(END_OF_INPUT, b"\"\\x80\""), b'"').into_inner(), State::Error(&b"\""[..], Error::unexpected()));
+        assert_eq!(quoted_string_unescaped(new(END_OF_INPUT, b"\"a\\qb\""), b'"').into_inner(), State::Error(&b"qb\""[..], Error::unexpected()));
+        assert_eq!(quoted_string_unescaped(new(END_OF_INPUT, b"abc"), b'"').into_inner(), State::Error(&b"abc"[..], Error::expected(b'"')));
+
+        assert_eq!(quoted_string_unescaped(new(DEFAULT, b""), b'"').into_inner(), State::Incomplete(1));
+        assert_eq!(quoted_string_unescaped(new(DEFAULT, b"\"ab"), b'"').into_inner(), State::Incomplete(1));
+        assert_eq!(quoted_string_unescaped(new(DEFAULT, b"\"ab\\"), b'"').into_inner(), State::Incomplete(1));
+        assert_eq!(quoted_string_unescaped(new(DEFAULT, b"\"ab\\\""), b'"').into_inner(), State::Incomplete(1));
+    }
+
+    #[test]
+    fn digit_value_test() {
+        assert_eq!(digit_value(new(END_OF_INPUT, b"0")).into_inner(), State::Data(new(END_OF_INPUT, b""), 0));
+        assert_eq!(digit_value(new(END_OF_INPUT, b"91")).into_inner(), State::Data(new(END_OF_INPUT, b"1"), 9));
+        assert_eq!(digit_value(new(END_OF_INPUT, b"a")).into_inner(), State::Error(&b"a"[..], Error::unexpected()));
+        assert_eq!(digit_value(new(DEFAULT, b"")).into_inner(), State::Incomplete(1));
+
+        let digits = b"0123456789abcdefABCDEF";
+        let values = [0, 1, 2, 3, 4, 5, 6, 7, 8, 9, 10, 11, 12, 13, 14, 15, 10, 11, 12, 13, 14, 15];
+
+        for (d, &v) in digits.iter().zip(values.iter()) {
+            assert_eq!(hex_digit_value(new(END_OF_INPUT, &[*d])).into_inner(), State::Data(new(END_OF_INPUT, b""), v));
+        }
+
+        assert_eq!(hex_digit_value(new(END_OF_INPUT, b"g")).into_inner(), State::Error(&b"g"[..], Error::unexpected()));
+        assert_eq!(hex_digit_value(new(END_OF_INPUT, b"G")).into_inner(), State::Error(&b"G"[..], Error::unexpected()));
+        assert_eq!(hex_digit_value(new(DEFAULT, b"")).into_inner(), State::Incomplete(1));
+    }
+
+    #[test]
+    fn signed_checked_test() {
+        assert_eq!(signed_checked::<i8>(new(END_OF_INPUT, b"0")).into_inner(), State::Data(new(END_OF_INPUT, b""), 0));
+        assert_eq!(signed_checked::<i8>(new(END_OF_INPUT, b"-0")).into_inner(), State::Data(new(END_OF_INPUT, b""), 0));
+        assert_eq!(signed_checked::<i8>(new(END_OF_INPUT, b"-128")).into_inner(), State::Data(new(END_OF_INPUT, b""), -128));
+        assert_eq!(signed_checked::<i8>(new(END_OF_INPUT, b"+127;")).into_inner(), State::Data(new(END_OF_INPUT, b";"), 127));
+        assert_eq!(signed_checked::<i64>(new(END_OF_INPUT, b"-9223372036854775808")).into_inner(), State::Data(new(END_OF_INPUT, b""), ::std::i64::MIN));
+        assert_eq!(signed_checked::<u8>(new(END_OF_INPUT, b"255")).into_inner(), State::Data(new(END_OF_INPUT, b""), 255));
+
+        assert_eq!(signed_checked::<i8>(new(END_OF_INPUT, b"128")).into_inner(), State::Error(&b""[..], Error::unexpected()));
+        assert_eq!(signed_checked::<i8>(new(END_OF_INPUT, b"-129 ")).into_inner(), State::Error(&b" "[..], Error::unexpected()));
+        assert_eq!(signed_checked::<u8>(new(END_OF_INPUT, b"-1")).into_inner(), State::Error(&b""[..], Error::unexpected()));
+        assert_eq!(signed_checked::<u8>(new(END_OF_INPUT, b"256")).into_inner(), State::Error(&b""[..], Error::unexpected()));
+        assert_eq!(signed_checked::<i8>(new(END_OF_INPUT, b"-a")).into_inner(), State::Error(&b"a"[..], Error::unexpected()));
+
+        assert_eq!(signed_checked::<i8>(new(DEFAULT, b"-12")).into_inner(), State::Incomplete(1));
+    }
+
+    #[test]
+    fn octal_binary_test() {
+        assert_eq!(octal::<u16>(new(END_OF_INPUT, b"0")).into_inner(), State::Data(new(END_OF_INPUT, b""), 0));
+        assert_eq!(octal::<u16>(new(END_OF_INPUT, b"0644")).into_inner(), State::Data(new(END_OF_INPUT, b""), 0o644));
+        assert_eq!(octal::<u32>(new(END_OF_INPUT, b"178")).into_inner(), State::Data(new(END_OF_INPUT, b"8"), 0o17));
+        assert_eq!(octal::<u32>(new(END_OF_INPUT, b"8")).into_inner(), State::Error(&b"8"[..], Error::unexpected()));
+        assert_eq!(octal::<u32>(new(DEFAULT, b"17")).into_inner(), State::Incomplete(1));
+
+        assert_eq!(binary::<u8>(new(END_OF_INPUT, b"0")).into_inner(), State::Data(new(END_OF_INPUT, b""), 0));
+        assert_eq!(binary::<u8>(new(END_OF_INPUT, b"11111111")).into_inner(), State::Data(new(END_OF_INPUT, b""), 255));
+        assert_eq!(binary::<i32>(new(END_OF_INPUT, b"1012")).into_inner(), State::Data(new(END_OF_INPUT, b"2"), 5));
+        assert_eq!(binary::<u8>(new(END_OF_INPUT, b"2")).into_inner(), State::Error(&b"2"[..], Error::unexpected()));
+        assert_eq!(binary::<u8>(new(DEFAULT, b"10")).into_inner(), State::Incomplete(1));
+    }
+
+    #[test]
+    fn hexadecimal_test() {
+        assert_eq!(hexadecimal::<u8>(new(END_OF_INPUT, b"0")).into_inner(), State::Data(new(END_OF_INPUT, b""), 0));
+        assert_eq!(hexadecimal::<u8>(new(END_OF_INPUT, b"fF")).into_inner(), State::Data(new(END_OF_INPUT, b""), 255));
+        assert_eq!(hexadecimal::<u32>(new(END_OF_INPUT, b"0a9Bg")).into_inner(), State::Data(new(END_OF_INPUT, b"g"), 0xa9b));
+        assert_eq!(hexadecimal::<u32>(new(END_OF_INPUT, b"g")).into_inner(), State::Error(&b"g"[..], Error::unexpected()));
+        assert_eq!(hexadecimal::<u32>(new(DEFAULT, b"ab")).into_inner(), State::Incomplete(1));
+    }
+
+    #[test]
+    fn with_separator_test() {
+        assert_eq!(decimal_with_separator::<u32>(new(END_OF_INPUT, b"1"), b'_').into_inner(), State::Data(new(END_OF_INPUT, b""), 1));
+        assert_eq!(decimal_with_separator::<u32>(new(END_OF_INPUT, b"1_000_000"), b'_').into_inner(), State::Data(new(END_OF_INPUT, b""), 1000000));
+        assert_eq!(decimal_with_separator::<u32>(new(END_OF_INPUT, b"1'0'0 "), b'\'').into_inner(), State::Data(new(END_OF_INPUT, b" "), 100));
+        assert_eq!(decimal_with_separator::<u32>(new(END_OF_INPUT, b"12a"), b'_').into_inner(), State::Data(new(END_OF_INPUT, b"a"), 12));
+        assert_eq!(decimal_with_separator::<u32>(new(END_OF_INPUT, b"_1"), b'_').into_inner(), State::Error(&b"_1"[..], Error::unexpected()));
+        assert_eq!(decimal_with_separator::<u32>(new(END_OF_INPUT, b"1__0"), b'_').into_inner(), State::Error(&b"_0"[..], Error::unexpected()));
+        assert_eq!(decimal_with_separator::<u32>(new(END_OF_INPUT, b"1_ "), b'_').into_inner(), State::Error(&b" "[..], Error::unexpected()));
+        assert_eq!(decimal_with_separator::<u32>(new(END_OF_INPUT, b"1_"), b'_').into_inner(), State::Error(&b""[..], Error::unexpected()));
+
+        assert_eq!(decimal_with_separator::<u32>(new(DEFAULT, b""), b'_').into_inner(), State::Incomplete(1));
+        assert_eq!(decimal_with_separator::<u32>(new(DEFAULT, b"1_0"), b'_').into_inner(), State::Incomplete(1));
+        assert_eq!(decimal_with_separator::<u32>(new(DEFAULT, b"1_"), b'_').into_inner(), State::Incomplete(1));
+        assert_eq!(decimal_with_separator::<u32>(new(DEFAULT, b"1_0;"), b'_').into_inner(), State::Data(new(DEFAULT, b";"), 10));
+
+        assert_eq!(hexadecimal_with_separator::<u32>(new(END_OF_INPUT, b"dead_BEEF"), b'_').into_inner(), State::Data(new(END_OF_INPUT, b""), 0xdeadbeef));
+        assert_eq!(hexadecimal_with_separator::<u32>(new(END_OF_INPUT, b"f_f_g"), b'_').into_inner(), State::Error(&b"g"[..], Error::unexpected()));
+        assert_eq!(hexadecimal_with_separator::<u32>(new(END_OF_INPUT, b"ff g"), b'_').into_inner(), State::Data(new(END_OF_INPUT, b" g"), 255));
+    }
+
+    #[test]
+    fn duration_test() {
+        assert_eq!(duration(new(END_OF_INPUT, b"0s")).into_inner(), State::Data(new(END_OF_INPUT, b""), Duration::from_secs(0)));
+        assert_eq!(duration(new(END_OF_INPUT, b"2h")).into_inner(), State::Data(new(END_OF_INPUT, b""), Duration::from_secs(7200)));
+        assert_eq!(duration(new(END_OF_INPUT, b"1h30m")).into_inner(), State::Data(new(END_OF_INPUT, b""), Duration::from_secs(5400)));
+        assert_eq!(duration(new(END_OF_INPUT, b"250ms")).into_inner(), State::Data(new(END_OF_INPUT, b""), Duration::from_millis(250)));
+        assert_eq!(duration(new(END_OF_INPUT, b"2.5s")).into_inner(), State::Data(new(END_OF_INPUT, b""), Duration::from_millis(2500)));
+        assert_eq!(duration(new(END_OF_INPUT, b"1.5m1s")).into_inner(), State::Data(new(END_OF_INPUT, b""), Duration::from_secs(91)));
+        assert_eq!(duration(new(END_OF_INPUT, b"3us4ns")).into_inner(), State::Data(new(END_OF_INPUT, b""), Duration::new(0, 3004)));
+        assert_eq!(duration(new(END_OF_INPUT, "7µs".as_bytes())).into_inner(), State::Data(new(END_OF_INPUT, b""), Duration::new(0, 7000)));
+        assert_eq!(duration(new(END_OF_INPUT, b"0.1234567891s")).into_inner(), State::Data(new(END_OF_INPUT, b""), Duration::new(0, 123456789)));
+        assert_eq!(duration(new(END_OF_INPUT, b"1s ")).into_inner(), State::Data(new(END_OF_INPUT, b" "), Duration::from_secs(1)));
+        assert_eq!(duration(new(END_OF_INPUT, b"1m")).into_inner(), State::Data(new(END_OF_INPUT, b""), Duration::from_secs(60)));
+        assert_eq!(duration(new(END_OF_INPUT, b"18446744073709551615s")).into_inner(), State::Data(new(END_OF_INPUT, b""), Duration::from_secs(18446744073709551615)));
+
+        assert_eq!(duration(new(END_OF_INPUT, b"18446744073709551616s")).into_inner(), State::Error(&b""[..], Error::unexpected()));
+        assert_eq!(duration(new(END_OF_INPUT, b"340282366920938463463374607431768211.999us")).into_inner(), State::Error(&b""[..], Error::unexpected()));
+        assert_eq!(duration(new(END_OF_INPUT, b"1d")).into_inner(), State::Error(&b"d"[..], Error::unexpected()));
+        assert_eq!(duration(new(END_OF_INPUT, b"s")).into_inner(), State::Error(&b"s"[..], Error::unexpected()));
+        assert_eq!(duration(new(END_OF_INPUT, b"1")).into_inner(), State::Incomplete(1));
+
+        assert_eq!(duration(new(DEFAULT, b"1")).into_inner(), State::Incomplete(1));
+        assert_eq!(duration(new(DEFAULT, b"1m")).into_inner(), State::Incomplete(1));
+        assert_eq!(duration(new(DEFAULT, b"1s")).into_inner(), State::Incomplete(1));
+        assert_eq!(duration(new(DEFAULT, b"1s;")).into_inner(), State::Data(new(DEFAULT, b";"), Duration::from_secs(1)));
+    }
+
+    #[test]
+    fn hex_bytes_test() {
+        assert_eq!(hex_bytes(new(END_OF_INPUT, b"00")).into_inner(), State::Data(new(END_OF_INPUT, b""), vec![0]));
+        assert_eq!(hex_bytes(new(END_OF_INPUT, b"0aFf10 ")).into_inner(), State::Data(new(END_OF_INPUT, b" "), vec![0x0a, 0xff, 0x10]));
+        assert_eq!(hex_bytes(new(END_OF_INPUT, b"abc")).into_inner(), State::Error(&b""[..], Error::unexpected()));
+        assert_eq!(hex_bytes(new(END_OF_INPUT, b"abcg")).into_inner(), State::Error(&b"g"[..], Error::unexpected()));
+        assert_eq!(hex_bytes(new(END_OF_INPUT, b"g")).into_inner(), State::Error(&b"g"[..], Error::unexpected()));
+        assert_eq!(hex_bytes(new(DEFAULT, b"ab")).into_inner(), State::Incomplete(1));
+
+        let r: State<_, [u8; 2], _> = hex_bytes_fixed(new(END_OF_INPUT, b"0aFf10")).into_inner();
+        assert_eq!(r, State::Data(new(END_OF_INPUT, b"10"), [0x0a, 0xff]));
+        let r: State<_, [u8; 0], _> = hex_bytes_fixed(new(END_OF_INPUT, b"")).into_inner();
+        assert_eq!(r, State::Data(new(END_OF_INPUT, b""), []));
+        let r: State<_, [u8; 2], _> = hex_bytes_fixed(new(END_OF_INPUT, b"0ag")).into_inner();
+        assert_eq!(r, State::Error(&b"g"[..], Error::unexpected()));
+        let r: State<_, [u8; 2], _> = hex_bytes_fixed(new(END_OF_INPUT, b"0ag0")).into_inner();
+        assert_eq!(r, State::Error(&b"g0"[..], Error::unexpected()));
+        let r: State<_, [u8; 2], _> = hex_bytes_fixed(new(DEFAULT, b"0aF")).into_inner();
+        assert_eq!(r, State::Incomplete(1));
+        let r: State<_, [u8; 2], _> = hex_bytes_fixed(new(END_OF_INPUT, b"0")).into_inner();
+        assert_eq!(r, State::Incomplete(3));
+    }
+
+    #[test]
+    fn quoted_printable_test() {
+        assert_eq!(quoted_printable(new(END_OF_INPUT, b"")).into_inner(), State::Data(new(END_OF_INPUT, b""), vec![]));
+        assert_eq!(quoted_printable(new(END_OF_INPUT, b"a=3D1\r\n")).into_inner(), State::Data(new(END_OF_INPUT, b""), b"a=1\r\n".to_vec()));
+        assert_eq!(quoted_printable(new(END_OF_INPUT, b"a=\r\nb=\nc=c3=A9")).into_inner(), State::Data(new(END_OF_INPUT, b""), b"abc\xc3\xa9".to_vec()));
+        assert_eq!(quoted_printable(new(END_OF_INPUT, b"ab\x00c")).into_inner(), State::Data(new(END_OF_INPUT, b"\x00c"), b"ab".to_vec()));
+        assert_eq!(quoted_printable(new(END_OF_INPUT, b"a=G0")).into_inner(), State::Error(&b"=G0"[..], Error::unexpected()));
+        assert_eq!(quoted_printable(new(END_OF_INPUT, b"a=\rb")).into_inner(), State::Error(&b"=\rb"[..], Error::unexpected()));
+        assert_eq!(quoted_printable(new(END_OF_INPUT, b"a=")).into_inner(), State::Error(&b"="[..], Error::unexpected()));
+        assert_eq!(quoted_printable(new(END_OF_INPUT, b"a=A")).into_inner(), State::Error(&b"=A"[..], Error::unexpected()));
+
+        assert_eq!(quoted_printable(new(DEFAULT, b"a")).into_inner(), State::Incomplete(1));
+        assert_eq!(quoted_printable(new(DEFAULT, b"a=")).into_inner(), State::Incomplete(2));
+        assert_eq!(quoted_printable(new(DEFAULT, b"a=A")).into_inner(), State::Incomplete(1));
+        assert_eq!(quoted_printable(new(DEFAULT, b"a=\r")).into_inner(), State::Incomplete(1));
+        assert_eq!(quoted_printable(new(DEFAULT, b"a=G")).into_inner(), State::Error(&b"=G"[..], Error::unexpected()));
+        assert_eq!(quoted_printable(new(DEFAULT, b"a=A0\x00")).into_inner(), State::Data(new(DEFAULT, b"\x00"), b"a\xa0".to_vec()));
+    }
+
+    #[test]
+    fn float_test() {
+        assert_eq!(float::<f64>(new(END_OF_INPUT, b"1")).into_inner(), State::Data(new(END_OF_INPUT, b""), 1.0));
+        assert_eq!(float::<f64>(new(END_OF_INPUT, b"+1.25")).into_inner(), State::Data(new(END_OF_INPUT, b""), 1.25));
+        assert_eq!(float::<f32>(new(END_OF_INPUT, b"-0.5 ")).into_inner(), State::Data(new(END_OF_INPUT, b" "), -0.5));
+        assert_eq!(float::<f64>(new(END_OF_INPUT, b"2.")).into_inner(), State::Data(new(END_OF_INPUT, b"."), 2.0));
+        assert_eq!(float::<f64>(new(END_OF_INPUT, b"2.a")).into_inner(), State::Data(new(END_OF_INPUT, b".a"), 2.0));
+        assert_eq!(float::<f64>(new(END_OF_INPUT, b"-a")).into_inner(), State::Error(&b"a"[..], Error::unexpected()));
+        assert_eq!(float::<f64>(new(END_OF_INPUT, b".5")).into_inner(), State::Error(&b".5"[..], Error::unexpected()));
+        assert_eq!(float::<u8>(new(END_OF_INPUT, b"1.5")).into_inner(), State::Error(&b""[..], Error::unexpected()));
+
+        assert_eq!(float::<f64>(new(DEFAULT, b"")).into_inner(), State::Incomplete(1));
+        assert_eq!(float::<f64>(new(DEFAULT, b"-")).into_inner(), State::Incomplete(1));
+        assert_eq!(float::<f64>(new(DEFAULT, b"12")).into_inner(), State::Incomplete(1));
+        assert_eq!(float::<f64>(new(DEFAULT, b"12.")).into_inner(), State::Incomplete(1));
+        assert_eq!(float::<f64>(new(DEFAULT, b"12.5")).into_inner(), State::Incomplete(1));
+        assert_eq!(float::<f64>(new(DEFAULT, b"12.5;")).into_inner(), State::Data(new(DEFAULT, b";"), 12.5));
+    }
+
+    #[test]
+    fn float_exp_test() {
+        assert_eq!(float_exp::<f64>(new(END_OF_INPUT, b"1.5")).into_inner(), State::Data(new(END_OF_INPUT, b""), 1.5));
+        assert_eq!(float_exp::<f64>(new(END_OF_INPUT, b"1.5e-3")).into_inner(), State::Data(new(END_OF_INPUT, b""), 1.5e-3));
+        assert_eq!(float_exp::<f64>(new(END_OF_INPUT, b"2E+10")).into_inner(), State::Data(new(END_OF_INPUT, b""), 2e10));
+        assert_eq!(float_exp::<f64>(new(END_OF_INPUT, b"-1e5,")).into_inner(), State::Data(new(END_OF_INPUT, b","), -1e5));
+        assert_eq!(float_exp::<f32>(new(END_OF_INPUT, b"1e")).into_inner(), State::Data(new(END_OF_INPUT, b"e"), 1.0));
+        assert_eq!(float_exp::<f32>(new(END_OF_INPUT, b"1e+")).into_inner(), State::Data(new(END_OF_INPUT, b"e+"), 1.0));
+        assert_eq!(float_exp::<f32>(new(END_OF_INPUT, b"1.e5")).into_inner(), State::Data(new(END_OF_INPUT, b".e5"), 1.0));
+        assert_eq!(float_exp::<f64>(new(END_OF_INPUT, b"e5")).into_inner(), State::Error(&b"e5"[..], Error::unexpected()));
+
+        assert_eq!(float_exp::<f64>(new(DEFAULT, b"1e")).into_inner(), State::Incomplete(1));
+        assert_eq!(float_exp::<f64>(new(DEFAULT, b"1e-")).into_inner(), State::Incomplete(1));
+        assert_eq!(float_exp::<f64>(new(DEFAULT, b"1e-3")).into_inner(), State::Incomplete(1));
+        assert_eq!(float_exp::<f64>(new(DEFAULT, b"1e-3 ")).into_inner(), State::Data(new(DEFAULT, b" "), 1e-3));
+    }
 }
@@ -0,0 +1,163 @@
+//! Parsers for fields smaller than a byte, eg. flags in protocol headers.
+//!
+//! The `bits` parser provides a `Bits` cursor over the input, which is read using `take_bits`,
+//! `bool_bit` and `tag_bits`. Bits are read most significant bit first. Once the closure returns
+//! the input is re-aligned to the next byte boundary, skipping any unread bits of the last byte.
+//!
+//! # Example
+//!
+//! ```
+//! use chomp::parse_only;
+//! use chomp::binary::bits::{bits, bool_bit, take_bits};
+//!
+//! // DNS header flags: QR, 4 bit opcode, AA, TC, RD
+//! let r = parse_only(|i| bits(i, |b| {
+//!     let qr     = try!(bool_bit(b));
+//!     let opcode = try!(take_bits(b, 4));
+//!     let aa     = try!(bool_bit(b));
+//!
+//!     Ok((qr, opcode, aa))
+//! }), b"\x8c\x00");
+//!
+//! assert_eq!(r, Ok((true, 1, true)));
+//! ```
+
+use {Input, U8Result};
+use primitives::InputBuffer;
+use parsers::Error;
+
+/// Cursor reading individual bits of a byte slice, created by the `bits` parser.
+#[derive(Debug)]
+pub struct Bits<'a> {
+    buf: &'a [u8],
+    /// Position in bits from the start of `buf`
+    pos: usize,
+}
+
+impl<'a> Bits<'a> {
+    /// Returns the number of bits read so far.
+    #[inline]
+    pub fn position(&self) -> usize {
+        self.pos
+    }
+}
+
+/// Error of the bit-level parsers.
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+pub enum BitError {
+    /// More input is required, contains the number of additional bytes needed.
+    Incomplete(usize),
+    /// The bits did not match, reported as an unexpected error at the current byte by `bits`.
+    Unexpected,
+}
+
+/// Runs `f` with a cursor over the bits of the input, re-aligning to the next byte boundary once
+/// `f` returns.
+///
+/// Errors from `f` are reported as unexpected errors at the byte containing the position of the
+/// cursor, `BitError::Incomplete` results in this parser being considered incomplete.
+#[inline]
+pub fn bits<'a, T, F>(i: Input<'a, u8>, f: F) -> U8Result<'a, T>
+  where F: FnOnce(&mut Bits<'a>) -> Result<T, BitError> {
+    let buf   = i.buffer();
+    let mut b = Bits { buf: buf, pos: 0 };
+
+    match f(&mut b) {
+        Ok(t)                        => i.replace(&buf[(b.pos + 7) / 8..]).ret(t),
+        Err(BitError::Incomplete(n)) => i.incomplete(n),
+        Err(BitError::Unexpected)    => i.replace(&buf[b.pos / 8..]).err(Error::unexpected()),
+    }
+}
+
+/// Reads `n` bits as an unsigned integer, most significant bit first.
+///
+/// # Panics
+///
+/// If `n` is larger than 64.
+#[inline]
+pub fn take_bits(b: &mut Bits, n: usize) -> Result<u64, BitError> {
+    assert!(n <= 64, "take_bits: can read at most 64 bits");
+
+    let end   = b.pos + n;
+    let bytes = (end + 7) / 8;
+
+    if bytes > b.buf.len() {
+        return Err(BitError::Incomplete(bytes - b.buf.len()));
+    }
+
+    let v = (b.pos..end).fold(0, |v, j| v << 1 | (b.buf[j / 8] >> (7 - j % 8) & 1) as u64);
+
+    b.pos = end;
+
+    Ok(v)
+}
+
+/// Reads a single bit as a `bool`.
+#[inline]
+pub fn bool_bit(b: &mut Bits) -> Result<bool, BitError> {
+    take_bits(b, 1).map(|v| v == 1)
+}
+
+/// Matches the `n` bits of `value`, not advancing the cursor if they do not match.
+///
+/// ```
+/// use chomp::parse_only;
+/// use chomp::binary::bits::{bits, tag_bits, take_bits};
+///
+/// // IPv4 version and header length
+/// let r = parse_only(|i| bits(i, |b| tag_bits(b, 4, 4).and_then(|_| take_bits(b, 4))), b"\x45");
+///
+/// assert_eq!(r, Ok(5));
+/// ```
+#[inline]
+pub fn tag_bits(b: &mut Bits, n: usize, value: u64) -> Result<(), BitError> {
+    let pos = b.pos;
+
+    take_bits(b, n).and_then(|v| if v == value {
+        Ok(())
+    } else {
+        b.pos = pos;
+
+        Err(BitError::Unexpected)
+    })
+}
+
+#[cfg(test)]
+mod test {
+    use super::{bits, bool_bit, tag_bits, take_bits, BitError};
+
+    use primitives::input::{new, DEFAULT, END_OF_INPUT};
+    use primitives::{IntoInner, State};
+    use parsers::Error;
+
+    #[test]
+    fn take_bits_test() {
+        let b = b"\xa5\x0f\xff";
+
+        assert_eq!(bits(new(END_OF_INPUT, b), |b| take_bits(b, 0)).into_inner(), State::Data(new(END_OF_INPUT, &b[..]), 0));
+        assert_eq!(bits(new(END_OF_INPUT, b), |b| take_bits(b, 3)).into_inner(), State::Data(new(END_OF_INPUT, &b[1..]), 0b101));
+        assert_eq!(bits(new(END_OF_INPUT, b), |b| take_bits(b, 8)).into_inner(), State::Data(new(END_OF_INPUT, &b[1..]), 0xa5));
+        assert_eq!(bits(new(END_OF_INPUT, b), |b| take_bits(b, 3).and_then(|_| take_bits(b, 9))).into_inner(), State::Data(new(END_OF_INPUT, &b[2..]), 0b0_0101_0000));
+        assert_eq!(bits(new(END_OF_INPUT, b), |b| take_bits(b, 24)).into_inner(), State::Data(new(END_OF_INPUT, &b[3..]), 0xa50fff));
+        assert_eq!(bits(new(END_OF_INPUT, b), |b| take_bits(b, 1).and_then(|_| Ok(b.position()))).into_inner(), State::Data(new(END_OF_INPUT, &b[1..]), 1));
+
+        assert_eq!(bits(new(DEFAULT, b), |b| take_bits(b, 25)).into_inner(), State::Incomplete(1));
+        assert_eq!(bits(new(DEFAULT, &b[..1]), |b| take_bits(b, 4).and_then(|_| take_bits(b, 20))).into_inner(), State::Incomplete(2));
+        assert_eq!(bits(new(DEFAULT, b""), |b| bool_bit(b)).into_inner(), State::Incomplete(1));
+    }
+
+    #[test]
+    fn bool_tag_test() {
+        let b = b"\xa5\x0f";
+
+        assert_eq!(bits(new(END_OF_INPUT, b), |b| bool_bit(b)).into_inner(), State::Data(new(END_OF_INPUT, &b[1..]), true));
+        assert_eq!(bits(new(END_OF_INPUT, b), |b| take_bits(b, 1).and_then(|_| bool_bit(b))).into_inner(), State::Data(new(END_OF_INPUT, &b[1..]), false));
+        assert_eq!(bits(new(END_OF_INPUT, b), |b| tag_bits(b, 4, 0xa).and_then(|_| tag_bits(b, 4, 0x5))).into_inner(), State::Data(new(END_OF_INPUT, &b[1..]), ()));
+        assert_eq!(bits(new(END_OF_INPUT, b), |b| tag_bits(b, 4, 0x5)).into_inner(), State::Error(&b[..], Error::unexpected()));
+        assert_eq!(bits(new(END_OF_INPUT, b), |b| take_bits(b, 8).and_then(|_| tag_bits(b, 8, 0xff))).into_inner(), State::Error(&b[1..], Error::unexpected()));
+        assert_eq!(bits(new(END_OF_INPUT, b), |b| tag_bits(b, 17, 0x14a1f)).into_inner(), State::Incomplete(1));
+
+        let r: State<_, (), _> = bits(new(END_OF_INPUT, b), |b| take_bits(b, 12).and_then(|_| Err(BitError::Unexpected))).into_inner();
+        assert_eq!(r, State::Error(&b[1..], Error::unexpected()));
+    }
+}
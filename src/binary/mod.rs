@@ -0,0 +1,562 @@
+//! Parsers for dealing with binary data in `u8` format, eg. fixed-size integers of file formats
+//! and network protocols.
+//!
+//! Parsers prefixed with `be_` read the value in big-endian (network) byte order while parsers
+//! prefixed with `le_` read it in little-endian byte order. Parsers suffixed with `_with` take the
+//! byte order as an `Endianness` argument, for formats where it is only known at runtime. All of
+//! them are considered incomplete if the buffer contains fewer bytes than the size of the value.
+//!
+//! Variable-length integers, as used by eg. Protocol Buffers, are parsed using `varint_u32` and
+//! `varint_u64`, and their zigzag-encoded signed counterparts using `zigzag_i32` and `zigzag_i64`.
+//! Fields smaller than a byte are parsed using the `bits` submodule, and padding aligning data to
+//! absolute offsets in the input is skipped using `align_to` and `pad`. NUL-terminated strings are
+//! parsed using `cstr` and `cstr_max`.
+//!
+//! # Example
+//!
+//! ```
+//! use chomp::parse_only;
+//! use chomp::binary::{be_u16, le_u32};
+//!
+//! let r = parse_only(|i| be_u16(i).bind(|i, a| le_u32(i).map(|b| (a, b))), b"\x01\x02\x03\x00\x00\x00");
+//!
+//! assert_eq!(r, Ok((0x0102, 3)));
+//! ```
+
+pub mod bits;
+
+use {Input, U8Result};
+use primitives::InputBuffer;
+use parsers::{Error, take};
+
+/// Byte order of a binary value.
+#[derive(Copy, Clone, Debug, Eq, PartialEq, Hash)]
+pub enum Endianness {
+    /// Most significant byte first, also known as network byte order.
+    Big,
+    /// Least significant byte first.
+    Little,
+}
+
+/// Parses a big-endian `u16`.
+///
+/// ```
+/// use chomp::parse_only;
+/// use chomp::binary::be_u16;
+///
+/// assert_eq!(parse_only(be_u16, b"\x12\x34"), Ok(0x1234));
+/// ```
+#[inline]
+pub fn be_u16(i: Input<u8>) -> U8Result<u16> {
+    be_uint(i, 2).map(|n| n as u16)
+}
+
+/// Parses a big-endian `u32`.
+#[inline]
+pub fn be_u32(i: Input<u8>) -> U8Result<u32> {
+    be_uint(i, 4).map(|n| n as u32)
+}
+
+/// Parses a big-endian `u64`.
+#[inline]
+pub fn be_u64(i: Input<u8>) -> U8Result<u64> {
+    be_uint(i, 8)
+}
+
+/// Parses a big-endian two's complement `i16`.
+#[inline]
+pub fn be_i16(i: Input<u8>) -> U8Result<i16> {
+    be_uint(i, 2).map(|n| n as u16 as i16)
+}
+
+/// Parses a big-endian two's complement `i32`.
+#[inline]
+pub fn be_i32(i: Input<u8>) -> U8Result<i32> {
+    be_uint(i, 4).map(|n| n as u32 as i32)
+}
+
+/// Parses a big-endian two's complement `i64`.
+#[inline]
+pub fn be_i64(i: Input<u8>) -> U8Result<i64> {
+    be_uint(i, 8).map(|n| n as i64)
+}
+
+/// Parses a big-endian IEEE-754 single precision float.
+///
+/// ```
+/// use chomp::parse_only;
+/// use chomp::binary::be_f32;
+///
+/// assert_eq!(parse_only(be_f32, b"\x3f\xc0\x00\x00"), Ok(1.5));
+/// ```
+#[inline]
+pub fn be_f32(i: Input<u8>) -> U8Result<f32> {
+    be_u32(i).map(f32::from_bits)
+}
+
+/// Parses a big-endian IEEE-754 double precision float.
+#[inline]
+pub fn be_f64(i: Input<u8>) -> U8Result<f64> {
+    be_u64(i).map(f64::from_bits)
+}
+
+/// Parses a little-endian `u16`.
+///
+/// ```
+/// use chomp::parse_only;
+/// use chomp::binary::le_u16;
+///
+/// assert_eq!(parse_only(le_u16, b"\x34\x12"), Ok(0x1234));
+/// ```
+#[inline]
+pub fn le_u16(i: Input<u8>) -> U8Result<u16> {
+    le_uint(i, 2).map(|n| n as u16)
+}
+
+/// Parses a little-endian `u32`.
+#[inline]
+pub fn le_u32(i: Input<u8>) -> U8Result<u32> {
+    le_uint(i, 4).map(|n| n as u32)
+}
+
+/// Parses a little-endian `u64`.
+#[inline]
+pub fn le_u64(i: Input<u8>) -> U8Result<u64> {
+    le_uint(i, 8)
+}
+
+/// Parses a little-endian two's complement `i16`.
+#[inline]
+pub fn le_i16(i: Input<u8>) -> U8Result<i16> {
+    le_uint(i, 2).map(|n| n as u16 as i16)
+}
+
+/// Parses a little-endian two's complement `i32`.
+#[inline]
+pub fn le_i32(i: Input<u8>) -> U8Result<i32> {
+    le_uint(i, 4).map(|n| n as u32 as i32)
+}
+
+/// Parses a little-endian two's complement `i64`.
+#[inline]
+pub fn le_i64(i: Input<u8>) -> U8Result<i64> {
+    le_uint(i, 8).map(|n| n as i64)
+}
+
+/// Parses a little-endian IEEE-754 single precision float.
+///
+/// ```
+/// use chomp::parse_only;
+/// use chomp::binary::le_f32;
+///
+/// assert_eq!(parse_only(le_f32, b"\x00\x00\xc0\x3f"), Ok(1.5));
+/// ```
+#[inline]
+pub fn le_f32(i: Input<u8>) -> U8Result<f32> {
+    le_u32(i).map(f32::from_bits)
+}
+
+/// Parses a little-endian IEEE-754 double precision float.
+#[inline]
+pub fn le_f64(i: Input<u8>) -> U8Result<f64> {
+    le_u64(i).map(f64::from_bits)
+}
+
+/// Parses a `u16` in the byte order `e`.
+///
+/// ```
+/// use chomp::parse_only;
+/// use chomp::binary::{Endianness, u16_with};
+///
+/// // Byte order mark determining the byte order of the following data
+/// let r = parse_only(|i| u16_with(i, Endianness::Little).bind(|i, bom| {
+///     let e = if bom == 0xfeff { Endianness::Little } else { Endianness::Big };
+///
+///     u16_with(i, e)
+/// }), b"\xfe\xff\x12\x34");
+///
+/// assert_eq!(r, Ok(0x1234));
+/// ```
+#[inline]
+pub fn u16_with(i: Input<u8>, e: Endianness) -> U8Result<u16> {
+    uint_with(i, 2, e).map(|n| n as u16)
+}
+
+/// Parses a `u32` in the byte order `e`.
+#[inline]
+pub fn u32_with(i: Input<u8>, e: Endianness) -> U8Result<u32> {
+    uint_with(i, 4, e).map(|n| n as u32)
+}
+
+/// Parses a `u64` in the byte order `e`.
+#[inline]
+pub fn u64_with(i: Input<u8>, e: Endianness) -> U8Result<u64> {
+    uint_with(i, 8, e)
+}
+
+/// Parses a two's complement `i16` in the byte order `e`.
+#[inline]
+pub fn i16_with(i: Input<u8>, e: Endianness) -> U8Result<i16> {
+    uint_with(i, 2, e).map(|n| n as u16 as i16)
+}
+
+/// Parses a two's complement `i32` in the byte order `e`.
+#[inline]
+pub fn i32_with(i: Input<u8>, e: Endianness) -> U8Result<i32> {
+    uint_with(i, 4, e).map(|n| n as u32 as i32)
+}
+
+/// Parses a two's complement `i64` in the byte order `e`.
+#[inline]
+pub fn i64_with(i: Input<u8>, e: Endianness) -> U8Result<i64> {
+    uint_with(i, 8, e).map(|n| n as i64)
+}
+
+/// Parses an IEEE-754 single precision float in the byte order `e`.
+#[inline]
+pub fn f32_with(i: Input<u8>, e: Endianness) -> U8Result<f32> {
+    u32_with(i, e).map(f32::from_bits)
+}
+
+/// Parses an IEEE-754 double precision float in the byte order `e`.
+#[inline]
+pub fn f64_with(i: Input<u8>, e: Endianness) -> U8Result<f64> {
+    u64_with(i, e).map(f64::from_bits)
+}
+
+/// Parses an unsigned LEB128 variable-length integer, where each byte contributes its lower seven
+/// bits, least significant group first, and the high bit is set on all but the last byte.
+///
+/// An encoding which does not fit in a `u32` results in an unexpected error at the first byte
+/// which does not fit. If the buffer ends before the last byte this parser is considered
+/// incomplete.
+///
+/// ```
+/// use chomp::parse_only;
+/// use chomp::binary::varint_u32;
+///
+/// assert_eq!(parse_only(varint_u32, b"\xac\x02"), Ok(300));
+/// ```
+#[inline]
+pub fn varint_u32(i: Input<u8>) -> U8Result<u32> {
+    varint(i, 32).map(|n| n as u32)
+}
+
+/// Parses an unsigned LEB128 variable-length integer into a `u64`, see `varint_u32` for details.
+#[inline]
+pub fn varint_u64(i: Input<u8>) -> U8Result<u64> {
+    varint(i, 64)
+}
+
+/// Parses a zigzag-encoded variable-length signed integer, where the sign is stored in the least
+/// significant bit so that numbers of small magnitude have short encodings.
+///
+/// See `varint_u32` for details on errors and incomplete input.
+///
+/// ```
+/// use chomp::parse_only;
+/// use chomp::binary::zigzag_i32;
+///
+/// assert_eq!(parse_only(zigzag_i32, b"\x03"), Ok(-2));
+/// assert_eq!(parse_only(zigzag_i32, b"\x04"), Ok(2));
+/// ```
+#[inline]
+pub fn zigzag_i32(i: Input<u8>) -> U8Result<i32> {
+    varint_u32(i).map(|n| (n >> 1) as i32 ^ -((n & 1) as i32))
+}
+
+/// Parses a zigzag-encoded variable-length signed integer into an `i64`, see `zigzag_i32` for
+/// details.
+#[inline]
+pub fn zigzag_i64(i: Input<u8>) -> U8Result<i64> {
+    varint_u64(i).map(|n| (n >> 1) as i64 ^ -((n & 1) as i64))
+}
+
+/// Skips padding bytes until the absolute offset of the input is a multiple of `n`, see
+/// `parsers::offset`.
+///
+/// If the buffer contains fewer bytes than the padding this parser is considered incomplete.
+///
+/// # Panics
+///
+/// If `n` is zero.
+///
+/// ```
+/// use chomp::{parse_only, any};
+/// use chomp::binary::align_to;
+///
+/// assert_eq!(parse_only(|i| any(i).skip(|i| align_to(i, 4)).then(any), b"a...b"), Ok(b'b'));
+/// ```
+#[inline]
+pub fn align_to(i: Input<u8>, n: usize) -> U8Result<()> {
+    let k = padding(i.offset(), n);
+
+    take(i, k).map(|_| ())
+}
+
+/// Skips padding bytes equal to `value` until the absolute offset of the input is a multiple of
+/// `n`, see `align_to`.
+///
+/// A padding byte which is not equal to `value` results in an expected error at that byte. If the
+/// buffer contains fewer bytes than the padding this parser is considered incomplete.
+///
+/// # Panics
+///
+/// If `n` is zero.
+///
+/// ```
+/// use chomp::{parse_only, any};
+/// use chomp::binary::pad;
+///
+/// assert_eq!(parse_only(|i| any(i).skip(|i| pad(i, 0, 4)).then(any), b"a\0\0\0b"), Ok(b'b'));
+/// assert!(parse_only(|i| any(i).skip(|i| pad(i, 0, 4)).then(any), b"a\0.\0b").is_err());
+/// ```
+#[inline]
+pub fn pad(i: Input<u8>, value: u8, n: usize) -> U8Result<()> {
+    let b = i.buffer();
+    let k = padding(i.offset(), n);
+
+    match b.iter().take(k).position(|&c| c != value) {
+        Some(p) => i.replace(&b[p..]).err(Error::expected(value)),
+        None    => take(i, k).map(|_| ()),
+    }
+}
+
+/// Internal function calculating the number of bytes required to align `offset` to `n`.
+#[inline]
+fn padding(offset: usize, n: usize) -> usize {
+    (n - offset % n) % n
+}
+
+/// Matches bytes up to a NUL terminator, returning the bytes before it and consuming the
+/// terminator.
+///
+/// If the buffer does not contain a NUL byte this parser is considered incomplete, unless it is
+/// the last slice in which case an unexpected error is reported at the end of the buffer.
+///
+/// ```
+/// use chomp::parse_only;
+/// use chomp::binary::cstr;
+///
+/// assert_eq!(parse_only(cstr, b"libc.so\0\x01"), Ok(&b"libc.so"[..]));
+/// ```
+#[inline]
+pub fn cstr<'a>(i: Input<'a, u8>) -> U8Result<'a, &'a [u8]> {
+    cstr_max(i, usize::max_value())
+}
+
+/// Matches at most `n` bytes followed by a NUL terminator, returning the bytes before it and
+/// consuming the terminator.
+///
+/// If there is no NUL byte among the first `n + 1` bytes an unexpected error is reported at the
+/// byte following the first `n`. See `cstr` for details on incomplete input.
+///
+/// ```
+/// use chomp::parse_only;
+/// use chomp::binary::cstr_max;
+///
+/// assert_eq!(parse_only(|i| cstr_max(i, 4), b"abc\0"), Ok(&b"abc"[..]));
+/// assert!(parse_only(|i| cstr_max(i, 2), b"abc\0").is_err());
+/// ```
+#[inline]
+pub fn cstr_max<'a>(i: Input<'a, u8>, n: usize) -> U8Result<'a, &'a [u8]> {
+    let b = i.buffer();
+
+    match b.iter().take(n.saturating_add(1)).position(|&c| c == 0) {
+        Some(p)                   => i.replace(&b[p + 1..]).ret(&b[..p]),
+        None if n < b.len()       => i.replace(&b[n..]).err(Error::unexpected()),
+        None if i.is_last_slice() => i.replace(&b[b.len()..]).err(Error::unexpected()),
+        None                      => i.incomplete(1),
+    }
+}
+
+/// Internal parser reading an unsigned LEB128 integer of at most `bits` bits.
+#[inline]
+fn varint(i: Input<u8>, bits: u32) -> U8Result<u64> {
+    let b     = i.buffer();
+    let mut n = 0;
+
+    for (j, &c) in b.iter().enumerate() {
+        let shift = 7 * j as u32;
+        let v     = (c & 0x7f) as u64;
+
+        if shift >= bits || (bits - shift < 7 && v >> (bits - shift) != 0) {
+            return i.replace(&b[j..]).err(Error::unexpected());
+        }
+
+        n |= v << shift;
+
+        if c & 0x80 == 0 {
+            return i.replace(&b[j + 1..]).ret(n);
+        }
+    }
+
+    i.incomplete(1)
+}
+
+/// Internal parser reading an `n` byte big-endian unsigned integer, `n` must be at most 8.
+#[inline]
+fn be_uint(i: Input<u8>, n: usize) -> U8Result<u64> {
+    take(i, n).map(|b| b.iter().fold(0, |a, &c| a << 8 | c as u64))
+}
+
+/// Internal parser reading an `n` byte little-endian unsigned integer, `n` must be at most 8.
+#[inline]
+fn le_uint(i: Input<u8>, n: usize) -> U8Result<u64> {
+    take(i, n).map(|b| b.iter().rev().fold(0, |a, &c| a << 8 | c as u64))
+}
+
+/// Internal parser reading an `n` byte unsigned integer in the byte order `e`, `n` must be at
+/// most 8.
+#[inline]
+fn uint_with(i: Input<u8>, n: usize, e: Endianness) -> U8Result<u64> {
+    match e {
+        Endianness::Big    => be_uint(i, n),
+        Endianness::Little => le_uint(i, n),
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::{be_u16, be_u32, be_u64, be_i16, be_i32, be_i64, be_f32, be_f64, le_u16, le_u32,
+                le_u64, le_i16, le_i32, le_i64, le_f32, le_f64, u16_with, u32_with, u64_with,
+                i16_with, i32_with, i64_with, f32_with, f64_with, Endianness, varint_u32, varint_u64,
+                zigzag_i32, zigzag_i64, align_to, pad, cstr, cstr_max};
+
+    use primitives::input::{new, new_at, DEFAULT, END_OF_INPUT};
+    use primitives::{IntoInner, State};
+    use parsers::Error;
+
+    #[test]
+    fn be_test() {
+        let b = b"\xfe\xdc\xba\x98\x76\x54\x32\x10!";
+
+        assert_eq!(be_u16(new(END_OF_INPUT, b)).into_inner(), State::Data(new(END_OF_INPUT, &b[2..]), 0xfedc));
+        assert_eq!(be_u32(new(END_OF_INPUT, b)).into_inner(), State::Data(new(END_OF_INPUT, &b[4..]), 0xfedcba98));
+        assert_eq!(be_u64(new(END_OF_INPUT, b)).into_inner(), State::Data(new(END_OF_INPUT, &b[8..]), 0xfedcba9876543210));
+        assert_eq!(be_i16(new(END_OF_INPUT, b)).into_inner(), State::Data(new(END_OF_INPUT, &b[2..]), -0x0124));
+        assert_eq!(be_i32(new(END_OF_INPUT, b)).into_inner(), State::Data(new(END_OF_INPUT, &b[4..]), -0x01234568));
+        assert_eq!(be_i64(new(END_OF_INPUT, b)).into_inner(), State::Data(new(END_OF_INPUT, &b[8..]), -0x0123456789abcdf0));
+
+        assert_eq!(be_u16(new(DEFAULT, &b[..1])).into_inner(), State::Incomplete(1));
+        assert_eq!(be_u32(new(END_OF_INPUT, &b[..1])).into_inner(), State::Incomplete(3));
+        assert_eq!(be_i64(new(DEFAULT, &b[..0])).into_inner(), State::Incomplete(8));
+    }
+
+    #[test]
+    fn le_test() {
+        let b = b"\x10\x32\x54\x76\x98\xba\xdc\xfe!";
+
+        assert_eq!(le_u16(new(END_OF_INPUT, b)).into_inner(), State::Data(new(END_OF_INPUT, &b[2..]), 0x3210));
+        assert_eq!(le_u32(new(END_OF_INPUT, b)).into_inner(), State::Data(new(END_OF_INPUT, &b[4..]), 0x76543210));
+        assert_eq!(le_u64(new(END_OF_INPUT, b)).into_inner(), State::Data(new(END_OF_INPUT, &b[8..]), 0xfedcba9876543210));
+        assert_eq!(le_i16(new(END_OF_INPUT, &b[6..])).into_inner(), State::Data(new(END_OF_INPUT, &b[8..]), -0x0124));
+        assert_eq!(le_i32(new(END_OF_INPUT, &b[4..])).into_inner(), State::Data(new(END_OF_INPUT, &b[8..]), -0x01234568));
+        assert_eq!(le_i64(new(END_OF_INPUT, b)).into_inner(), State::Data(new(END_OF_INPUT, &b[8..]), -0x0123456789abcdf0));
+
+        assert_eq!(le_u16(new(DEFAULT, &b[..1])).into_inner(), State::Incomplete(1));
+        assert_eq!(le_u64(new(END_OF_INPUT, &b[..5])).into_inner(), State::Incomplete(3));
+    }
+
+    #[test]
+    fn float_test() {
+        let b = b"\xbf\x80\x00\x00\x00\x00\x80\xbf";
+
+        assert_eq!(be_f32(new(END_OF_INPUT, b)).into_inner(), State::Data(new(END_OF_INPUT, &b[4..]), -1.0));
+        assert_eq!(le_f32(new(END_OF_INPUT, &b[4..])).into_inner(), State::Data(new(END_OF_INPUT, b""), -1.0));
+        assert_eq!(f32_with(new(END_OF_INPUT, b), Endianness::Big).into_inner(), State::Data(new(END_OF_INPUT, &b[4..]), -1.0));
+        assert_eq!(f32_with(new(END_OF_INPUT, &b[4..]), Endianness::Little).into_inner(), State::Data(new(END_OF_INPUT, b""), -1.0));
+
+        let b = b"\x40\x04\x00\x00\x00\x00\x00\x00\x00\x00\x00\x00\x00\x00\x04\x40";
+
+        assert_eq!(be_f64(new(END_OF_INPUT, b)).into_inner(), State::Data(new(END_OF_INPUT, &b[8..]), 2.5));
+        assert_eq!(le_f64(new(END_OF_INPUT, &b[8..])).into_inner(), State::Data(new(END_OF_INPUT, b""), 2.5));
+        assert_eq!(f64_with(new(END_OF_INPUT, b), Endianness::Big).into_inner(), State::Data(new(END_OF_INPUT, &b[8..]), 2.5));
+        assert_eq!(f64_with(new(END_OF_INPUT, &b[8..]), Endianness::Little).into_inner(), State::Data(new(END_OF_INPUT, b""), 2.5));
+
+        let r = be_f64(new(END_OF_INPUT, b"\x7f\xf8\x00\x00\x00\x00\x00\x00")).into_inner();
+        assert!(match r { State::Data(_, f) => f.is_nan(), _ => false });
+
+        assert_eq!(be_f32(new(DEFAULT, &b[..3])).into_inner(), State::Incomplete(1));
+        assert_eq!(le_f64(new(DEFAULT, &b[..0])).into_inner(), State::Incomplete(8));
+    }
+
+    #[test]
+    fn with_test() {
+        let b = b"\x10\x32\x54\x76\x98\xba\xdc\xfe!";
+
+        assert_eq!(u16_with(new(END_OF_INPUT, b), Endianness::Big).into_inner(), State::Data(new(END_OF_INPUT, &b[2..]), 0x1032));
+        assert_eq!(u16_with(new(END_OF_INPUT, b), Endianness::Little).into_inner(), State::Data(new(END_OF_INPUT, &b[2..]), 0x3210));
+        assert_eq!(u32_with(new(END_OF_INPUT, b), Endianness::Big).into_inner(), State::Data(new(END_OF_INPUT, &b[4..]), 0x10325476));
+        assert_eq!(u32_with(new(END_OF_INPUT, b), Endianness::Little).into_inner(), State::Data(new(END_OF_INPUT, &b[4..]), 0x76543210));
+        assert_eq!(u64_with(new(END_OF_INPUT, b), Endianness::Big).into_inner(), State::Data(new(END_OF_INPUT, &b[8..]), 0x1032547698badcfe));
+        assert_eq!(u64_with(new(END_OF_INPUT, b), Endianness::Little).into_inner(), State::Data(new(END_OF_INPUT, &b[8..]), 0xfedcba9876543210));
+        assert_eq!(i16_with(new(END_OF_INPUT, &b[6..]), Endianness::Big).into_inner(), State::Data(new(END_OF_INPUT, &b[8..]), -0x2302));
+        assert_eq!(i16_with(new(END_OF_INPUT, &b[6..]), Endianness::Little).into_inner(), State::Data(new(END_OF_INPUT, &b[8..]), -0x0124));
+        assert_eq!(i32_with(new(END_OF_INPUT, &b[4..]), Endianness::Little).into_inner(), State::Data(new(END_OF_INPUT, &b[8..]), -0x01234568));
+        assert_eq!(i64_with(new(END_OF_INPUT, b), Endianness::Little).into_inner(), State::Data(new(END_OF_INPUT, &b[8..]), -0x0123456789abcdf0));
+
+        assert_eq!(u32_with(new(DEFAULT, &b[..1]), Endianness::Big).into_inner(), State::Incomplete(3));
+        assert_eq!(u32_with(new(DEFAULT, &b[..1]), Endianness::Little).into_inner(), State::Incomplete(3));
+    }
+
+    #[test]
+    fn varint_test() {
+        assert_eq!(varint_u32(new(END_OF_INPUT, b"\x00")).into_inner(), State::Data(new(END_OF_INPUT, b""), 0));
+        assert_eq!(varint_u32(new(END_OF_INPUT, b"\x7f\x01")).into_inner(), State::Data(new(END_OF_INPUT, b"\x01"), 127));
+        assert_eq!(varint_u32(new(END_OF_INPUT, b"\xac\x02")).into_inner(), State::Data(new(END_OF_INPUT, b""), 300));
+        assert_eq!(varint_u32(new(END_OF_INPUT, b"\xff\xff\xff\xff\x0f")).into_inner(), State::Data(new(END_OF_INPUT, b""), 0xffffffff));
+        assert_eq!(varint_u32(new(END_OF_INPUT, b"\xff\xff\xff\xff\x1f")).into_inner(), State::Error(&b"\x1f"[..], Error::unexpected()));
+        assert_eq!(varint_u32(new(END_OF_INPUT, b"\x80\x80\x80\x80\x80\x00")).into_inner(), State::Error(&b"\x00"[..], Error::unexpected()));
+        assert_eq!(varint_u64(new(END_OF_INPUT, b"\xff\xff\xff\xff\x1f")).into_inner(), State::Data(new(END_OF_INPUT, b""), 0x1ffffffff));
+        assert_eq!(varint_u64(new(END_OF_INPUT, b"\xff\xff\xff\xff\xff\xff\xff\xff\xff\x01")).into_inner(), State::Data(new(END_OF_INPUT, b""), 0xffffffffffffffff));
+        assert_eq!(varint_u64(new(END_OF_INPUT, b"\xff\xff\xff\xff\xff\xff\xff\xff\xff\x02")).into_inner(), State::Error(&b"\x02"[..], Error::unexpected()));
+
+        assert_eq!(varint_u32(new(DEFAULT, b"")).into_inner(), State::Incomplete(1));
+        assert_eq!(varint_u32(new(END_OF_INPUT, b"\xac")).into_inner(), State::Incomplete(1));
+        assert_eq!(varint_u64(new(DEFAULT, b"\xff\xff")).into_inner(), State::Incomplete(1));
+
+        assert_eq!(zigzag_i32(new(END_OF_INPUT, b"\x00")).into_inner(), State::Data(new(END_OF_INPUT, b""), 0));
+        assert_eq!(zigzag_i32(new(END_OF_INPUT, b"\x01")).into_inner(), State::Data(new(END_OF_INPUT, b""), -1));
+        assert_eq!(zigzag_i32(new(END_OF_INPUT, b"\x02")).into_inner(), State::Data(new(END_OF_INPUT, b""), 1));
+        assert_eq!(zigzag_i32(new(END_OF_INPUT, b"\xfe\xff\xff\xff\x0f")).into_inner(), State::Data(new(END_OF_INPUT, b""), 0x7fffffff));
+        assert_eq!(zigzag_i32(new(END_OF_INPUT, b"\xff\xff\xff\xff\x0f")).into_inner(), State::Data(new(END_OF_INPUT, b""), -0x80000000));
+        assert_eq!(zigzag_i64(new(END_OF_INPUT, b"\xff\xff\xff\xff\xff\xff\xff\xff\xff\x01")).into_inner(), State::Data(new(END_OF_INPUT, b""), -0x8000000000000000));
+        assert_eq!(zigzag_i64(new(END_OF_INPUT, b"\xfe\xff\xff\xff\xff\xff\xff\xff\xff\x01")).into_inner(), State::Data(new(END_OF_INPUT, b""), 0x7fffffffffffffff));
+        assert_eq!(zigzag_i64(new(DEFAULT, b"\x80")).into_inner(), State::Incomplete(1));
+    }
+
+    #[test]
+    fn align_test() {
+        let b = b"\x00\x00\x00\x01\x02";
+
+        assert_eq!(align_to(new(END_OF_INPUT, b), 4).into_inner(), State::Data(new(END_OF_INPUT, &b[..]), ()));
+        assert_eq!(align_to(new_at(END_OF_INPUT, 1, b), 4).into_inner(), State::Data(new(END_OF_INPUT, &b[3..]), ()));
+        assert_eq!(align_to(new_at(END_OF_INPUT, 6, b), 4).into_inner(), State::Data(new(END_OF_INPUT, &b[2..]), ()));
+        assert_eq!(align_to(new_at(END_OF_INPUT, 7, b), 1).into_inner(), State::Data(new(END_OF_INPUT, &b[..]), ()));
+        assert_eq!(align_to(new_at(DEFAULT, 1, &b[..2]), 4).into_inner(), State::Incomplete(1));
+
+        assert_eq!(pad(new_at(END_OF_INPUT, 1, b), 0, 4).into_inner(), State::Data(new(END_OF_INPUT, &b[3..]), ()));
+        assert_eq!(pad(new_at(END_OF_INPUT, 4, b), 0, 4).into_inner(), State::Data(new(END_OF_INPUT, &b[..]), ()));
+        assert_eq!(pad(new_at(END_OF_INPUT, 0, b), 0, 5).into_inner(), State::Data(new(END_OF_INPUT, &b[..]), ()));
+        assert_eq!(pad(new_at(END_OF_INPUT, 1, b), 0, 8).into_inner(), State::Error(&b[3..], Error::expected(0)));
+        assert_eq!(pad(new_at(DEFAULT, 1, &b[..2]), 0, 4).into_inner(), State::Incomplete(1));
+        assert_eq!(pad(new_at(DEFAULT, 1, &b[..2]), 1, 4).into_inner(), State::Error(&b[..2], Error::expected(1)));
+    }
+
+    #[test]
+    fn cstr_test() {
+        assert_eq!(cstr(new(END_OF_INPUT, b"\0")).into_inner(), State::Data(new(END_OF_INPUT, b""), &b""[..]));
+        assert_eq!(cstr(new(DEFAULT, b"abc\0def\0")).into_inner(), State::Data(new(DEFAULT, b"def\0"), &b"abc"[..]));
+        assert_eq!(cstr(new(END_OF_INPUT, b"abc")).into_inner(), State::Error(&b""[..], Error::unexpected()));
+        assert_eq!(cstr(new(DEFAULT, b"abc")).into_inner(), State::Incomplete(1));
+        assert_eq!(cstr(new(DEFAULT, b"")).into_inner(), State::Incomplete(1));
+
+        assert_eq!(cstr_max(new(END_OF_INPUT, b"abc\0"), 3).into_inner(), State::Data(new(END_OF_INPUT, b""), &b"abc"[..]));
+        assert_eq!(cstr_max(new(END_OF_INPUT, b"\0"), 0).into_inner(), State::Data(new(END_OF_INPUT, b""), &b""[..]));
+        assert_eq!(cstr_max(new(DEFAULT, b"abc\0"), 2).into_inner(), State::Error(&b"c\0"[..], Error::unexpected()));
+        assert_eq!(cstr_max(new(DEFAULT, b"abc"), 0).into_inner(), State::Error(&b"abc"[..], Error::unexpected()));
+        assert_eq!(cstr_max(new(DEFAULT, b"abc"), 3).into_inner(), State::Incomplete(1));
+        assert_eq!(cstr_max(new(END_OF_INPUT, b"abc"), 3).into_inner(), State::Error(&b""[..], Error::unexpected()));
+    }
+}
@@ -0,0 +1,234 @@
+use std::cmp;
+use std::io;
+
+use primitives::input;
+use primitives::{State, InputBuffer, IntoInner};
+
+use {Input, ParseResult};
+use buffer::{StreamError, Stream};
+
+/// Stream implementation for `io::BufRead` instances, parsing directly from the buffer of the
+/// reader without copying the data into a `Buffer` whenever possible.
+///
+/// If a parser requires more data than is available in the buffer of the reader, the data is
+/// copied into an internal buffer which is then filled until the request can be satisfied, after
+/// which `StreamError::Retry` is returned like with an automatically filled `Source`. Once the
+/// internal buffer has been parsed parsing resumes directly from the buffer of the reader.
+///
+/// ```
+/// use std::io;
+///
+/// use chomp::{take, take_while1};
+/// use chomp::buffer::{BufReadStream, Stream, StreamError};
+///
+/// let mut s = BufReadStream::new(io::BufReader::with_capacity(4, &b"foo bar"[..]));
+///
+/// assert_eq!(s.parse(|i| take(i, 2)), Ok(&b"fo"[..]));
+/// assert_eq!(s.parse(|i| take(i, 4)), Err(StreamError::Retry));
+/// assert_eq!(s.parse(|i| take(i, 4)), Ok(&b"o ba"[..]));
+/// assert_eq!(s.offset(), 6);
+/// ```
+#[derive(Debug)]
+pub struct BufReadStream<R: io::BufRead> {
+    /// Source reader
+    reader:       R,
+    /// Items copied from the reader when a parser required more data than it had buffered
+    buffer:       Vec<u8>,
+    /// Number of items of `buffer` which have been consumed
+    used:         usize,
+    /// Number of items to consume from the reader before the next parse
+    pending:      usize,
+    /// The requested amount of items to be available in `buffer`
+    request:      usize,
+    /// Number of items consumed since the start of the reader
+    offset:       usize,
+    /// If the last parser did not manage to complete with the available data
+    incomplete:   bool,
+    /// If the reader did not manage to provide any more data
+    end_of_input: bool,
+}
+
+impl<R: io::BufRead> BufReadStream<R> {
+    /// Creates a new stream from a `BufRead` instance.
+    #[inline]
+    pub fn new(reader: R) -> Self {
+        BufReadStream {
+            reader:       reader,
+            buffer:       Vec::new(),
+            used:         0,
+            pending:      0,
+            request:      0,
+            offset:       0,
+            incomplete:   false,
+            end_of_input: false,
+        }
+    }
+
+    /// Returns the number of items consumed since the start of the reader.
+    #[inline]
+    pub fn offset(&self) -> usize {
+        self.offset
+    }
+
+    /// Copies data from the reader into the internal buffer until it satisfies the last request.
+    fn fill(&mut self) -> io::Result<()> {
+        self.buffer.drain(..self.used);
+        self.used = 0;
+
+        while self.buffer.len() < self.request {
+            let n = {
+                let data = try!(self.reader.fill_buf());
+
+                if data.is_empty() {
+                    self.end_of_input = true;
+
+                    break;
+                }
+
+                let n = cmp::min(data.len(), self.request - self.buffer.len());
+
+                self.buffer.extend_from_slice(&data[..n]);
+
+                n
+            };
+
+            self.reader.consume(n);
+        }
+
+        self.incomplete = false;
+
+        Ok(())
+    }
+}
+
+impl<'a, R: io::BufRead> Stream<'a, 'a> for BufReadStream<R> {
+    type Item = u8;
+
+    #[inline]
+    fn parse<F, T, E>(&'a mut self, f: F) -> Result<T, StreamError<'a, Self::Item, E>>
+      where F: FnOnce(Input<'a, Self::Item>) -> ParseResult<'a, Self::Item, T, E>,
+            T: 'a,
+            E: 'a {
+        // Data parsed directly from the reader can only be consumed once it is no longer borrowed
+        self.reader.consume(self.pending);
+        self.pending = 0;
+
+        if self.incomplete {
+            if let Err(e) = self.fill() {
+                return Err(StreamError::from_io(e));
+            }
+        }
+
+        if self.used < self.buffer.len() {
+            let input_state = if self.end_of_input { input::END_OF_INPUT } else { input::DEFAULT };
+            let buffer      = &self.buffer[self.used..];
+
+            match f(input::new_at(input_state, self.offset, buffer)).into_inner() {
+                State::Data(remainder, data) => {
+                    let n = buffer.len() - remainder.buffer().len();
+
+                    self.used   += n;
+                    self.offset += n;
+
+                    Ok(data)
+                },
+                State::Error(remainder, err) => {
+                    let n = buffer.len() - remainder.len();
+
+                    self.used   += n;
+                    self.offset += n;
+
+                    Err(StreamError::ParseError(remainder, err))
+                },
+                State::Incomplete(n) => {
+                    self.request = buffer.len() + n;
+
+                    if self.end_of_input {
+                        Err(StreamError::Incomplete(self.request))
+                    } else {
+                        self.incomplete = true;
+
+                        Err(StreamError::Retry)
+                    }
+                },
+            }
+        } else {
+            let buffer = match self.reader.fill_buf() {
+                Ok(buffer) => buffer,
+                Err(e)     => return Err(StreamError::from_io(e)),
+            };
+
+            if buffer.is_empty() {
+                self.end_of_input = true;
+
+                return Err(StreamError::EndOfInput);
+            }
+
+            self.buffer.clear();
+            self.used = 0;
+
+            match f(input::new_at(input::DEFAULT, self.offset, buffer)).into_inner() {
+                State::Data(remainder, data) => {
+                    let n = buffer.len() - remainder.buffer().len();
+
+                    self.pending  = n;
+                    self.offset  += n;
+
+                    Ok(data)
+                },
+                State::Error(remainder, err) => {
+                    let n = buffer.len() - remainder.len();
+
+                    self.pending  = n;
+                    self.offset  += n;
+
+                    Err(StreamError::ParseError(remainder, err))
+                },
+                State::Incomplete(n) => {
+                    // Move the data into the internal buffer so it can be extended
+                    self.buffer.extend_from_slice(buffer);
+
+                    self.pending    = buffer.len();
+                    self.request    = buffer.len() + n;
+                    self.incomplete = true;
+
+                    Err(StreamError::Retry)
+                },
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use std::io;
+
+    use {take, take_while};
+    use buffer::{StreamError, Stream};
+
+    use super::BufReadStream;
+
+    #[test]
+    fn parse() {
+        let mut s = BufReadStream::new(io::BufReader::with_capacity(2, &b"abcdef"[..]));
+
+        assert_eq!(s.parse(|i| take(i, 1)), Ok(&b"a"[..]));
+        assert_eq!(s.parse(|i| take(i, 3)), Err(StreamError::Retry));
+        assert_eq!(s.parse(|i| take(i, 3)), Ok(&b"bcd"[..]));
+        assert_eq!(s.offset(), 4);
+        assert_eq!(s.parse(|i| take_while(i, |_| true)), Err(StreamError::Retry));
+        assert_eq!(s.parse(|i| take_while(i, |_| true)), Ok(&b"ef"[..]));
+        assert_eq!(s.parse(|i| take(i, 1)), Err(StreamError::EndOfInput));
+        assert_eq!(s.offset(), 6);
+    }
+
+    #[test]
+    fn incomplete() {
+        let mut s = BufReadStream::new(io::BufReader::with_capacity(2, &b"abc"[..]));
+
+        assert_eq!(s.parse(|i| take(i, 4)), Err(StreamError::Retry));
+        assert_eq!(s.parse(|i| take(i, 4)), Err(StreamError::Incomplete(4)));
+        assert_eq!(s.parse(|i| take(i, 3)), Ok(&b"abc"[..]));
+        assert_eq!(s.parse(|i| take(i, 1)), Err(StreamError::EndOfInput));
+    }
+}
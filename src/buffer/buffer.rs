@@ -1,12 +1,16 @@
+use std::cmp;
+use std::mem;
 use std::ops;
 use std::ptr;
 use std::io;
 
 use std::cell::Cell;
+use std::sync::{Arc, Mutex};
 
 use buffer::DataSource;
 
-const DEFAULT_BUFFER_SIZE: usize = 6 * 1024;
+/// Default size of `FixedSizeBuffer`, also used by `SourceBuilder`.
+pub const DEFAULT_BUFFER_SIZE: usize = 6 * 1024;
 
 /// Trait all parser buffers implement.
 ///
@@ -24,7 +28,7 @@ pub trait Buffer<I>: ops::Deref<Target=[I]> {
     ///
     /// * Return `0` if no more data is available or if the slice is of zero length.
     ///
-    /// * The slice might contain uninitialized memory, do not read from the slice.
+    /// * The slice handed to the `DataSource` must not contain uninitialized memory.
     #[inline]
     fn fill<S: DataSource<Item=I>>(&mut self, &mut S) -> io::Result<usize>;
 
@@ -52,14 +56,20 @@ pub trait Buffer<I>: ops::Deref<Target=[I]> {
     /// Returns the maximum amount of data which can be stored
     #[inline]
     fn capacity(&self) -> usize;
+
+    /// Returns the number of times the buffer has moved its data to free space for additional
+    /// items.
+    #[inline]
+    fn compactions(&self) -> usize {
+        0
+    }
 }
 
 /// A fixed size buffer.
 ///
-/// Only allocates when created.
-// TODO: Tests
+/// Only allocates when created, the memory is initialized using `I::default()`.
 #[derive(Debug, Eq, PartialEq)]
-pub struct FixedSizeBuffer<I: Copy> {
+pub struct FixedSizeBuffer<I: Copy + Default> {
     /// Backing memory.
     buffer:    Vec<I>,
     /// Number of items of `buffer` which contain actual data.
@@ -68,9 +78,11 @@ pub struct FixedSizeBuffer<I: Copy> {
     ///
     /// As long as used <= populated it is safe.
     used:      Cell<usize>,
+    /// Number of times data has been moved to the start of the buffer.
+    copies:    usize,
 }
 
-impl<I: Copy> FixedSizeBuffer<I> {
+impl<I: Copy + Default> FixedSizeBuffer<I> {
     /// Creates a fixed-size buffer with the default buffer size.
     #[inline]
     pub fn new() -> Self {
@@ -82,26 +94,20 @@ impl<I: Copy> FixedSizeBuffer<I> {
     pub fn with_size(size: usize) -> Self {
         assert!(size > 0);
 
-        let mut buf = Vec::with_capacity(size);
-
-        // TODO: Would it be better with a Default requirement on I?
-        // We set the length here to allow fill() to hand out a slice of uninitialized memory
-        // to be populated.
-        // NOTE: We cannot actually expose this memory to the parser since self.populated will
-        // be the upper limit for the deref to slice.
-        unsafe {
-            buf.set_len(size);
-        }
-
         FixedSizeBuffer {
-            buffer:    buf,
+            // Initialized to allow fill() to hand out a slice of the unpopulated memory to the
+            // data source.
+            // NOTE: This memory is never exposed to the parser since self.populated will be the
+            // upper limit for the deref to slice.
+            buffer:    vec![I::default(); size],
             populated: 0,
             used:      Cell::new(0),
+            copies:    0,
         }
     }
 }
 
-impl<I: Copy> ops::Deref for FixedSizeBuffer<I> {
+impl<I: Copy + Default> ops::Deref for FixedSizeBuffer<I> {
     type Target = [I];
 
     #[inline]
@@ -110,14 +116,14 @@ impl<I: Copy> ops::Deref for FixedSizeBuffer<I> {
     }
 }
 
-impl<I: Copy> ops::DerefMut for FixedSizeBuffer<I> {
+impl<I: Copy + Default> ops::DerefMut for FixedSizeBuffer<I> {
     #[inline]
     fn deref_mut(&mut self) -> &mut [I] {
         &mut self.buffer[self.used.get()..self.populated]
     }
 }
 
-impl<I: Copy> Buffer<I> for FixedSizeBuffer<I> {
+impl<I: Copy + Default> Buffer<I> for FixedSizeBuffer<I> {
     #[inline]
     fn fill<S: DataSource<Item=I>>(&mut self, s: &mut S) -> io::Result<usize> {
         s.read(&mut self.buffer[self.populated..]).map(|n| {
@@ -143,6 +149,7 @@ impl<I: Copy> Buffer<I> for FixedSizeBuffer<I> {
 
             self.populated -= self.used.get();
             self.used.set(0);
+            self.copies    += 1;
         }
     }
 
@@ -162,16 +169,181 @@ impl<I: Copy> Buffer<I> for FixedSizeBuffer<I> {
     fn capacity(&self) -> usize {
         self.buffer.len()
     }
+
+    #[inline]
+    fn compactions(&self) -> usize {
+        self.copies
+    }
+}
+
+/// A pool of fixed size buffers, allowing short-lived `Source`s to reuse the memory of previous
+/// buffers instead of allocating new ones.
+///
+/// Cloning the pool creates a new handle to the same pool, which can be shared between threads.
+/// The memory of a buffer is cleared when it is returned to the pool, so the data read by one
+/// `Source` is never visible to the next one.
+///
+/// ```
+/// use std::io;
+///
+/// use chomp::take;
+/// use chomp::buffer::{BufferPool, Source, Stream};
+///
+/// let pool = BufferPool::with_size(16);
+///
+/// for _ in 0..2 {
+///     let mut s = Source::from_read(io::Cursor::new(&b"abc"[..]), pool.get());
+///
+///     assert_eq!(s.parse(|i| take(i, 3)), Ok(&b"abc"[..]));
+/// }
+///
+/// assert_eq!(pool.len(), 1);
+/// ```
+#[derive(Clone, Debug)]
+pub struct BufferPool<I: Copy + Default> {
+    /// Size of the buffers in the pool.
+    size:    usize,
+    /// Memory of the buffers which have been returned to the pool.
+    buffers: Arc<Mutex<Vec<Vec<I>>>>,
+}
+
+impl<I: Copy + Default> BufferPool<I> {
+    /// Creates an empty pool of buffers with the default buffer size.
+    #[inline]
+    pub fn new() -> Self {
+        Self::with_size(DEFAULT_BUFFER_SIZE)
+    }
+
+    /// Creates an empty pool of buffers with the supplied buffer size.
+    #[inline]
+    pub fn with_size(size: usize) -> Self {
+        assert!(size > 0);
+
+        BufferPool {
+            size:    size,
+            buffers: Arc::new(Mutex::new(Vec::new())),
+        }
+    }
+
+    /// Checks out a buffer from the pool, allocating a new one if the pool is empty.
+    ///
+    /// The buffer is returned to the pool once it is dropped.
+    #[inline]
+    pub fn get(&self) -> PooledBuffer<I> {
+        let memory = self.buffers.lock().ok().and_then(|mut b| b.pop());
+
+        PooledBuffer {
+            buffer: match memory {
+                Some(memory) => FixedSizeBuffer {
+                    buffer:    memory,
+                    populated: 0,
+                    used:      Cell::new(0),
+                    copies:    0,
+                },
+                None         => FixedSizeBuffer::with_size(self.size),
+            },
+            pool:   self.clone(),
+        }
+    }
+
+    /// Returns the number of buffers currently available in the pool.
+    #[inline]
+    pub fn len(&self) -> usize {
+        self.buffers.lock().map(|b| b.len()).unwrap_or(0)
+    }
+
+    /// Returns true if there are no buffers available in the pool.
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+}
+
+impl<I: Copy + Default> Default for BufferPool<I> {
+    #[inline]
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// A fixed size buffer checked out from a `BufferPool`, returned to the pool when dropped.
+#[derive(Debug)]
+pub struct PooledBuffer<I: Copy + Default> {
+    buffer: FixedSizeBuffer<I>,
+    pool:   BufferPool<I>,
+}
+
+impl<I: Copy + Default> Drop for PooledBuffer<I> {
+    fn drop(&mut self) {
+        let mut memory = mem::take(&mut self.buffer.buffer);
+
+        // Clear the data of this buffer so it is never handed to the data source of the next
+        // Source using the memory
+        for c in memory.iter_mut() {
+            *c = I::default();
+        }
+
+        if let Ok(mut b) = self.pool.buffers.lock() {
+            b.push(memory);
+        }
+    }
+}
+
+impl<I: Copy + Default> ops::Deref for PooledBuffer<I> {
+    type Target = [I];
+
+    #[inline]
+    fn deref(&self) -> &[I] {
+        &self.buffer
+    }
+}
+
+impl<I: Copy + Default> ops::DerefMut for PooledBuffer<I> {
+    #[inline]
+    fn deref_mut(&mut self) -> &mut [I] {
+        &mut self.buffer
+    }
+}
+
+impl<I: Copy + Default> Buffer<I> for PooledBuffer<I> {
+    #[inline]
+    fn fill<S: DataSource<Item=I>>(&mut self, s: &mut S) -> io::Result<usize> {
+        self.buffer.fill(s)
+    }
+
+    #[inline]
+    fn request_space(&mut self, items: usize) {
+        self.buffer.request_space(items)
+    }
+
+    #[inline]
+    fn consume(&self, items: usize) {
+        self.buffer.consume(items)
+    }
+
+    #[inline]
+    fn len(&self) -> usize {
+        self.buffer.len()
+    }
+
+    #[inline]
+    fn capacity(&self) -> usize {
+        self.buffer.capacity()
+    }
+
+    #[inline]
+    fn compactions(&self) -> usize {
+        self.buffer.compactions()
+    }
 }
 
 /// A buffer which will reallocate to fit the requested amount of data.
 ///
 /// # Note:
 ///
-/// Will not decrease in size.
-// TODO: Tests
+/// Will not decrease in size, newly allocated memory is initialized using `I::default()`.
 #[derive(Debug)]
-pub struct GrowingBuffer<I: Copy> {
+pub struct GrowingBuffer<I: Copy + Default> {
     /// Backing memory.
     buffer:    Vec<I>,
     /// Number of items of `buffer` which contain actual data.
@@ -182,9 +354,11 @@ pub struct GrowingBuffer<I: Copy> {
     ///
     /// As long as used <= populated it is safe.
     used:      Cell<usize>,
+    /// Number of times data has been moved to the start of the buffer.
+    copies:    usize,
 }
 
-impl<I: Copy> GrowingBuffer<I> {
+impl<I: Copy + Default> GrowingBuffer<I> {
     /// Creates a new unlimited `GrowingBuffer`.
     #[inline]
     pub fn new() -> Self {
@@ -196,7 +370,7 @@ impl<I: Copy> GrowingBuffer<I> {
     /// # Note
     ///
     /// The actual amount of allocated memory might be larger than the specified limit, depends on
-    /// the allocator.
+    /// the allocator, but the buffer will never hold more than `limit` items.
     #[inline]
     pub fn with_limit(limit: usize) -> Self {
         GrowingBuffer {
@@ -204,11 +378,27 @@ impl<I: Copy> GrowingBuffer<I> {
             populated: 0,
             limit:     limit,
             used:      Cell::new(0),
+            copies:    0,
+        }
+    }
+
+    /// Creates a new `GrowingBuffer` with the specified initial capacity and limit, 0 means no
+    /// limit.
+    ///
+    /// A limit equal to the capacity makes the buffer behave like a `FixedSizeBuffer`.
+    #[inline]
+    pub fn with_capacity(capacity: usize, limit: usize) -> Self {
+        GrowingBuffer {
+            buffer:    vec![I::default(); capacity],
+            populated: 0,
+            limit:     limit,
+            used:      Cell::new(0),
+            copies:    0,
         }
     }
 }
 
-impl<I: Copy> ops::Deref for GrowingBuffer<I> {
+impl<I: Copy + Default> ops::Deref for GrowingBuffer<I> {
     type Target = [I];
 
     #[inline]
@@ -217,14 +407,14 @@ impl<I: Copy> ops::Deref for GrowingBuffer<I> {
     }
 }
 
-impl<I: Copy> ops::DerefMut for GrowingBuffer<I> {
+impl<I: Copy + Default> ops::DerefMut for GrowingBuffer<I> {
     #[inline]
     fn deref_mut(&mut self) -> &mut [I] {
         &mut self.buffer[self.used.get()..self.populated]
     }
 }
 
-impl<I: Copy> Buffer<I> for GrowingBuffer<I> {
+impl<I: Copy + Default> Buffer<I> for GrowingBuffer<I> {
     #[inline]
     fn fill<S: DataSource<Item=I>>(&mut self, s: &mut S) -> io::Result<usize> {
         s.read(&mut self.buffer[self.populated..]).map(|n| {
@@ -238,25 +428,25 @@ impl<I: Copy> Buffer<I> for GrowingBuffer<I> {
 
     #[inline]
     fn request_space(&mut self, items: usize) {
-        // If we are over the limit, refuse
-        if self.limit != 0 && self.buffer.capacity() > self.limit {
-            return;
-        }
+        if items + self.len() > self.buffer.len() {
+            // We do not have enough space for the new items, grow at least by doubling to
+            // amortize the reallocations but never past the limit, the Source reports requests
+            // the buffer cannot hold
+            let mut cap = cmp::max(self.buffer.len() * 2, self.buffer.len() + items);
+
+            if self.limit != 0 {
+                cap = cmp::min(cap, cmp::max(self.limit, self.buffer.len()));
+            }
 
-        if items + self.len() > self.buffer.capacity() {
-            // We do not have enough space for the new items, reallocate
-            self.buffer.reserve(items);
+            let additional = cap - self.buffer.len();
 
-            let cap = self.buffer.capacity();
+            self.buffer.reserve_exact(additional);
 
-            // TODO: Would it be better with a Default requirement on I?
-            // We set the length here to allow fill() to hand out a slice of uninitialized memory
-            // to be populated.
-            // NOTE: We cannot actually expose this memory to the parser since self.populated will
-            // be the upper limit for the deref to slice.
-            unsafe {
-                self.buffer.set_len(cap);
-            }
+            // Initialized to allow fill() to hand out a slice of the unpopulated memory to the
+            // data source.
+            // NOTE: This memory is never exposed to the parser since self.populated will be the
+            // upper limit for the deref to slice.
+            self.buffer.resize(cap, I::default());
         }
 
         // Only copy if we actually need to free the space
@@ -267,6 +457,7 @@ impl<I: Copy> Buffer<I> for GrowingBuffer<I> {
 
             self.populated -= self.used.get();
             self.used.set(0);
+            self.copies    += 1;
         }
     }
 
@@ -286,4 +477,138 @@ impl<I: Copy> Buffer<I> for GrowingBuffer<I> {
     fn capacity(&self) -> usize {
         self.buffer.len()
     }
+
+    #[inline]
+    fn compactions(&self) -> usize {
+        self.copies
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use std::cmp;
+    use std::io;
+
+    use buffer::DataSource;
+    use buffer::data_source::IteratorDataSource;
+
+    use super::{Buffer, BufferPool, FixedSizeBuffer, GrowingBuffer};
+
+    /// Data source asserting that it is only handed default-initialized memory.
+    struct Initialized(u8);
+
+    impl DataSource for Initialized {
+        type Item = u8;
+
+        fn read(&mut self, buffer: &mut [u8]) -> io::Result<usize> {
+            assert!(buffer.iter().all(|&b| b == 0));
+
+            for b in buffer.iter_mut() {
+                *b = self.0;
+            }
+
+            Ok(buffer.len())
+        }
+    }
+
+    #[test]
+    fn fixed_size() {
+        let mut b = FixedSizeBuffer::with_size(4);
+        let mut s = IteratorDataSource::new(b"abcdef".iter().cloned());
+
+        assert_eq!(b.capacity(), 4);
+        assert_eq!(b.fill(&mut s).unwrap(), 4);
+        assert_eq!(&b[..], b"abcd");
+
+        b.consume(3);
+        b.request_space(2);
+
+        assert_eq!(b.compactions(), 1);
+        assert_eq!(&b[..], b"d");
+        assert_eq!(b.fill(&mut s).unwrap(), 2);
+        assert_eq!(&b[..], b"def");
+        assert_eq!(b.fill(&mut s).unwrap(), 0);
+
+        assert_eq!(FixedSizeBuffer::with_size(3).fill(&mut Initialized(1)).unwrap(), 3);
+    }
+
+    #[test]
+    fn pool() {
+        let pool = BufferPool::with_size(4);
+        let mut s = IteratorDataSource::new(b"abcdef".iter().cloned());
+
+        assert!(pool.is_empty());
+
+        {
+            let mut a = pool.get();
+            let mut b = pool.get();
+
+            assert_eq!(a.fill(&mut s).unwrap(), 4);
+            assert_eq!(&a[..], b"abcd");
+            assert_eq!(b.capacity(), 4);
+            assert_eq!(b.len(), 0);
+        }
+
+        assert_eq!(pool.len(), 2);
+
+        let mut a = pool.get();
+
+        assert_eq!(pool.len(), 1);
+        assert_eq!(a.capacity(), 4);
+        assert_eq!(a.len(), 0);
+        assert_eq!(a.fill(&mut s).unwrap(), 2);
+        assert_eq!(&a[..], b"ef");
+
+        drop(a);
+
+        // The previous data is cleared before the memory is reused
+        let a = pool.get();
+
+        assert!(a.buffer.buffer.iter().all(|&c| c == 0));
+    }
+
+    #[test]
+    fn growing() {
+        let mut b = GrowingBuffer::new();
+        let mut s = IteratorDataSource::new(b"abcdef".iter().cloned());
+
+        assert_eq!(b.fill(&mut s).unwrap(), 0);
+
+        b.request_space(2);
+
+        let cap = b.capacity();
+
+        assert!(cap >= 2);
+        assert_eq!(b.fill(&mut s).unwrap(), cmp::min(cap, 6));
+        assert_eq!(&b[..2], b"ab");
+
+        let mut b = GrowingBuffer::new();
+
+        b.request_space(3);
+
+        assert_eq!(b.fill(&mut Initialized(1)).unwrap(), b.capacity());
+        assert!(b.iter().all(|&c| c == 1));
+    }
+
+    #[test]
+    fn growing_limit() {
+        let mut b = GrowingBuffer::with_limit(16);
+
+        b.request_space(10);
+
+        assert_eq!(b.capacity(), 10);
+        assert_eq!(b.fill(&mut Initialized(1)).unwrap(), 10);
+
+        // Doubling would exceed the limit
+        b.request_space(1);
+
+        assert_eq!(b.capacity(), 16);
+        assert_eq!(b.fill(&mut Initialized(1)).unwrap(), 6);
+
+        b.request_space(4);
+
+        assert_eq!(b.capacity(), 16);
+        assert_eq!(b.len(), 16);
+        assert_eq!(b.fill(&mut Initialized(1)).unwrap(), 0);
+    }
 }
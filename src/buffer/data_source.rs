@@ -1,6 +1,13 @@
 //! Implementation of datasources for `Source`.
 
+use std::cmp;
 use std::io;
+use std::sync::mpsc::Receiver;
+
+#[cfg(feature = "async")]
+use futures::{Async, Poll};
+#[cfg(feature = "tokio")]
+use tokio_io::AsyncRead;
 
 /// Abstraction over `io::Read`, `Iterator` and others.
 pub trait DataSource {
@@ -16,13 +23,41 @@ pub trait DataSource {
     /// * If no data could be written (or is available), or if the slice is of zero-length, `Ok(0)`
     ///   should be returned (includes EOF).
     ///
-    /// * The slice must not be read from, may contain uninitialized memory.
+    /// * The slice may contain arbitrary data, it should only be written to.
     #[inline]
     fn read(&mut self, &mut [Self::Item]) -> io::Result<usize>;
+
+    /// Creates a data source which reads all data from `self` followed by all data from `next`.
+    ///
+    /// ```
+    /// use std::io;
+    ///
+    /// use chomp::take;
+    /// use chomp::buffer::{FixedSizeBuffer, Source, Stream, StreamError};
+    /// use chomp::buffer::data_source::{DataSource, ReadDataSource};
+    ///
+    /// let preamble = ReadDataSource::new(io::Cursor::new(&b"abc"[..]));
+    /// let body     = ReadDataSource::new(io::Cursor::new(&b"def"[..]));
+    ///
+    /// let mut s = Source::with_buffer(preamble.chain(body), FixedSizeBuffer::new());
+    ///
+    /// assert_eq!(s.parse(|i| take(i, 2)), Ok(&b"ab"[..]));
+    /// assert_eq!(s.parse(|i| take(i, 3)), Err(StreamError::Retry));
+    /// assert_eq!(s.parse(|i| take(i, 3)), Ok(&b"cde"[..]));
+    /// ```
+    #[inline]
+    fn chain<D>(self, next: D) -> Chain<Self, D>
+      where Self: Sized,
+            D: DataSource<Item=Self::Item> {
+        Chain {
+            first:      self,
+            second:     next,
+            done_first: false,
+        }
+    }
 }
 
 /// Implementation of `DataSource` for `io::Read` instances.
-// TODO: Tests
 #[derive(Debug)]
 pub struct ReadDataSource<R: io::Read>(R);
 
@@ -50,7 +85,18 @@ impl<R: io::Read> DataSource for ReadDataSource<R> {
 }
 
 /// Implementation of `DataSource` for `Iterator`.
-// TODO: Tests
+///
+/// Allows any iterator of tokens to back a `Source`, eg. generated data or the output of a lexer.
+///
+/// ```
+/// use chomp::{take_while, token};
+/// use chomp::buffer::{FixedSizeBuffer, Source, Stream};
+///
+/// let mut s = Source::from_iter("foo bar".chars(), FixedSizeBuffer::with_size(4));
+///
+/// assert_eq!(s.parse(|i| take_while(i, |c| c != ' ').map(|s| s.to_vec())), Ok(vec!['f', 'o', 'o']));
+/// assert_eq!(s.parse(|i| token(i, ' ')), Ok(' '));
+/// ```
 #[derive(Debug)]
 pub struct IteratorDataSource<I: Iterator>(I);
 
@@ -88,3 +134,258 @@ impl<I: Iterator> DataSource for IteratorDataSource<I> {
         Ok(n)
     }
 }
+
+/// Implementation of `DataSource` for `mpsc::Receiver` instances receiving chunks of data.
+///
+/// Allows a producer thread to feed a parser running in another thread. Reading blocks until a
+/// chunk is available, the end of the input is reached once all senders have been dropped.
+#[derive(Debug)]
+pub struct ChannelDataSource<T> {
+    receiver: Receiver<Vec<T>>,
+    /// Last received chunk
+    chunk:    Vec<T>,
+    /// Number of items of `chunk` which have been read
+    pos:      usize,
+}
+
+impl<T> ChannelDataSource<T> {
+    /// Creates a new `ChannelDataSource` from a `Receiver` instance.
+    #[inline]
+    pub fn new(inner: Receiver<Vec<T>>) -> Self {
+        ChannelDataSource {
+            receiver: inner,
+            chunk:    Vec::new(),
+            pos:      0,
+        }
+    }
+
+    /// Consumes self to reveal the underlying `Receiver` instance, any data left of the last
+    /// received chunk is discarded.
+    #[inline]
+    pub fn into_inner(self) -> Receiver<Vec<T>> {
+        self.receiver
+    }
+}
+
+impl<T: Copy> DataSource for ChannelDataSource<T> {
+    type Item = T;
+
+    #[inline]
+    fn read(&mut self, buffer: &mut [T]) -> io::Result<usize> {
+        if buffer.is_empty() {
+            return Ok(0);
+        }
+
+        while self.pos >= self.chunk.len() {
+            match self.receiver.recv() {
+                Ok(chunk) => {
+                    self.chunk = chunk;
+                    self.pos   = 0;
+                },
+                // All senders have disconnected
+                Err(_)    => return Ok(0),
+            }
+        }
+
+        let n = cmp::min(buffer.len(), self.chunk.len() - self.pos);
+
+        buffer[..n].copy_from_slice(&self.chunk[self.pos..self.pos + n]);
+
+        self.pos += n;
+
+        Ok(n)
+    }
+}
+
+/// Data source reading from two data sources in sequence, created by `DataSource::chain`.
+#[derive(Debug)]
+pub struct Chain<A, B> {
+    first:      A,
+    second:     B,
+    done_first: bool,
+}
+
+impl<A, B> Chain<A, B> {
+    /// Consumes self to reveal the underlying data sources.
+    #[inline]
+    pub fn into_inner(self) -> (A, B) {
+        (self.first, self.second)
+    }
+}
+
+impl<A: DataSource, B: DataSource<Item=A::Item>> DataSource for Chain<A, B> {
+    type Item = A::Item;
+
+    #[inline]
+    fn read(&mut self, buffer: &mut [A::Item]) -> io::Result<usize> {
+        if ! self.done_first {
+            match self.first.read(buffer) {
+                Ok(0) if ! buffer.is_empty() => self.done_first = true,
+                r                            => return r,
+            }
+        }
+
+        self.second.read(buffer)
+    }
+}
+
+/// Data source which is polled for data from within a task, instead of blocking until data is
+/// available.
+#[cfg(feature = "async")]
+pub trait AsyncDataSource {
+    /// The type of items this data source produces.
+    type Item;
+
+    /// Attempts to populate the supplied buffer with data, returns the number of items written.
+    ///
+    /// If no data is currently available `Async::NotReady` is returned and the current task is
+    /// notified once more data can be read.
+    ///
+    /// # Notes
+    ///
+    /// Same as for `DataSource::read`.
+    fn poll_read(&mut self, buffer: &mut [Self::Item]) -> Poll<usize, io::Error>;
+}
+
+/// Implementation of `DataSource` for `AsyncDataSource` instances, `Async::NotReady` is
+/// translated into an IO-error of kind `WouldBlock`.
+#[cfg(feature = "async")]
+#[derive(Debug)]
+pub struct PollDataSource<A: AsyncDataSource>(A);
+
+#[cfg(feature = "async")]
+impl<A: AsyncDataSource> PollDataSource<A> {
+    /// Creates a new `PollDataSource` from an `AsyncDataSource` instance.
+    #[inline]
+    pub fn new(inner: A) -> Self {
+        PollDataSource(inner)
+    }
+
+    /// Consumes self to reveal the underlying `AsyncDataSource` instance.
+    #[inline]
+    pub fn into_inner(self) -> A {
+        self.0
+    }
+}
+
+#[cfg(feature = "async")]
+impl<A: AsyncDataSource> DataSource for PollDataSource<A> {
+    type Item = A::Item;
+
+    #[inline]
+    fn read(&mut self, buffer: &mut [A::Item]) -> io::Result<usize> {
+        match self.0.poll_read(buffer) {
+            Ok(Async::Ready(n)) => Ok(n),
+            Ok(Async::NotReady) => Err(io::ErrorKind::WouldBlock.into()),
+            Err(e)              => Err(e),
+        }
+    }
+}
+
+/// Implementation of `AsyncDataSource` for `tokio_io::AsyncRead` instances.
+#[cfg(feature = "tokio")]
+#[derive(Debug)]
+pub struct AsyncReadDataSource<R: AsyncRead>(R);
+
+#[cfg(feature = "tokio")]
+impl<R: AsyncRead> AsyncReadDataSource<R> {
+    /// Creates a new `AsyncReadDataSource` from an `AsyncRead` instance.
+    #[inline]
+    pub fn new(inner: R) -> Self {
+        AsyncReadDataSource(inner)
+    }
+
+    /// Consumes self to reveal the underlying `AsyncRead` instance.
+    #[inline]
+    pub fn into_inner(self) -> R {
+        self.0
+    }
+}
+
+#[cfg(feature = "tokio")]
+impl<R: AsyncRead> AsyncDataSource for AsyncReadDataSource<R> {
+    type Item = u8;
+
+    #[inline]
+    fn poll_read(&mut self, buffer: &mut [u8]) -> Poll<usize, io::Error> {
+        self.0.poll_read(buffer)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use std::io;
+
+    use super::{ChannelDataSource, DataSource, IteratorDataSource, ReadDataSource};
+
+    #[test]
+    fn read() {
+        let mut s = ReadDataSource::new(io::Cursor::new(&b"abc"[..]));
+        let mut b = [0; 2];
+
+        assert_eq!(s.read(&mut b).unwrap(), 2);
+        assert_eq!(&b, b"ab");
+        assert_eq!(s.read(&mut b).unwrap(), 1);
+        assert_eq!(&b[..1], b"c");
+        assert_eq!(s.read(&mut b).unwrap(), 0);
+        assert_eq!(s.into_inner().position(), 3);
+    }
+
+    #[test]
+    fn iterator() {
+        let mut s = IteratorDataSource::new(vec![1, 2, 3].into_iter());
+        let mut b = [0; 2];
+
+        assert_eq!(s.read(&mut []).unwrap(), 0);
+        assert_eq!(s.read(&mut b).unwrap(), 2);
+        assert_eq!(b, [1, 2]);
+        assert_eq!(s.read(&mut b).unwrap(), 1);
+        assert_eq!(b[0], 3);
+        assert_eq!(s.read(&mut b).unwrap(), 0);
+        assert_eq!(s.into_inner().next(), None);
+    }
+
+    #[test]
+    fn chain() {
+        let mut s = IteratorDataSource::new(vec![1, 2].into_iter()).chain(IteratorDataSource::new(vec![3, 4, 5].into_iter()));
+        let mut b = [0; 2];
+
+        assert_eq!(s.read(&mut []).unwrap(), 0);
+        assert_eq!(s.read(&mut b).unwrap(), 2);
+        assert_eq!(b, [1, 2]);
+        assert_eq!(s.read(&mut b).unwrap(), 2);
+        assert_eq!(b, [3, 4]);
+        assert_eq!(s.read(&mut b).unwrap(), 1);
+        assert_eq!(b[0], 5);
+        assert_eq!(s.read(&mut b).unwrap(), 0);
+    }
+
+    #[test]
+    fn channel() {
+        use std::sync::mpsc;
+        use std::thread;
+
+        let (tx, rx) = mpsc::channel();
+
+        let t = thread::spawn(move || {
+            tx.send(vec![1, 2, 3]).unwrap();
+            tx.send(vec![]).unwrap();
+            tx.send(vec![4]).unwrap();
+        });
+
+        let mut s = ChannelDataSource::new(rx);
+        let mut b = [0; 2];
+
+        assert_eq!(s.read(&mut []).unwrap(), 0);
+        assert_eq!(s.read(&mut b).unwrap(), 2);
+        assert_eq!(b, [1, 2]);
+        assert_eq!(s.read(&mut b).unwrap(), 1);
+        assert_eq!(b[0], 3);
+        assert_eq!(s.read(&mut b).unwrap(), 1);
+        assert_eq!(b[0], 4);
+
+        t.join().unwrap();
+
+        assert_eq!(s.read(&mut b).unwrap(), 0);
+    }
+}
@@ -0,0 +1,174 @@
+use std::fs::File;
+use std::io;
+use std::path::Path;
+
+use memmap::{Mmap, Protection};
+
+use primitives::input;
+use primitives::{State, InputBuffer, IntoInner};
+
+use {Input, ParseResult};
+use buffer::{Mark, StreamError, Stream};
+
+/// Stream implementation for memory-mapped files, parsing the contents of the file in place
+/// instead of copying them through a `Buffer`.
+///
+/// The whole file is provided to the parser at once as the last slice of input, making this behave
+/// like `SliceStream` over the contents of the file.
+///
+/// The file must not be modified by other processes while it is mapped.
+///
+/// ```
+/// # #[macro_use] extern crate chomp;
+/// # fn main() {
+/// use chomp::{token, take_while, take_while1};
+/// use chomp::ascii::is_whitespace;
+/// use chomp::buffer::{MmapStream, Stream};
+///
+/// let mut s = MmapStream::open("./README.md").unwrap();
+///
+/// let r = s.parse(parser!{
+///     take_while(|c| c != b'#');
+///     token(b'#');
+///     take_while1(is_whitespace);
+///     take_while1(|c| c != b'\r' && c != b'\n')
+/// });
+///
+/// assert_eq!(r, Ok(&b"Chomp"[..]));
+/// # }
+/// ```
+#[derive(Debug)]
+pub struct MmapStream {
+    pos:  usize,
+    mmap: Mmap,
+}
+
+impl MmapStream {
+    /// Maps the file at the given path into memory.
+    ///
+    /// Fails if the file cannot be opened or mapped, mapping an empty file fails on some
+    /// platforms.
+    #[inline]
+    pub fn open<P: AsRef<Path>>(path: P) -> io::Result<Self> {
+        Mmap::open_path(path, Protection::Read).map(Self::new)
+    }
+
+    /// Maps the given file into memory.
+    #[inline]
+    pub fn from_file(file: &File) -> io::Result<Self> {
+        Mmap::open(file, Protection::Read).map(Self::new)
+    }
+
+    /// Creates a new stream from an existing read-only memory map.
+    #[inline]
+    pub fn new(mmap: Mmap) -> Self {
+        MmapStream {
+            pos:  0,
+            mmap: mmap,
+        }
+    }
+
+    /// The number of bytes left in the stream.
+    #[inline]
+    pub fn len(&self) -> usize {
+        self.mmap.len() - self.pos
+    }
+
+    /// Returns true if no more bytes are available.
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Returns the number of bytes consumed since the start of the file.
+    #[inline]
+    pub fn offset(&self) -> usize {
+        self.pos
+    }
+
+    /// Returns a mark of the current position of the stream which can be used with `rewind` to
+    /// return to this position.
+    #[inline]
+    pub fn mark(&self) -> Mark {
+        Mark(self.pos)
+    }
+
+    /// Rewinds the stream to the position of the mark, allowing the input to be parsed again.
+    ///
+    /// # Panics
+    ///
+    /// If the mark is outside of the file, which can happen if it was created by another stream.
+    #[inline]
+    pub fn rewind(&mut self, mark: Mark) {
+        assert!(mark.0 <= self.mmap.len());
+
+        self.pos = mark.0;
+    }
+}
+
+impl<'a> Stream<'a, 'a> for MmapStream {
+    type Item = u8;
+
+    #[inline]
+    fn parse<F, T, E>(&'a mut self, f: F) -> Result<T, StreamError<'a, Self::Item, E>>
+      where F: FnOnce(Input<'a, Self::Item>) -> ParseResult<'a, Self::Item, T, E>,
+            T: 'a,
+            E: 'a {
+        if self.is_empty() {
+            return Err(StreamError::EndOfInput);
+        }
+
+        // The file is mapped read-only, concurrent modification is documented as not allowed
+        let slice = unsafe { &self.mmap.as_slice()[self.pos..] };
+
+        match f(input::new_at(input::END_OF_INPUT, self.pos, slice)).into_inner() {
+            State::Data(remainder, data) => {
+                self.pos += slice.len() - remainder.buffer().len();
+
+                Ok(data)
+            },
+            State::Error(remainder, err) => {
+                self.pos += slice.len() - remainder.len();
+
+                Err(StreamError::ParseError(remainder, err))
+            },
+            State::Incomplete(n) => Err(StreamError::Incomplete(n + slice.len())),
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use std::fs::File;
+    use std::io::Read;
+
+    use {any, take_while};
+    use buffer::{StreamError, Stream};
+
+    use super::MmapStream;
+
+    #[test]
+    fn parse_file() {
+        let mut contents = Vec::new();
+
+        File::open("./Cargo.toml").unwrap().read_to_end(&mut contents).unwrap();
+
+        let mut s = MmapStream::from_file(&File::open("./Cargo.toml").unwrap()).unwrap();
+
+        assert_eq!(s.len(), contents.len());
+        assert_eq!(s.parse(|i| take_while(i, |c| c != b'\n')), Ok(&b"[package]"[..]));
+        assert_eq!(s.offset(), 9);
+
+        let m = s.mark();
+
+        assert_eq!(s.parse(any), Ok(b'\n'));
+        assert_eq!(s.parse(|i| take_while(i, |_| true)), Ok(&contents[10..]));
+        assert!(s.is_empty());
+        assert_eq!(s.parse(any), Err(StreamError::EndOfInput));
+
+        s.rewind(m);
+
+        assert_eq!(s.offset(), 9);
+        assert_eq!(s.parse(any), Ok(b'\n'));
+    }
+}
@@ -26,25 +26,82 @@
 //! assert_eq!(r, Ok(&b"Chomp"[..]));
 //! # }
 //! ```
+//!
+//! Repeatedly parsing items until the end of the input, refilling the buffer as needed, is done
+//! using `Source::frames`:
+//!
+//! ```
+//! use std::fs::File;
+//!
+//! use chomp::{take_till, token};
+//! use chomp::buffer::{FixedSizeBuffer, Source};
+//!
+//! let f = File::open("./README.md").unwrap();
+//!
+//! let mut b = Source::from_read(f, FixedSizeBuffer::new());
+//!
+//! let lines = b.frames(|i| take_till(i, |c| c == b'\n').bind(|i, l| token(i, b'\n').map(|_| l.len())));
+//!
+//! for line in lines {
+//!     // Each item is the length of a line, or an error which ends the iteration
+//!     let _: usize = line.unwrap();
+//! }
+//! ```
+//!
+//! Items which borrow the buffer cannot be yielded from an iterator since the buffer is reused
+//! for the next item, use `Stream::parse` in a loop instead:
+//!
+//! ```
+//! use std::fs::File;
+//!
+//! use chomp::{take_till, token};
+//! use chomp::buffer::{FixedSizeBuffer, Source, Stream, StreamError};
+//!
+//! let f = File::open("./README.md").unwrap();
+//!
+//! let mut b = Source::from_read(f, FixedSizeBuffer::new());
+//!
+//! loop {
+//!     match b.parse(|i| take_till(i, |c| c == b'\n').bind(|i, l| token(i, b'\n').map(|_| l))) {
+//!         Ok(_line)                    => {},
+//!         // Needed to refill the buffer, automatically done on the next call to parse()
+//!         Err(StreamError::Retry)      => {},
+//!         Err(StreamError::EndOfInput) => break,
+//!         Err(e)                       => panic!("{:?}", e),
+//!     }
+//! }
+//! ```
 
 mod stateful;
 mod buffer;
+mod buf_read;
 mod slice;
+#[cfg(feature = "mmap")]
+mod mmap;
 
 pub mod data_source;
 
+use std::error;
+use std::fmt;
 use std::io;
 
 use {ParseResult, Input};
 use parse::ParseError;
 
 pub use self::slice::SliceStream;
+pub use self::buf_read::BufReadStream;
+#[cfg(feature = "mmap")]
+pub use self::mmap::MmapStream;
 pub use self::data_source::DataSource;
-pub use self::stateful::Source;
+pub use self::stateful::{Frames, Source, SourceBuilder, Stats};
+#[cfg(feature = "async")]
+pub use self::stateful::ParseAsync;
 pub use self::buffer::{
     Buffer,
+    BufferPool,
     FixedSizeBuffer,
     GrowingBuffer,
+    PooledBuffer,
 };
 
 /// Error type for parsing using the `Stream` trait.
@@ -52,8 +109,14 @@ pub use self::buffer::{
 pub enum StreamError<'a, I, E>
   where I: 'a {
     /// An error occurred in the parser, the given slice indicates the part which failed.
+    ///
+    /// The input is consumed up to the start of the slice, `Source::offset` yields the absolute
+    /// offset of the error.
     ParseError(&'a [I], E),
     /// Parser failed to complete with the available data.
+    ///
+    /// Contains the total number of items the parser required from the start of the unparsed
+    /// input, including the items which were available.
     Incomplete(usize),
     /// An IO-error occurred while attempting to fill the buffer.
     IoError(io::Error),
@@ -63,6 +126,16 @@ pub enum StreamError<'a, I, E>
     ///
     /// Filling the buffer is automatic by default.
     Retry,
+    /// The data source is non-blocking and no data is currently available, retry once it is
+    /// ready again.
+    ///
+    /// All buffered data and state is retained.
+    WouldBlock,
+    /// The last parser required more data than the buffer can hold, contains the number of items
+    /// requested and the capacity of the buffer.
+    ///
+    /// Parsing can be attempted again with a parser requiring less data.
+    BufferLimitExceeded(usize, usize),
 }
 
 impl<'a, I, E> PartialEq for StreamError<'a, I, E>
@@ -74,11 +147,144 @@ impl<'a, I, E> PartialEq for StreamError<'a, I, E>
             (&StreamError::Incomplete(n1), &StreamError::Incomplete(n2)) => n1 == n2,
             (&StreamError::EndOfInput, &StreamError::EndOfInput) => true,
             (&StreamError::Retry, &StreamError::Retry) => true,
+            (&StreamError::WouldBlock, &StreamError::WouldBlock) => true,
+            (&StreamError::BufferLimitExceeded(r1, c1), &StreamError::BufferLimitExceeded(r2, c2)) => r1 == r2 && c1 == c2,
+            _ => false,
+        }
+    }
+}
+
+/// Error type yielded by the `Frames` iterator.
+///
+/// Unlike `StreamError` this does not borrow the buffer, parse errors instead carry the absolute
+/// offset at which they occurred.
+#[derive(Debug)]
+pub enum FrameError<E> {
+    /// An error occurred in the parser at the given offset from the start of the source.
+    ParseError(usize, E),
+    /// The input ended in the middle of a frame, contains the number of items the frame required.
+    Incomplete(usize),
+    /// An IO-error occurred while attempting to fill the buffer.
+    IoError(io::Error),
+    /// The frame required more data than the buffer can hold, contains the number of items
+    /// requested and the capacity of the buffer.
+    BufferLimitExceeded(usize, usize),
+}
+
+impl<E> PartialEq for FrameError<E>
+  where E: PartialEq {
+    #[inline]
+    fn eq(&self, other: &FrameError<E>) -> bool {
+        match (self, other) {
+            (&FrameError::ParseError(n1, ref e1), &FrameError::ParseError(n2, ref e2)) => n1 == n2 && e1 == e2,
+            (&FrameError::Incomplete(n1), &FrameError::Incomplete(n2)) => n1 == n2,
+            (&FrameError::BufferLimitExceeded(r1, c1), &FrameError::BufferLimitExceeded(r2, c2)) => r1 == r2 && c1 == c2,
             _ => false,
         }
     }
 }
 
+impl<'a, I, E> StreamError<'a, I, E> {
+    /// Converts an IO-error to `StreamError::WouldBlock` if it is of kind `WouldBlock`, otherwise
+    /// to `StreamError::IoError`.
+    #[inline]
+    fn from_io(e: io::Error) -> Self {
+        match e.kind() {
+            io::ErrorKind::WouldBlock => StreamError::WouldBlock,
+            _                         => StreamError::IoError(e),
+        }
+    }
+}
+
+impl<'a, I, E> fmt::Display for StreamError<'a, I, E>
+  where E: fmt::Display {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match *self {
+            StreamError::ParseError(_, ref e) => write!(f, "parse error: {}", e),
+            StreamError::Incomplete(n)        => write!(f, "incomplete input, {} items required", n),
+            StreamError::IoError(ref e)       => write!(f, "io error: {}", e),
+            StreamError::EndOfInput           => write!(f, "end of input"),
+            StreamError::Retry                => write!(f, "more input required, retry"),
+            StreamError::WouldBlock           => write!(f, "operation would block"),
+            StreamError::BufferLimitExceeded(r, c) => write!(f, "buffer limit exceeded, {} items requested but the buffer can hold {}", r, c),
+        }
+    }
+}
+
+impl<'a, I, E> error::Error for StreamError<'a, I, E>
+  where I: fmt::Debug,
+        E: error::Error {
+    fn description(&self) -> &str {
+        match *self {
+            StreamError::ParseError(_, _) => "parse error",
+            StreamError::Incomplete(_)    => "incomplete input",
+            StreamError::IoError(_)       => "io error",
+            StreamError::EndOfInput       => "end of input",
+            StreamError::Retry            => "more input required, retry",
+            StreamError::WouldBlock       => "operation would block",
+            StreamError::BufferLimitExceeded(_, _) => "buffer limit exceeded",
+        }
+    }
+}
+
+/// Converts the error into an `io::Error`, IO-errors are returned as is. Parse errors are
+/// converted to a message of kind `InvalidData` since they borrow the buffer.
+impl<'a, I, E> From<StreamError<'a, I, E>> for io::Error
+  where E: fmt::Display {
+    fn from(e: StreamError<'a, I, E>) -> Self {
+        let kind = match e {
+            StreamError::IoError(e)       => return e,
+            StreamError::ParseError(_, _) => io::ErrorKind::InvalidData,
+            StreamError::Incomplete(_)    => io::ErrorKind::UnexpectedEof,
+            StreamError::EndOfInput       => io::ErrorKind::UnexpectedEof,
+            StreamError::Retry            => io::ErrorKind::WouldBlock,
+            StreamError::WouldBlock       => io::ErrorKind::WouldBlock,
+            StreamError::BufferLimitExceeded(_, _) => io::ErrorKind::InvalidData,
+        };
+
+        io::Error::new(kind, e.to_string())
+    }
+}
+
+impl<E> fmt::Display for FrameError<E>
+  where E: fmt::Display {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match *self {
+            FrameError::ParseError(n, ref e) => write!(f, "parse error at offset {}: {}", n, e),
+            FrameError::Incomplete(n)        => write!(f, "incomplete frame, {} items required", n),
+            FrameError::IoError(ref e)       => write!(f, "io error: {}", e),
+            FrameError::BufferLimitExceeded(r, c) => write!(f, "buffer limit exceeded, {} items requested but the buffer can hold {}", r, c),
+        }
+    }
+}
+
+impl<E> error::Error for FrameError<E>
+  where E: error::Error {
+    fn description(&self) -> &str {
+        match *self {
+            FrameError::ParseError(_, _) => "parse error",
+            FrameError::Incomplete(_)    => "incomplete frame",
+            FrameError::IoError(_)       => "io error",
+            FrameError::BufferLimitExceeded(_, _) => "buffer limit exceeded",
+        }
+    }
+}
+
+/// Converts the error into an `io::Error`, IO-errors are returned as is.
+impl<E> From<FrameError<E>> for io::Error
+  where E: fmt::Display {
+    fn from(e: FrameError<E>) -> Self {
+        let kind = match e {
+            FrameError::IoError(e)       => return e,
+            FrameError::ParseError(_, _) => io::ErrorKind::InvalidData,
+            FrameError::Incomplete(_)    => io::ErrorKind::UnexpectedEof,
+            FrameError::BufferLimitExceeded(_, _) => io::ErrorKind::InvalidData,
+        };
+
+        io::Error::new(kind, e.to_string())
+    }
+}
+
 impl<'a, I, E> From<ParseError<'a, I, E>> for StreamError<'a, I, E>
   where I: 'a {
     fn from(e: ParseError<'a, I, E>) -> Self {
@@ -89,6 +295,10 @@ impl<'a, I, E> From<ParseError<'a, I, E>> for StreamError<'a, I, E>
     }
 }
 
+/// Position in a stream which can be returned to, created by `SliceStream::mark`.
+#[derive(Clone, Copy, Debug, Eq, PartialEq, Hash)]
+pub struct Mark(usize);
+
 /// Trait wrapping the state management in reading from a data source while parsing.
 pub trait Stream<'a, 'i> {
     /// The input item type, usually depending on which `DataSource` is used.
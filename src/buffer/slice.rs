@@ -2,7 +2,7 @@ use primitives::input;
 use primitives::{State, InputBuffer, IntoInner};
 
 use {Input, ParseResult};
-use buffer::{IntoStream, StreamError, Stream};
+use buffer::{IntoStream, Mark, StreamError, Stream};
 
 /// Stream implementation for immutable slices.
 ///
@@ -66,6 +66,39 @@ impl<'i, I: 'i> SliceStream<'i, I> {
     pub fn is_empty(&self) -> bool {
         self.len() == 0
     }
+
+    /// Returns a mark of the current position of the stream which can be used with `rewind` to
+    /// return to this position.
+    ///
+    /// ```
+    /// use chomp::{string, token};
+    /// use chomp::buffer::{IntoStream, Stream};
+    ///
+    /// let mut s = (&b"GET /"[..]).into_stream();
+    /// let m     = s.mark();
+    ///
+    /// assert!(s.parse(|i| token(i, b'G').then(|i| string(i, b"ET /foo"))).is_err());
+    ///
+    /// s.rewind(m);
+    ///
+    /// assert_eq!(s.parse(|i| string(i, b"GET")), Ok(&b"GET"[..]));
+    /// ```
+    #[inline]
+    pub fn mark(&self) -> Mark {
+        Mark(self.pos)
+    }
+
+    /// Rewinds the stream to the position of the mark, allowing the input to be parsed again.
+    ///
+    /// # Panics
+    ///
+    /// If the mark is outside of the slice, which can happen if it was created by another stream.
+    #[inline]
+    pub fn rewind(&mut self, mark: Mark) {
+        assert!(mark.0 <= self.slice.len());
+
+        self.pos = mark.0;
+    }
 }
 
 impl<'a, 'i, I: 'i> IntoStream<'a, 'i> for &'i [I] {
@@ -78,6 +111,29 @@ impl<'a, 'i, I: 'i> IntoStream<'a, 'i> for &'i [I] {
     }
 }
 
+/// Streams the UTF-8 encoded bytes of the string, parsers like `unicode::string_utf8` and
+/// `unicode::utf8_char` can be used to parse text from it.
+///
+/// ```
+/// use chomp::take_remainder;
+/// use chomp::buffer::{IntoStream, Stream};
+/// use chomp::unicode::string_utf8;
+///
+/// let mut s = "föo".into_stream();
+///
+/// assert_eq!(s.parse(|i| string_utf8(i, "fö")), Ok("fö"));
+/// assert_eq!(s.parse(take_remainder), Ok(&b"o"[..]));
+/// ```
+impl<'a, 'i> IntoStream<'a, 'i> for &'i str {
+    type Item = u8;
+    type Into = SliceStream<'i, u8>;
+
+    #[inline]
+    fn into_stream(self) -> SliceStream<'i, u8> {
+        SliceStream::new(self.as_bytes())
+    }
+}
+
 impl<'a, 'i, I: 'i> Stream<'a, 'i> for SliceStream<'i, I> {
     type Item = I;
 
@@ -90,7 +146,7 @@ impl<'a, 'i, I: 'i> Stream<'a, 'i> for SliceStream<'i, I> {
             return Err(StreamError::EndOfInput);
         }
 
-        match f(input::new(input::END_OF_INPUT, &self.slice[self.pos..])).into_inner() {
+        match f(input::new_at(input::END_OF_INPUT, self.pos, &self.slice[self.pos..])).into_inner() {
             State::Data(remainder, data) => {
                 // TODO: Do something neater with the remainder
                 self.pos += self.len() - remainder.buffer().len();
@@ -1,5 +1,9 @@
 use std::io;
 use std::cmp;
+use std::sync::mpsc::Receiver;
+
+#[cfg(feature = "async")]
+use futures::{Async, Future, Poll};
 
 use {Input, ParseResult};
 use primitives::input;
@@ -9,10 +13,19 @@ use buffer::{
     Buffer,
     DataSource,
     FixedSizeBuffer,
+    FrameError,
+    GrowingBuffer,
     StreamError,
     Stream,
 };
-use buffer::data_source::{IteratorDataSource, ReadDataSource};
+use buffer::buffer::DEFAULT_BUFFER_SIZE;
+use buffer::data_source::{ChannelDataSource, IteratorDataSource, ReadDataSource};
+#[cfg(feature = "async")]
+use buffer::data_source::{AsyncDataSource, PollDataSource};
+#[cfg(feature = "tokio")]
+use buffer::data_source::AsyncReadDataSource;
+#[cfg(feature = "tokio")]
+use tokio_io::AsyncRead;
 
 bitflags!{
     flags ParserState: u64 {
@@ -24,6 +37,8 @@ bitflags!{
         const END_OF_INPUT   = 2,
         /// `parse()` should attempt to read more data whenever the `INCOMPLETE` flag is set.
         const AUTOMATIC_FILL = 4,
+        /// The buffer is full but cannot hold the amount of data requested by the last parser.
+        const LIMIT_EXCEEDED = 8,
     }
 }
 
@@ -36,8 +51,26 @@ pub struct Source<S: DataSource, B: Buffer<S::Item>> {
     buffer:  B,
     /// The requested amount of bytes to be available for reading from the buffer
     request: usize,
+    /// Number of items consumed since the start of the source
+    offset:  usize,
     /// Input state, if end has been reached
     state:   ParserState,
+    /// Statistics of the reads from the source
+    stats:   Stats,
+}
+
+/// Statistics of a `Source`, useful when tuning buffer sizes.
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq, Hash)]
+pub struct Stats {
+    /// Number of items read from the data source.
+    pub items_read:  usize,
+    /// Number of successful reads from the data source which yielded data.
+    pub reads:       usize,
+    /// Number of times the buffer moved its data to free space for more items, see
+    /// `Buffer::compactions`.
+    pub compactions: usize,
+    /// The largest number of items which have been stored in the buffer at once.
+    pub peak_len:    usize,
 }
 
 impl<R: io::Read> Source<ReadDataSource<R>, FixedSizeBuffer<u8>> {
@@ -48,6 +81,32 @@ impl<R: io::Read> Source<ReadDataSource<R>, FixedSizeBuffer<u8>> {
     }
 }
 
+impl<R: io::Read> Source<ReadDataSource<R>, GrowingBuffer<u8>> {
+    /// Creates a `SourceBuilder` for configuring a `Source` reading from a `Read` instance.
+    ///
+    /// ```
+    /// use std::io;
+    ///
+    /// use chomp::take;
+    /// use chomp::buffer::{Source, Stream, StreamError};
+    ///
+    /// let mut s = Source::builder(io::Cursor::new(&b"abcdefgh"[..]))
+    ///     .buffer_size(2)
+    ///     .growing(true)
+    ///     .limit(4)
+    ///     .build();
+    ///
+    /// assert_eq!(s.parse(|i| take(i, 3)), Err(StreamError::Retry));
+    /// assert_eq!(s.parse(|i| take(i, 3)), Ok(&b"abc"[..]));
+    /// assert_eq!(s.parse(|i| take(i, 5)), Err(StreamError::Retry));
+    /// assert_eq!(s.parse(|i| take(i, 5)), Err(StreamError::BufferLimitExceeded(5, 4)));
+    /// ```
+    #[inline]
+    pub fn builder(source: R) -> SourceBuilder<ReadDataSource<R>> {
+        SourceBuilder::new(ReadDataSource::new(source))
+    }
+}
+
 impl<R: io::Read, B: Buffer<u8>> Source<ReadDataSource<R>, B> {
     /// Creates a new `Source` from `Read` and buffer instances.
     #[inline]
@@ -64,6 +123,77 @@ impl<I: Iterator, B: Buffer<I::Item>> Source<IteratorDataSource<I>, B> {
     }
 }
 
+impl<T: Copy, B: Buffer<T>> Source<ChannelDataSource<T>, B> {
+    /// Creates a new `Source` from `Receiver` and `Buffer` instances, the end of the input is
+    /// reached once all senders have been dropped.
+    ///
+    /// ```
+    /// use std::sync::mpsc;
+    /// use std::thread;
+    ///
+    /// use chomp::take;
+    /// use chomp::buffer::{FixedSizeBuffer, Source};
+    ///
+    /// let (tx, rx) = mpsc::channel();
+    ///
+    /// thread::spawn(move || {
+    ///     tx.send(b"foo".to_vec()).unwrap();
+    ///     tx.send(b"bar".to_vec()).unwrap();
+    /// }).join().unwrap();
+    ///
+    /// let mut s = Source::from_channel(rx, FixedSizeBuffer::with_size(8));
+    ///
+    /// let r: Result<Vec<_>, _> = s.frames(|i| take(i, 2).map(|b| b.to_vec())).collect();
+    ///
+    /// assert_eq!(r.unwrap(), vec![b"fo".to_vec(), b"ob".to_vec(), b"ar".to_vec()]);
+    /// ```
+    #[inline]
+    pub fn from_channel(source: Receiver<Vec<T>>, buffer: B) -> Self {
+        Self::with_buffer(ChannelDataSource::new(source), buffer)
+    }
+}
+
+#[cfg(feature = "async")]
+impl<A: AsyncDataSource, B: Buffer<A::Item>> Source<PollDataSource<A>, B> {
+    /// Creates a new `Source` from `AsyncDataSource` and `Buffer` instances, use `parse_async` to
+    /// parse from it.
+    #[inline]
+    pub fn from_async(source: A, buffer: B) -> Self {
+        Self::with_buffer(PollDataSource::new(source), buffer)
+    }
+}
+
+#[cfg(feature = "tokio")]
+impl<R: AsyncRead, B: Buffer<u8>> Source<PollDataSource<AsyncReadDataSource<R>>, B> {
+    /// Creates a new `Source` from `tokio_io::AsyncRead` and `Buffer` instances, use
+    /// `parse_async` to parse from it.
+    ///
+    /// Data is only read from the reader once a parser requires more data than is available in
+    /// the buffer and never more than the buffer can hold, leaving the remaining data in the
+    /// reader until the parsers have caught up.
+    ///
+    /// ```
+    /// # extern crate chomp;
+    /// # extern crate futures;
+    /// # fn main() {
+    /// use futures::Future;
+    ///
+    /// use chomp::{any, take};
+    /// use chomp::buffer::{FixedSizeBuffer, Source};
+    ///
+    /// let s = Source::from_async_read(&b"\x03abc"[..], FixedSizeBuffer::new());
+    ///
+    /// let (_, r) = s.parse_async(|i| any(i).bind(|i, n| take(i, n as usize)).map(|b| b.to_vec())).wait().unwrap();
+    ///
+    /// assert_eq!(r, Some(b"abc".to_vec()));
+    /// # }
+    /// ```
+    #[inline]
+    pub fn from_async_read(source: R, buffer: B) -> Self {
+        Self::from_async(AsyncReadDataSource::new(source), buffer)
+    }
+}
+
 impl<S: DataSource, B: Buffer<S::Item>> Source<S, B> {
     /// Creates a new `Source` from `DataSource` and `Buffer` instances.
     #[inline]
@@ -72,7 +202,9 @@ impl<S: DataSource, B: Buffer<S::Item>> Source<S, B> {
             source:  source,
             buffer:  buffer,
             request: 0,
+            offset:  0,
             state:   INCOMPLETE | AUTOMATIC_FILL,
+            stats:   Stats::default(),
         }
     }
 
@@ -83,6 +215,7 @@ impl<S: DataSource, B: Buffer<S::Item>> Source<S, B> {
 
         let mut buffer = &mut self.buffer;
         let     source = &mut self.source;
+        let     stats  = &mut self.stats;
 
         if buffer.len() < request {
             let diff = request - buffer.len();
@@ -92,7 +225,13 @@ impl<S: DataSource, B: Buffer<S::Item>> Source<S, B> {
             while buffer.len() < request {
                 match try!(buffer.fill(source)) {
                     0 => break,
-                    n => read = read + n,
+                    n => {
+                        read = read + n;
+
+                        stats.items_read += n;
+                        stats.reads      += 1;
+                        stats.peak_len    = cmp::max(stats.peak_len, buffer.len());
+                    },
                 }
             }
         }
@@ -111,6 +250,9 @@ impl<S: DataSource, B: Buffer<S::Item>> Source<S, B> {
 
             if self.buffer.len() >= req {
                 self.state.remove(END_OF_INPUT);
+            } else if self.buffer.len() >= self.buffer.capacity() {
+                // The source might still have data, but the buffer is full
+                self.state.insert(LIMIT_EXCEEDED);
             } else {
                 self.state.insert(END_OF_INPUT);
             }
@@ -119,6 +261,20 @@ impl<S: DataSource, B: Buffer<S::Item>> Source<S, B> {
         })
     }
 
+    /// Returns the number of additional items the last call to `parse()` required before it
+    /// returned `StreamError::Retry`, or 0 if no more input is currently required.
+    ///
+    /// Useful when automatic fill is disabled to read exactly the amount of data needed from
+    /// the underlying source before calling `fill()`.
+    #[inline]
+    pub fn needed(&self) -> usize {
+        if self.state.contains(INCOMPLETE) {
+            self.request.saturating_sub(self.buffer.len())
+        } else {
+            0
+        }
+    }
+
     /// Returns the number of bytes left in the buffer which have not yet been parsed.
     #[inline]
     pub fn len(&self) -> usize {
@@ -145,6 +301,117 @@ impl<S: DataSource, B: Buffer<S::Item>> Source<S, B> {
         &self.buffer
     }
 
+    /// Returns statistics about the reads from the data source and the usage of the buffer.
+    ///
+    /// ```
+    /// use std::io;
+    ///
+    /// use chomp::take;
+    /// use chomp::buffer::{FixedSizeBuffer, Source, Stream};
+    ///
+    /// let mut s = Source::from_read(io::Cursor::new(&b"abcdef"[..]), FixedSizeBuffer::with_size(4));
+    ///
+    /// assert_eq!(s.parse(|i| take(i, 3)), Ok(&b"abc"[..]));
+    /// assert_eq!(s.parse(|i| take(i, 3)), Err(chomp::buffer::StreamError::Retry));
+    /// assert_eq!(s.parse(|i| take(i, 3)), Ok(&b"def"[..]));
+    ///
+    /// let stats = s.stats();
+    ///
+    /// assert_eq!(stats.items_read, 6);
+    /// assert_eq!(stats.reads, 2);
+    /// assert_eq!(stats.compactions, 1);
+    /// assert_eq!(stats.peak_len, 4);
+    /// ```
+    #[inline]
+    pub fn stats(&self) -> Stats {
+        Stats {
+            compactions: self.buffer.compactions(),
+            ..self.stats
+        }
+    }
+
+    /// Returns the number of items consumed since the start of the source, which is the absolute
+    /// offset of the start of the buffer.
+    #[inline]
+    pub fn offset(&self) -> usize {
+        self.offset
+    }
+
+    /// Returns an iterator parsing consecutive frames using `f`, eg. the messages of a
+    /// length-prefixed protocol.
+    ///
+    /// The buffer is refilled whenever a frame is incomplete, and the iterator ends once the
+    /// source is exhausted between two frames. Input ending in the middle of a frame yields
+    /// `FrameError::Incomplete`. Errors end the iteration, except for IO-errors of kind
+    /// `WouldBlock` after which the iteration can be resumed once the source is ready.
+    ///
+    /// ```
+    /// use std::io;
+    ///
+    /// use chomp::{any, take};
+    /// use chomp::buffer::{FixedSizeBuffer, Source};
+    ///
+    /// let mut s = Source::from_read(io::Cursor::new(&b"\x02ab\x03cde"[..]), FixedSizeBuffer::with_size(4));
+    ///
+    /// let r: Result<Vec<_>, _> = s.frames(|i| any(i).bind(|i, n| take(i, n as usize)).map(|b| b.to_vec())).collect();
+    ///
+    /// assert_eq!(r.unwrap(), vec![b"ab".to_vec(), b"cde".to_vec()]);
+    /// ```
+    #[inline]
+    pub fn frames<'s, F, T, E>(&'s mut self, f: F) -> Frames<'s, S, B, F>
+      where F: for<'a> FnMut(Input<'a, S::Item>) -> ParseResult<'a, S::Item, T, E> {
+        Frames {
+            source: self,
+            parser: f,
+            done:   false,
+        }
+    }
+
+    /// Returns a future parsing one value using `f`, resolving once enough data has been read to
+    /// complete or definitively fail the parse.
+    ///
+    /// The future yields the source along with the parsed value, or `None` if the source was
+    /// exhausted before any data could be parsed. IO-errors of kind `WouldBlock` make the future
+    /// return `Async::NotReady`, which means the data source is responsible for notifying the
+    /// current task once it is ready again.
+    ///
+    /// ```
+    /// # extern crate chomp;
+    /// # extern crate futures;
+    /// # fn main() {
+    /// use std::io;
+    ///
+    /// use futures::Future;
+    ///
+    /// use chomp::take;
+    /// use chomp::buffer::{FixedSizeBuffer, Source};
+    ///
+    /// let s = Source::from_read(io::Cursor::new(&b"abcde"[..]), FixedSizeBuffer::with_size(4));
+    ///
+    /// let (s, r) = s.parse_async(|i| take(i, 3).map(|b| b.to_vec())).wait().unwrap();
+    ///
+    /// assert_eq!(r, Some(b"abc".to_vec()));
+    /// assert_eq!(s.offset(), 3);
+    /// # }
+    /// ```
+    #[cfg(feature = "async")]
+    #[inline]
+    pub fn parse_async<F, T, E>(self, f: F) -> ParseAsync<S, B, F>
+      where F: for<'a> FnMut(Input<'a, S::Item>) -> ParseResult<'a, S::Item, T, E> {
+        ParseAsync {
+            source: Some(self),
+            parser: f,
+        }
+    }
+
+    /// Consumes `n` items from the buffer, keeping track of the offset.
+    #[inline]
+    fn advance(&mut self, n: usize) {
+        self.buffer.consume(n);
+
+        self.offset += n;
+    }
+
     /// Resets the buffer state, keeping the current buffer contents and cursor position.
     ///
     /// This is useful when streaming data and more data has been made available on a
@@ -156,7 +423,6 @@ impl<S: DataSource, B: Buffer<S::Item>> Source<S, B> {
 
     /// Changes the setting automatic fill feature, `true` will make the buffer automatically
     /// call `fill()` on the next call to `parse()` after a `Retry` was encountered.
-    // TODO: Make a part of the constructor/builder
     #[inline]
     pub fn set_autofill(&mut self, value: bool) {
         if value {
@@ -175,7 +441,7 @@ impl<S: DataSource<Item=u8>, B: Buffer<u8>> io::Read for Source<S, B> {
         }
 
         (&self.buffer[..]).read(buf).map(|n| {
-            self.buffer.consume(n);
+            self.advance(n);
 
             n
         })
@@ -194,7 +460,7 @@ impl<S: DataSource<Item=u8>, B: Buffer<u8>> io::BufRead for Source<S, B> {
 
     #[inline]
     fn consume(&mut self, num: usize) {
-        self.buffer.consume(num)
+        self.advance(num)
     }
 }
 
@@ -208,7 +474,13 @@ impl<'a, S: DataSource, B: Buffer<S::Item>> Stream<'a, 'a> for Source<S, B>
             T: 'a,
             E: 'a {
         if self.state.contains(INCOMPLETE | AUTOMATIC_FILL) {
-            try!(self.fill().map_err(StreamError::IoError));
+            try!(self.fill().map_err(StreamError::from_io));
+        }
+
+        if self.state.contains(LIMIT_EXCEEDED) {
+            self.state.remove(LIMIT_EXCEEDED);
+
+            return Err(StreamError::BufferLimitExceeded(self.request, self.buffer.capacity()));
         }
 
         if self.is_empty() {
@@ -217,17 +489,23 @@ impl<'a, S: DataSource, B: Buffer<S::Item>> Stream<'a, 'a> for Source<S, B>
 
         let input_state = if self.state.contains(END_OF_INPUT) { input::END_OF_INPUT } else { input::DEFAULT };
 
-        match f(input::new(input_state, &self.buffer)).into_inner() {
+        match f(input::new_at(input_state, self.offset, &self.buffer)).into_inner() {
             State::Data(remainder, data) => {
                 // TODO: Do something neater with the remainder
-                self.buffer.consume(self.buffer.len() - remainder.buffer().len());
+                let n = self.buffer.len() - remainder.buffer().len();
+
+                self.buffer.consume(n);
+                self.offset += n;
 
                 Ok(data)
             },
             State::Error(remainder, err) => {
                 // TODO: Do something neater with the remainder
                 // TODO: Detail this behaviour, maybe make it configurable
-                self.buffer.consume(self.buffer.len() - remainder.len());
+                let n = self.buffer.len() - remainder.len();
+
+                self.buffer.consume(n);
+                self.offset += n;
 
                 Err(StreamError::ParseError(remainder, err))
             },
@@ -246,13 +524,203 @@ impl<'a, S: DataSource, B: Buffer<S::Item>> Stream<'a, 'a> for Source<S, B>
     }
 }
 
+/// Builder for configuring the buffer size, growth limit and refill policy of a `Source`, created
+/// by `Source::builder` or `SourceBuilder::new`.
+///
+/// By default the buffer has a fixed size of 6 KB and is automatically filled.
+#[derive(Debug)]
+pub struct SourceBuilder<S: DataSource> {
+    source:   S,
+    size:     usize,
+    growing:  bool,
+    limit:    usize,
+    autofill: bool,
+}
+
+impl<S: DataSource> SourceBuilder<S>
+  where S::Item: Copy + Default {
+    /// Creates a new builder for a `Source` reading from the supplied `DataSource`.
+    #[inline]
+    pub fn new(source: S) -> Self {
+        SourceBuilder {
+            source:   source,
+            size:     DEFAULT_BUFFER_SIZE,
+            growing:  false,
+            limit:    0,
+            autofill: true,
+        }
+    }
+
+    /// Sets the initial size of the buffer.
+    #[inline]
+    pub fn buffer_size(mut self, size: usize) -> Self {
+        assert!(size > 0);
+
+        self.size = size;
+
+        self
+    }
+
+    /// Sets if the buffer is allowed to grow to fit the data requested by parsers.
+    #[inline]
+    pub fn growing(mut self, value: bool) -> Self {
+        self.growing = value;
+
+        self
+    }
+
+    /// Sets the maximum size of a growing buffer, 0 means no limit.
+    ///
+    /// Requests larger than this result in `StreamError::BufferLimitExceeded`. Only used if the
+    /// buffer is growing.
+    #[inline]
+    pub fn limit(mut self, limit: usize) -> Self {
+        self.limit = limit;
+
+        self
+    }
+
+    /// Sets if the buffer should be automatically filled on the next call to `parse()` after a
+    /// `Retry` was encountered, see `Source::set_autofill`.
+    #[inline]
+    pub fn autofill(mut self, value: bool) -> Self {
+        self.autofill = value;
+
+        self
+    }
+
+    /// Creates the configured `Source`.
+    #[inline]
+    pub fn build(self) -> Source<S, GrowingBuffer<S::Item>> {
+        let limit = if self.growing { self.limit } else { self.size };
+
+        let mut s = Source::with_buffer(self.source, GrowingBuffer::with_capacity(self.size, limit));
+
+        s.set_autofill(self.autofill);
+
+        s
+    }
+}
+
+/// Iterator over the frames of a `Source`, created by `Source::frames`.
+pub struct Frames<'s, S: 's + DataSource, B: 's + Buffer<S::Item>, F> {
+    /// Source the frames are parsed from
+    source: &'s mut Source<S, B>,
+    /// Frame parser
+    parser: F,
+    /// If the source is exhausted or an error has been yielded
+    done:   bool,
+}
+
+impl<'s, S, B, F, T, E> Iterator for Frames<'s, S, B, F>
+  where S: DataSource,
+        B: Buffer<S::Item>,
+        F: for<'a> FnMut(Input<'a, S::Item>) -> ParseResult<'a, S::Item, T, E> {
+    type Item = Result<T, FrameError<E>>;
+
+    #[inline]
+    fn next(&mut self) -> Option<Self::Item> {
+        while !self.done {
+            // The error borrows the buffer, take its contents before using the source again
+            let err = match self.source.parse(&mut self.parser) {
+                Ok(t)                              => return Some(Ok(t)),
+                Err(StreamError::Retry)            => None,
+                Err(StreamError::EndOfInput)       => break,
+                Err(StreamError::ParseError(_, e)) => Some(FrameError::ParseError(0, e)),
+                Err(StreamError::Incomplete(n))    => Some(FrameError::Incomplete(n)),
+                Err(StreamError::IoError(e))       => Some(FrameError::IoError(e)),
+                Err(StreamError::WouldBlock)       => Some(FrameError::IoError(io::ErrorKind::WouldBlock.into())),
+                Err(StreamError::BufferLimitExceeded(r, c)) => Some(FrameError::BufferLimitExceeded(r, c)),
+            };
+
+            let err = match err {
+                // The source has consumed the input up to the error
+                Some(FrameError::ParseError(_, e)) => FrameError::ParseError(self.source.offset(), e),
+                Some(e)                            => e,
+                None                               => match self.source.fill() {
+                    Ok(_)  => continue,
+                    Err(e) => FrameError::IoError(e),
+                },
+            };
+
+            // The source can be resumed once it is ready again
+            self.done = match err {
+                FrameError::IoError(ref e) => e.kind() != io::ErrorKind::WouldBlock,
+                _                          => true,
+            };
+
+            return Some(Err(err));
+        }
+
+        self.done = true;
+
+        None
+    }
+}
+
+/// Future parsing a value from a `Source`, created by `Source::parse_async`.
+#[cfg(feature = "async")]
+pub struct ParseAsync<S: DataSource, B: Buffer<S::Item>, F> {
+    /// Source the value is parsed from, `None` once the future has completed
+    source: Option<Source<S, B>>,
+    /// Value parser
+    parser: F,
+}
+
+#[cfg(feature = "async")]
+impl<S, B, F, T, E> Future for ParseAsync<S, B, F>
+  where S: DataSource,
+        B: Buffer<S::Item>,
+        F: for<'a> FnMut(Input<'a, S::Item>) -> ParseResult<'a, S::Item, T, E> {
+    type Item  = (Source<S, B>, Option<T>);
+    type Error = FrameError<E>;
+
+    #[inline]
+    fn poll(&mut self) -> Poll<Self::Item, Self::Error> {
+        loop {
+            let r = {
+                let source = self.source.as_mut().expect("cannot poll ParseAsync after it has completed");
+
+                // The error borrows the buffer, take its contents before using the source again
+                let r = match source.parse(&mut self.parser) {
+                    Ok(t)                              => Ok(Some(t)),
+                    Err(StreamError::EndOfInput)       => Ok(None),
+                    Err(StreamError::WouldBlock)       => return Ok(Async::NotReady),
+                    Err(StreamError::Retry)            => Err(None),
+                    Err(StreamError::ParseError(_, e)) => Err(Some(FrameError::ParseError(0, e))),
+                    Err(StreamError::Incomplete(n))    => Err(Some(FrameError::Incomplete(n))),
+                    Err(StreamError::IoError(e))       => Err(Some(FrameError::IoError(e))),
+                    Err(StreamError::BufferLimitExceeded(r, c)) => Err(Some(FrameError::BufferLimitExceeded(r, c))),
+                };
+
+                match r {
+                    Ok(t)  => Ok(t),
+                    // The source has consumed the input up to the error
+                    Err(Some(FrameError::ParseError(_, e))) => return Err(FrameError::ParseError(source.offset(), e)),
+                    Err(Some(e))                            => return Err(e),
+                    Err(None)                               => match source.fill() {
+                        Ok(_)                                            => continue,
+                        Err(ref e) if e.kind() == io::ErrorKind::WouldBlock => return Ok(Async::NotReady),
+                        Err(e)                                           => return Err(FrameError::IoError(e)),
+                    },
+                }
+            };
+
+            return r.map(|t| Async::Ready((self.source.take().unwrap(), t)));
+        }
+    }
+}
+
 #[cfg(test)]
 mod test {
     use std::io;
-    use {any, take};
+    use {any, take, token};
     use Error;
+    use parsers::offset;
     use buffer::{
+        DataSource,
         FixedSizeBuffer,
+        FrameError,
         StreamError,
         Stream,
     };
@@ -264,6 +732,96 @@ mod test {
         Source::with_buffer(ReadDataSource::new(io::Cursor::new(source)), FixedSizeBuffer::with_size(buffer_length))
     }
 
+    /// Data source yielding the given chunks in order, `None` is a `WouldBlock` error.
+    struct NonBlocking(Vec<Option<&'static [u8]>>);
+
+    impl DataSource for NonBlocking {
+        type Item = u8;
+
+        fn read(&mut self, buffer: &mut [u8]) -> io::Result<usize> {
+            if self.0.is_empty() {
+                return Ok(0);
+            }
+
+            match self.0.remove(0) {
+                Some(d) => {
+                    buffer[..d.len()].copy_from_slice(d);
+
+                    Ok(d.len())
+                },
+                None    => Err(io::ErrorKind::WouldBlock.into()),
+            }
+        }
+    }
+
+    #[test]
+    fn would_block() {
+        let mut b = Source::with_buffer(NonBlocking(vec![None, Some(b"a"), None, Some(b"bc")]), FixedSizeBuffer::with_size(4));
+
+        assert_eq!(b.parse(|i| take(i, 2)), Err(StreamError::WouldBlock));
+        assert_eq!(b.parse(|i| take(i, 2)), Err(StreamError::Retry));
+        assert_eq!(b.parse(|i| take(i, 2)), Err(StreamError::WouldBlock));
+        assert_eq!(b.len(), 1);
+        assert_eq!(b.parse(|i| take(i, 2)), Ok(&b"ab"[..]));
+        assert_eq!(b.parse(|i| take(i, 2)), Err(StreamError::Retry));
+        assert_eq!(b.parse(|i| take(i, 2)), Err(StreamError::Incomplete(2)));
+
+        let mut b = Source::with_buffer(NonBlocking(vec![Some(b"a"), None, Some(b"b")]), FixedSizeBuffer::with_size(4));
+
+        let r: Vec<_> = b.frames(|i| take(i, 2).map(|b| b.to_vec())).map(|r| r.map_err(|e| match e {
+            FrameError::IoError(e) => e.kind(),
+            _                      => panic!("unexpected error"),
+        })).collect();
+
+        assert_eq!(r, vec![Err(io::ErrorKind::WouldBlock), Ok(b"ab".to_vec())]);
+    }
+
+    #[cfg(feature = "async")]
+    #[test]
+    fn parse_async() {
+        use futures::{Async, Future, Poll};
+
+        use buffer::data_source::AsyncDataSource;
+
+        struct Polled(NonBlocking);
+
+        impl AsyncDataSource for Polled {
+            type Item = u8;
+
+            fn poll_read(&mut self, buffer: &mut [u8]) -> Poll<usize, io::Error> {
+                match self.0.read(buffer) {
+                    Ok(n)  => Ok(Async::Ready(n)),
+                    Err(_) => Ok(Async::NotReady),
+                }
+            }
+        }
+
+        let b = Source::from_async(Polled(NonBlocking(vec![None, Some(b"a"), None, Some(b"bcd")])), FixedSizeBuffer::with_size(4));
+        let mut f = b.parse_async(|i| take(i, 2).map(|b| b.to_vec()));
+
+        assert!(f.poll().unwrap().is_not_ready());
+        assert!(f.poll().unwrap().is_not_ready());
+
+        let (b, r) = match f.poll() {
+            Ok(Async::Ready(r)) => r,
+            _                   => panic!("expected a value"),
+        };
+
+        assert_eq!(r, Some(b"ab".to_vec()));
+        assert_eq!(b.offset(), 2);
+
+        let mut f = b.parse_async(|i| token(i, b'x'));
+
+        match f.poll() {
+            Err(e) => assert_eq!(e, FrameError::ParseError(2, Error::expected(b'x'))),
+            _      => panic!("expected an error"),
+        }
+
+        let b = buf(&b""[..], 2);
+
+        assert_eq!(b.parse_async(|i| token(i, b'x')).wait().unwrap().1, None);
+    }
+
     #[test]
     #[should_panic]
     fn bufsize_zero() {
@@ -292,6 +850,91 @@ mod test {
         assert_eq!(n, 0);
     }
 
+    #[test]
+    fn offset_test() {
+        let mut b = buf(&b"test"[..], 2);
+
+        assert_eq!(b.parse(|i| take(i, 2).then(offset)), Ok(2));
+        assert_eq!(b.offset(), 2);
+        assert_eq!(b.parse(|i| take(i, 1).then(offset)), Err(StreamError::Retry));
+        assert_eq!(b.parse(|i| take(i, 1).then(offset)), Ok(3));
+        assert_eq!(b.offset(), 3);
+    }
+
+    #[test]
+    fn frames() {
+        let mut b = buf(&b"testing"[..], 2);
+
+        let r: Vec<_> = b.frames(|i| take(i, 2).map(|b| b.to_vec())).collect();
+
+        assert_eq!(r, vec![Ok(b"te".to_vec()), Ok(b"st".to_vec()), Ok(b"in".to_vec()), Err(FrameError::Incomplete(2))]);
+
+        let mut b = buf(&b"test"[..], 2);
+
+        let r: Vec<_> = b.frames(|i| take(i, 2).map(|b| b.to_vec())).collect();
+
+        assert_eq!(r, vec![Ok(b"te".to_vec()), Ok(b"st".to_vec())]);
+
+        let mut b = buf(&b"aab"[..], 2);
+
+        let r: Vec<_> = b.frames(|i| token(i, b'a')).collect();
+
+        assert_eq!(r, vec![Ok(b'a'), Ok(b'a'), Err(FrameError::ParseError(2, Error::expected(b'a')))]);
+    }
+
+    #[test]
+    fn buffer_limit() {
+        use buffer::GrowingBuffer;
+
+        let mut b = buf(&b"abcdef"[..], 4);
+
+        assert_eq!(b.parse(|i| take(i, 5)), Err(StreamError::Retry));
+        assert_eq!(b.parse(|i| take(i, 5)), Err(StreamError::BufferLimitExceeded(5, 4)));
+        assert_eq!(b.parse(|i| take(i, 4)), Ok(&b"abcd"[..]));
+
+        let mut b = buf(&b"abcdef"[..], 4);
+
+        let r: Vec<_> = b.frames(|i| take(i, 5).map(|b| b.to_vec())).collect();
+
+        assert_eq!(r, vec![Err(FrameError::BufferLimitExceeded(5, 4))]);
+
+        let mut b = Source::with_buffer(ReadDataSource::new(io::Cursor::new(&[0; 32][..])), GrowingBuffer::with_limit(16));
+
+        assert_eq!(b.parse(|i| take(i, 20)), Err(StreamError::Retry));
+        assert_eq!(b.parse(|i| take(i, 20)), Err(StreamError::BufferLimitExceeded(20, 16)));
+        assert_eq!(b.parse(|i| take(i, 16)), Ok(&[0; 16][..]));
+    }
+
+    #[test]
+    fn builder() {
+        let b = Source::builder(io::Cursor::new(&b"test"[..])).build();
+
+        assert_eq!(b.capacity(), 6 * 1024);
+
+        let mut b = Source::builder(io::Cursor::new(&b"abcdef"[..])).buffer_size(4).limit(8).autofill(false).build();
+
+        assert_eq!(b.parse(|i| take(i, 5)), Err(StreamError::Retry));
+        assert_eq!(b.parse(|i| take(i, 5)), Err(StreamError::Retry));
+        assert_eq!(b.fill().unwrap(), 4);
+        assert_eq!(b.parse(|i| take(i, 5)), Err(StreamError::BufferLimitExceeded(5, 4)));
+        assert_eq!(b.capacity(), 4);
+    }
+
+    #[test]
+    fn error_conversion() {
+        let mut b = buf(&b"ab"[..], 4);
+
+        let e: io::Error = b.parse(|i| token(i, b'b')).unwrap_err().into();
+        assert_eq!(e.kind(), io::ErrorKind::InvalidData);
+
+        let e: io::Error = b.parse(|i| take(i, 3)).unwrap_err().into();
+        assert_eq!(e.kind(), io::ErrorKind::WouldBlock);
+
+        let e: io::Error = b.frames(|i| take(i, 3).map(|b| b.to_vec())).next().unwrap().unwrap_err().into();
+        assert_eq!(e.kind(), io::ErrorKind::UnexpectedEof);
+        assert_eq!(e.to_string(), "incomplete frame, 3 items required");
+    }
+
     #[test]
     fn fill() {
         let mut n = 0; // Times it has entered the parsing function
@@ -419,8 +1062,10 @@ mod test {
         assert_eq!(b.parse(|i| { n += 1; take(i, 2).inspect(|_| m += 1) }), Err(StreamError::Retry));
         assert_eq!(n, 1);
         assert_eq!(m, 0);
+        assert_eq!(b.needed(), 2);
 
         assert_eq!(b.fill().unwrap(), 2);
+        assert_eq!(b.needed(), 0);
 
         assert_eq!(b.parse(|i| { n += 1; take(i, 2).inspect(|_| m += 1) }), Ok(&b"te"[..]));
         assert_eq!(n, 2);
@@ -1,6 +1,8 @@
 //! Bounded versions of combinators.
 //!
-//! This module provides bounded versions of `many`, `many_till` and `skip_many`.
+//! This module provides bounded versions of `many`, `many_till`, `skip_many` and `sep_by` as well as
+//! the allocation-free `fold_many` and `sep_by_fold`, and `many_with_capacity` which pre-allocates
+//! its result.
 //!
 //! The core range types are used to describe a half-open range of successive applications of a
 //! parser. `usize` is used to specify an exact number of iterations:
@@ -14,6 +16,18 @@
 //!
 //! assert_eq!(r, Ok(vec![b'a', b'b', b'c']));
 //! ```
+//!
+//! Inclusive ranges are also supported:
+//!
+//! ```
+//! use chomp::combinators::bounded::many;
+//! use chomp::{parse_only, any};
+//!
+//! // Read any character 2 to 3 times
+//! let r: Result<Vec<_>, _> = parse_only(|i| many(i, 2..=3, any), b"abcd");
+//!
+//! assert_eq!(r, Ok(vec![b'a', b'b', b'c']));
+//! ```
 
 use std::marker::PhantomData;
 use std::iter::FromIterator;
@@ -21,11 +35,14 @@ use std::ops::{
     Range,
     RangeFrom,
     RangeFull,
+    RangeInclusive,
     RangeTo,
+    RangeToInclusive,
 };
 use std::cmp::max;
 
 use {Input, ParseResult};
+use combinators::option;
 use primitives::{InputClone, InputBuffer, IntoInner, State};
 
 /// Trait for applying a parser multiple times based on a range.
@@ -698,6 +715,101 @@ impl BoundedRange for usize {
     }
 }
 
+impl BoundedRange for RangeInclusive<usize> {
+    #[inline]
+    fn parse_many<'a, I, T, E, F, U>(self, i: Input<'a, I>, f: F) -> ParseResult<'a, I, T, E>
+      where I: Copy,
+            U: 'a,
+            F: FnMut(Input<'a, I>) -> ParseResult<'a, I, U, E>,
+            T: FromIterator<U> {
+        let (start, end) = self.into_inner();
+
+        // RangeInclusive does not perform this assertion
+        assert!(start <= end);
+
+        // Closed on both sides, convert to the half-open equivalent
+        match end.checked_add(1) {
+            Some(end) => BoundedRange::parse_many(start..end, i, f),
+            None      => BoundedRange::parse_many(start.., i, f),
+        }
+    }
+
+    #[inline]
+    fn skip_many<'a, I, T, E, F>(self, i: Input<'a, I>, f: F) -> ParseResult<'a, I, (), E>
+      where T: 'a,
+            F: FnMut(Input<'a, I>) -> ParseResult<'a, I, T, E> {
+        let (start, end) = self.into_inner();
+
+        // RangeInclusive does not perform this assertion
+        assert!(start <= end);
+
+        match end.checked_add(1) {
+            Some(end) => BoundedRange::skip_many(start..end, i, f),
+            None      => BoundedRange::skip_many(start.., i, f),
+        }
+    }
+
+    #[inline]
+    fn many_till<'a, I, T, E, R, F, U, N, V>(self, i: Input<'a, I>, p: R, end: F) -> ParseResult<'a, I, T, E>
+      where I: Copy,
+            U: 'a,
+            V: 'a,
+            N: 'a,
+            T: FromIterator<U>,
+            R: FnMut(Input<'a, I>) -> ParseResult<'a, I, U, E>,
+            F: FnMut(Input<'a, I>) -> ParseResult<'a, I, V, N> {
+        let (start, stop) = self.into_inner();
+
+        // RangeInclusive does not perform this assertion
+        assert!(start <= stop);
+
+        match stop.checked_add(1) {
+            Some(stop) => BoundedRange::many_till(start..stop, i, p, end),
+            None       => BoundedRange::many_till(start.., i, p, end),
+        }
+    }
+}
+
+impl BoundedRange for RangeToInclusive<usize> {
+    #[inline]
+    fn parse_many<'a, I, T, E, F, U>(self, i: Input<'a, I>, f: F) -> ParseResult<'a, I, T, E>
+      where I: Copy,
+            U: 'a,
+            F: FnMut(Input<'a, I>) -> ParseResult<'a, I, U, E>,
+            T: FromIterator<U> {
+        // Open on left side, closed on right, convert to the half-open equivalent
+        match self.end.checked_add(1) {
+            Some(end) => BoundedRange::parse_many(..end, i, f),
+            None      => BoundedRange::parse_many(.., i, f),
+        }
+    }
+
+    #[inline]
+    fn skip_many<'a, I, T, E, F>(self, i: Input<'a, I>, f: F) -> ParseResult<'a, I, (), E>
+      where T: 'a,
+            F: FnMut(Input<'a, I>) -> ParseResult<'a, I, T, E> {
+        match self.end.checked_add(1) {
+            Some(end) => BoundedRange::skip_many(..end, i, f),
+            None      => BoundedRange::skip_many(.., i, f),
+        }
+    }
+
+    #[inline]
+    fn many_till<'a, I, T, E, R, F, U, N, V>(self, i: Input<'a, I>, p: R, end: F) -> ParseResult<'a, I, T, E>
+      where I: Copy,
+            U: 'a,
+            V: 'a,
+            N: 'a,
+            T: FromIterator<U>,
+            R: FnMut(Input<'a, I>) -> ParseResult<'a, I, U, E>,
+            F: FnMut(Input<'a, I>) -> ParseResult<'a, I, V, N> {
+        match self.end.checked_add(1) {
+            Some(stop) => BoundedRange::many_till(..stop, i, p, end),
+            None       => BoundedRange::many_till(.., i, p, end),
+        }
+    }
+}
+
 /// Applies the parser `F` multiple times until it fails or the maximum value of the range has
 /// been reached, collecting the successful values into a `T: FromIterator`.
 ///
@@ -723,6 +835,40 @@ pub fn many<'a, I, T, E, F, U, R>(i: Input<'a, I>, r: R, f: F) -> ParseResult<'a
     BoundedRange::parse_many(r, i, f)
 }
 
+/// Applies the parser `F` multiple times until it fails or the maximum value of the range has
+/// been reached, collecting the successful values into a `Vec` which has space for at least
+/// `capacity` items reserved up front.
+///
+/// Propagates errors if the minimum number of iterations has not been met
+///
+/// # Panics
+///
+/// Will panic if the end of the range is smaller than the start of the range.
+///
+/// # Notes
+///
+/// * Allocates at least `capacity` items up front, only reallocating if more items than that are
+///   parsed.
+/// * Will never yield more items than the upper bound of the range.
+/// * If the last parser succeeds on the last input item then this parser is still considered
+///   incomplete if the input flag END_OF_INPUT is not set as there might be more data to fill.
+///
+/// ```
+/// use chomp::{parse_only, any};
+/// use chomp::combinators::bounded::many_with_capacity;
+///
+/// let r = parse_only(|i| many_with_capacity(i, 64, .., any), b"abc");
+///
+/// assert_eq!(r, Ok(vec![b'a', b'b', b'c']));
+/// ```
+#[inline]
+pub fn many_with_capacity<'a, I, E, F, U, R>(i: Input<'a, I>, capacity: usize, r: R, f: F) -> ParseResult<'a, I, Vec<U>, E>
+  where R: BoundedRange,
+        U: 'a,
+        F: FnMut(Input<'a, I>) -> ParseResult<'a, I, U, E> {
+    fold_many(i, r, Vec::with_capacity(capacity), f, |mut v, t| { v.push(t); v })
+}
+
 /// Applies the parser `F` multiple times until it fails or the maximum value of the range has
 /// been reached, throwing away any produced value.
 ///
@@ -772,6 +918,83 @@ pub fn many_till<'a, I, T, E, R, F, U, N, P, V>(i: Input<'a, I>, r: R, p: P, end
     BoundedRange::many_till(r, i, p, end)
 }
 
+/// Applies the parser `P` multiple times until the parser `F` succeeds and returns a tuple of a
+/// value populated by the values yielded by `P` and the value yielded by `F`. Consumes the matched
+/// part of `F`. If `F` does not succeed within the given range `R` this combinator will propagate
+/// any failure from `P`.
+///
+/// # Panics
+///
+/// Will panic if the end of the range is smaller than the start of the range.
+///
+/// # Notes
+///
+/// * Will allocate depending on the `FromIterator` implementation.
+/// * Will never yield more items than the upper bound of the range.
+/// * If the last parser succeeds on the last input item then this combinator is still considered
+///   incomplete unless the parser `F` matches or the lower bound has not been met.
+#[inline]
+pub fn many_till_with_end<'a, I, T, E, R, F, U, N, P, V>(i: Input<'a, I>, r: R, p: P, mut end: F) -> ParseResult<'a, I, (T, V), E>
+  where I: Copy,
+        U: 'a,
+        V: 'a,
+        N: 'a,
+        R: BoundedRange,
+        T: FromIterator<U>,
+        P: FnMut(Input<'a, I>) -> ParseResult<'a, I, U, E>,
+        F: FnMut(Input<'a, I>) -> ParseResult<'a, I, V, N> {
+    // Value of the last successful application of end, only succeeds once
+    let mut last = None;
+
+    let r = BoundedRange::many_till(r, i, p, |i| end(i).map(|v| last = Some(v)));
+
+    // Unwrap cannot fail, many_till only succeeds if end succeeded
+    r.map(|t| (t, last.take().unwrap()))
+}
+
+/// Applies the parser `P` multiple times until it fails or the maximum value of the range has
+/// been reached, folding each successful value into an accumulator using `F`, starting from
+/// `init`.
+///
+/// Propagates errors if the minimum number of iterations has not been met
+///
+/// # Panics
+///
+/// Will panic if the end of the range is smaller than the start of the range.
+///
+/// # Notes
+///
+/// * Does not allocate, unlike `many` no intermediate `T: FromIterator` is constructed.
+/// * Will never apply `P` more times than the upper bound of the range.
+/// * If the last parser succeeds on the last input item then this parser is still considered
+///   incomplete if the input flag END_OF_INPUT is not set as there might be more data to fill.
+///
+/// ```
+/// use chomp::{parse_only, token};
+/// use chomp::ascii::decimal;
+/// use chomp::combinators::bounded::fold_many;
+///
+/// let p = |i| fold_many(i, 1.., 0, |i| decimal::<u32>(i).bind(|i, n| token(i, b',').map(|_| n)), |a, n| a + n);
+///
+/// assert_eq!(parse_only(p, b"1,2,3,"), Ok(6));
+/// ```
+#[inline]
+pub fn fold_many<'a, I, T, E, R, P, F, A>(i: Input<'a, I>, r: R, init: A, mut p: P, mut f: F) -> ParseResult<'a, I, A, E>
+  where T: 'a,
+        A: 'a,
+        R: BoundedRange,
+        P: FnMut(Input<'a, I>) -> ParseResult<'a, I, T, E>,
+        F: FnMut(A, T) -> A {
+    let mut acc = Some(init);
+
+    BoundedRange::skip_many(r, i, |i| p(i).map(|t| {
+        // acc is always populated outside of this closure
+        let a = acc.take().unwrap();
+
+        acc = Some(f(a, t));
+    })).map(|_| acc.take().unwrap())
+}
+
 /// Applies the parser `p` multiple times, separated by the parser `sep` and returns a value
 /// populated with the values yielded by `p`. If the number of items yielded by `p` does not fall
 /// into the range `r` and the separator or parser registers error or incomplete failure is
@@ -812,16 +1035,201 @@ pub fn sep_by<'a, I, T, E, R, F, U, N, P, V>(i: Input<'a, I>, r: R, mut p: P, mu
     BoundedRange::parse_many(r, i, parser)
 }
 
+/// Applies the parser `p` multiple times, each match followed by the parser `sep`. All values
+/// yielded by `p` are collected into the type `T: FromIterator`. If the number of items yielded
+/// by `p` does not fall into the range `r` and the separator or parser registers error or
+/// incomplete failure is propagated.
+///
+/// # Panics
+///
+/// Will panic if the end of the range is smaller than the start of the range.
+///
+/// # Notes
+///
+/// * Will allocate depending on the `FromIterator` implementation.
+/// * Will never yield more items than the upper bound of the range.
+/// * An item is only accepted if it is followed by the separator.
+#[inline]
+pub fn end_by<'a, I, T, E, R, F, U, N, P, V>(i: Input<'a, I>, r: R, mut p: P, mut sep: F) -> ParseResult<'a, I, T, E>
+  where I: Copy,
+        U: 'a,
+        V: 'a,
+        N: 'a,
+        T: FromIterator<U>,
+        E: From<N>,
+        R: BoundedRange,
+        P: FnMut(Input<'a, I>) -> ParseResult<'a, I, U, E>,
+        F: FnMut(Input<'a, I>) -> ParseResult<'a, I, V, N> {
+    BoundedRange::parse_many(r, i, |i| p(i).bind(|i, t| sep(i).map(|_| t).map_err(From::from)))
+}
+
+/// Applies the parser `p` multiple times, separated by the parser `sep` and optionally followed
+/// by a trailing `sep`. All values yielded by `p` are collected into the type `T: FromIterator`.
+/// If the number of items yielded by `p` does not fall into the range `r` and the separator or
+/// parser registers error or incomplete failure is propagated.
+///
+/// # Panics
+///
+/// Will panic if the end of the range is smaller than the start of the range.
+///
+/// # Notes
+///
+/// * Will allocate depending on the `FromIterator` implementation.
+/// * Will never yield more items than the upper bound of the range.
+/// * The trailing separator is only consumed if at least one item was parsed.
+#[inline]
+pub fn sep_end_by<'a, I, T, E, R, F, U, N, P, V>(i: Input<'a, I>, r: R, mut p: P, mut sep: F) -> ParseResult<'a, I, T, E>
+  where I: Copy,
+        U: 'a,
+        V: 'a,
+        N: 'a,
+        T: FromIterator<U>,
+        E: From<N>,
+        R: BoundedRange,
+        P: FnMut(Input<'a, I>) -> ParseResult<'a, I, U, E>,
+        F: FnMut(Input<'a, I>) -> ParseResult<'a, I, V, N> {
+    // If we have parsed at least one item
+    let mut item = false;
+
+    let r = {
+        // Add sep in front of p if we have read at least one item
+        let parser = |i| (if item {
+                sep(i).map(|_| ())
+            } else {
+                i.ret(())
+            })
+            .then(&mut p)
+            .inspect(|_| item = true);
+
+        BoundedRange::parse_many(r, i, parser)
+    };
+
+    if item {
+        r.bind(|i, t| option(i, |i| sep(i).map(|_| ()), ()).map(|_| t).map_err(From::from))
+    } else {
+        r
+    }
+}
+
+/// Applies the parser `p` multiple times, separated by the parser `sep`, folding each value
+/// yielded by `p` into an accumulator using `f`, starting from `init`. If the number of items
+/// yielded by `p` does not fall into the range `r` and the separator or parser registers error or
+/// incomplete failure is propagated.
+///
+/// # Panics
+///
+/// Will panic if the end of the range is smaller than the start of the range.
+///
+/// # Notes
+///
+/// * Does not allocate, unlike `sep_by` no intermediate `T: FromIterator` is constructed.
+/// * Will never apply `p` more times than the upper bound of the range.
+/// * If the last parser succeeds on the last input item then this combinator is still considered
+///   incomplete unless the parser `F` matches or the lower bound has not been met.
+///
+/// ```
+/// use chomp::{parse_only, token};
+/// use chomp::ascii::decimal;
+/// use chomp::combinators::bounded::sep_by_fold;
+///
+/// let p = |i| sep_by_fold(i, .., (0, 0), decimal::<u32>, |i| token(i, b';'), |(n, sum), d| (n + 1, sum + d));
+///
+/// assert_eq!(parse_only(p, b"91;03;20"), Ok((3, 114)));
+/// ```
+#[inline]
+pub fn sep_by_fold<'a, I, T, E, R, P, S, N, V, F, A>(i: Input<'a, I>, r: R, init: A, mut p: P, mut sep: S, f: F) -> ParseResult<'a, I, A, E>
+  where T: 'a,
+        V: 'a,
+        N: 'a,
+        A: 'a,
+        E: From<N>,
+        R: BoundedRange,
+        P: FnMut(Input<'a, I>) -> ParseResult<'a, I, T, E>,
+        S: FnMut(Input<'a, I>) -> ParseResult<'a, I, V, N>,
+        F: FnMut(A, T) -> A {
+    // If we have parsed at least one item
+    let mut item = false;
+    // Add sep in front of p if we have read at least one item
+    let parser   = |i| (if item {
+            sep(i).map(|_| ())
+        } else {
+            i.ret(())
+        })
+        .then(&mut p)
+        .inspect(|_| item = true);
+
+    fold_many(i, r, init, parser, f)
+}
+
+/// Applies the parser `p` multiple times, separated by the parser `sep`, collecting both the values
+/// yielded by `p` and the values yielded by `sep` in the order they appeared. If the number of
+/// items yielded by `p` does not fall into the range `r` and the separator or parser registers
+/// error or incomplete failure is propagated.
+///
+/// The resulting separator `Vec` will always contain one less item than the item `Vec`, unless
+/// no items were parsed.
+///
+/// # Panics
+///
+/// Will panic if the end of the range is smaller than the start of the range.
+///
+/// # Notes
+///
+/// * Allocates two `Vec`s.
+/// * Will never yield more items than the upper bound of the range.
+/// * If the last parser succeeds on the last input item then this combinator is still considered
+///   incomplete unless the parser `F` matches or the lower bound has not been met.
+#[inline]
+pub fn sep_by_with_seps<'a, I, E, R, F, U, N, P, V>(i: Input<'a, I>, r: R, mut p: P, mut sep: F) -> ParseResult<'a, I, (Vec<U>, Vec<V>), E>
+  where U: 'a,
+        V: 'a,
+        N: 'a,
+        E: From<N>,
+        R: BoundedRange,
+        P: FnMut(Input<'a, I>) -> ParseResult<'a, I, U, E>,
+        F: FnMut(Input<'a, I>) -> ParseResult<'a, I, V, N> {
+    // If we have parsed at least one item
+    let mut item = false;
+    // Add sep in front of p if we have read at least one item
+    let parser   = |i| (if item {
+            sep(i).map(Some)
+        } else {
+            i.ret(None)
+        })
+        .bind(|i, s| p(i).map(|t| (s, t)))
+        .inspect(|_| item = true);
+
+    fold_many(i, r, (Vec::new(), Vec::new()), parser, |(mut items, mut seps), (s, t)| {
+        if let Some(s) = s {
+            seps.push(s);
+        }
+
+        items.push(t);
+
+        (items, seps)
+    })
+}
+
 #[cfg(test)]
 mod test {
+    use std::iter::FromIterator;
+
     use ParseResult;
-    use parsers::{any, token};
+    use parsers::{any, token, string, Error};
     use primitives::input::*;
     use primitives::{IntoInner, State};
 
     use super::{
+        BoundedRange,
+        end_by,
+        fold_many,
         many,
         many_till,
+        many_till_with_end,
+        many_with_capacity,
+        sep_by_fold,
+        sep_by_with_seps,
+        sep_end_by,
         skip_many,
     };
 
@@ -1275,6 +1683,189 @@ mod test {
         assert_eq!(r.into_inner(), State::Data(new(END_OF_INPUT, b"ab"), ()));
     }
 
+    #[test]
+    fn many_range_inclusive() {
+        let r: ParseResult<_, Vec<_>, _> = many(new(END_OF_INPUT, b"aaaab"), 2..=3, |i| token(i, b'a'));
+        assert_eq!(r.into_inner(), State::Data(new(END_OF_INPUT, b"ab"), vec![b'a', b'a', b'a']));
+        let r: ParseResult<_, Vec<_>, _> = many(new(END_OF_INPUT, b"aab"), 2..=3, |i| token(i, b'a'));
+        assert_eq!(r.into_inner(), State::Data(new(END_OF_INPUT, b"b"), vec![b'a', b'a']));
+        let r: ParseResult<_, Vec<_>, _> = many(new(END_OF_INPUT, b"ab"), 2..=3, |i| token(i, b'a'));
+        assert_eq!(r.into_inner(), State::Error(b"b", Error::expected(b'a')));
+        let r: ParseResult<_, Vec<_>, _> = many(new(END_OF_INPUT, b"aab"), 0..=0, |i| token(i, b'a'));
+        assert_eq!(r.into_inner(), State::Data(new(END_OF_INPUT, b"aab"), vec![]));
+        let r: ParseResult<_, Vec<_>, _> = many(new(END_OF_INPUT, b"aab"), 0..=::std::usize::MAX, |i| token(i, b'a'));
+        assert_eq!(r.into_inner(), State::Data(new(END_OF_INPUT, b"b"), vec![b'a', b'a']));
+
+        let r: ParseResult<_, Vec<_>, _> = many(new(END_OF_INPUT, b"aaab"), ..=2, |i| token(i, b'a'));
+        assert_eq!(r.into_inner(), State::Data(new(END_OF_INPUT, b"ab"), vec![b'a', b'a']));
+        let r: ParseResult<_, Vec<_>, _> = many(new(END_OF_INPUT, b"b"), ..=2, |i| token(i, b'a'));
+        assert_eq!(r.into_inner(), State::Data(new(END_OF_INPUT, b"b"), vec![]));
+        let r: ParseResult<_, Vec<_>, _> = many(new(END_OF_INPUT, b"aab"), ..=::std::usize::MAX, |i| token(i, b'a'));
+        assert_eq!(r.into_inner(), State::Data(new(END_OF_INPUT, b"b"), vec![b'a', b'a']));
+    }
+
+    #[test]
+    fn skip_range_inclusive() {
+        assert_eq!(skip_many(new(END_OF_INPUT, b"aaaab"), 2..=3, |i| token(i, b'a')).into_inner(), State::Data(new(END_OF_INPUT, b"ab"), ()));
+        assert_eq!(skip_many(new(END_OF_INPUT, b"ab"), 2..=3, |i| token(i, b'a')).into_inner(), State::Error(b"b", Error::expected(b'a')));
+        assert_eq!(skip_many(new(END_OF_INPUT, b"aaab"), ..=2, |i| token(i, b'a')).into_inner(), State::Data(new(END_OF_INPUT, b"ab"), ()));
+    }
+
+    #[test]
+    fn many_till_range_inclusive() {
+        let r: ParseResult<_, Vec<_>, _> = many_till(new(END_OF_INPUT, b"aab"), 1..=2, |i| token(i, b'a'), |i| token(i, b'b'));
+        assert_eq!(r.into_inner(), State::Data(new(END_OF_INPUT, b""), vec![b'a', b'a']));
+        // Same behaviour as the equivalent half-open range when the maximum is reached
+        let r: ParseResult<_, Vec<_>, _> = many_till(new(END_OF_INPUT, b"aaab"), 1..=2, |i| token(i, b'a'), |i| token(i, b'b'));
+        let s: ParseResult<_, Vec<_>, _> = many_till(new(END_OF_INPUT, b"aaab"), 1..3, |i| token(i, b'a'), |i| token(i, b'b'));
+        assert_eq!(r.into_inner(), s.into_inner());
+        let r: ParseResult<_, Vec<_>, _> = many_till(new(END_OF_INPUT, b"aab"), ..=2, |i| token(i, b'a'), |i| token(i, b'b'));
+        assert_eq!(r.into_inner(), State::Data(new(END_OF_INPUT, b""), vec![b'a', b'a']));
+    }
+
+    #[test]
+    fn many_till_with_end_range() {
+        let r: ParseResult<_, (Vec<_>, _), _> = many_till_with_end(new(END_OF_INPUT, b"aab"), 1..3, |i| token(i, b'a'), any);
+        assert_eq!(r.into_inner(), State::Data(new(END_OF_INPUT, b"b"), (vec![b'a'], b'a')));
+        let r: ParseResult<_, (Vec<_>, _), _> = many_till_with_end(new(END_OF_INPUT, b"aab"), 1..3, |i| token(i, b'a'), |i| token(i, b'b'));
+        assert_eq!(r.into_inner(), State::Data(new(END_OF_INPUT, b""), (vec![b'a', b'a'], b'b')));
+        let r: ParseResult<_, (Vec<_>, _), _> = many_till_with_end(new(END_OF_INPUT, b"b"), 1..3, |i| token(i, b'a'), |i| token(i, b'b'));
+        assert_eq!(r.into_inner(), State::Error(b"b", Error::expected(b'a')));
+    }
+
+    #[test]
+    #[should_panic]
+    fn panic_many_range_inclusive_lt() {
+        let r: ParseResult<_, Vec<_>, _> = many(new(DEFAULT, b"aaaab"), 2..=1, |i| token(i, b'a'));
+        assert_eq!(r.into_inner(), State::Data(new(DEFAULT, b"ab"), vec![b'a', b'a', b'a']));
+    }
+
+    /// Collects the size hint provided by the iterator before iterating
+    #[derive(Debug)]
+    struct SizeHint(usize, Option<usize>);
+
+    impl<T> FromIterator<T> for SizeHint {
+        fn from_iter<I: IntoIterator<Item=T>>(iter: I) -> Self {
+            let iter = iter.into_iter();
+            let hint = iter.size_hint();
+
+            // Drain the iterator to let the parser run
+            iter.count();
+
+            SizeHint(hint.0, hint.1)
+        }
+    }
+
+    #[test]
+    fn many_size_hint() {
+        fn hint<R: BoundedRange>(r: R) -> (usize, Option<usize>) {
+            match many(new(END_OF_INPUT, b"aaaaa"), r, |i| token(i, b'a')).into_inner() {
+                State::Data(_, SizeHint(l, u)) => (l, u),
+                s                              => panic!("Expected data, got {:?}", s),
+            }
+        }
+
+        assert_eq!(hint(2..4), (2, Some(3)));
+        assert_eq!(hint(2..=4), (2, Some(4)));
+        assert_eq!(hint(2..), (2, None));
+        assert_eq!(hint(..), (0, None));
+        assert_eq!(hint(..3), (0, Some(2)));
+        assert_eq!(hint(..=3), (0, Some(3)));
+        assert_eq!(hint(3), (3, Some(3)));
+    }
+
+    #[test]
+    fn many_with_capacity_test() {
+        let r = many_with_capacity(new(END_OF_INPUT, b"aaab"), 16, .., |i| token(i, b'a'));
+        match r.into_inner() {
+            State::Data(b, v) => {
+                assert_eq!(b, new(END_OF_INPUT, b"b"));
+                assert_eq!(v, vec![b'a', b'a', b'a']);
+                assert!(v.capacity() >= 16);
+            },
+            s => panic!("Expected data, got {:?}", s),
+        }
+
+        let r = many_with_capacity(new(END_OF_INPUT, b"ab"), 16, 2.., |i| token(i, b'a'));
+        assert_eq!(r.into_inner(), State::Error(b"b", Error::expected(b'a')));
+        let r = many_with_capacity(new(DEFAULT, b"aa"), 16, .., |i| token(i, b'a'));
+        assert_eq!(r.into_inner(), State::Incomplete(1));
+    }
+
+    #[test]
+    fn fold_many_test() {
+        let r = fold_many(new(DEFAULT, b"aab"), .., 0, |i| token(i, b'a'), |n, _| n + 1);
+        assert_eq!(r.into_inner(), State::Data(new(DEFAULT, b"b"), 2));
+        let r = fold_many(new(DEFAULT, b"aa"), .., 0, |i| token(i, b'a'), |n, _| n + 1);
+        assert_eq!(r.into_inner(), State::Incomplete(1));
+        let r = fold_many(new(END_OF_INPUT, b"aa"), .., 0, |i| token(i, b'a'), |n, _| n + 1);
+        assert_eq!(r.into_inner(), State::Data(new(END_OF_INPUT, b""), 2));
+        let r = fold_many(new(END_OF_INPUT, b"abc"), .., vec![], any, |mut v, c| { v.push(c); v });
+        assert_eq!(r.into_inner(), State::Data(new(END_OF_INPUT, b""), vec![b'a', b'b', b'c']));
+
+        let r = fold_many(new(DEFAULT, b"aaab"), 2..3, 0, |i| token(i, b'a'), |n, _| n + 1);
+        assert_eq!(r.into_inner(), State::Data(new(DEFAULT, b"ab"), 2));
+        let r = fold_many(new(DEFAULT, b"ab"), 2..3, 0, |i| token(i, b'a').map_err(|_| "token_err"), |n, _| n + 1);
+        assert_eq!(r.into_inner(), State::Error(b"b", "token_err"));
+        let r = fold_many(new(DEFAULT, b"aaab"), 2, 0, |i| token(i, b'a'), |n, _| n + 1);
+        assert_eq!(r.into_inner(), State::Data(new(DEFAULT, b"ab"), 2));
+        let r = fold_many(new(END_OF_INPUT, b"aaab"), ..2, 0, |i| token(i, b'a'), |n, _| n + 1);
+        assert_eq!(r.into_inner(), State::Data(new(END_OF_INPUT, b"aab"), 1));
+        let r = fold_many(new(END_OF_INPUT, b"b"), 1.., 0, |i| token(i, b'a').map_err(|_| "token_err"), |n, _| n + 1);
+        assert_eq!(r.into_inner(), State::Error(b"b", "token_err"));
+    }
+
+    #[test]
+    fn sep_by_fold_test() {
+        let r = sep_by_fold(new(END_OF_INPUT, b""), .., 0, any, |i| token(i, b';'), |n, _| n + 1);
+        assert_eq!(r.into_inner(), State::Data(new(END_OF_INPUT, b""), 0));
+        let r = sep_by_fold(new(END_OF_INPUT, b"a;b;c"), .., vec![], any, |i| token(i, b';'), |mut v, c| { v.push(c); v });
+        assert_eq!(r.into_inner(), State::Data(new(END_OF_INPUT, b""), vec![b'a', b'b', b'c']));
+        let r = sep_by_fold(new(END_OF_INPUT, b"a;c;"), .., 0, any, |i| token(i, b';'), |n, _| n + 1);
+        assert_eq!(r.into_inner(), State::Data(new(END_OF_INPUT, b";"), 2));
+        let r = sep_by_fold(new(END_OF_INPUT, b"a--c-"), .., 0, any, |i| string(i, b"--"), |n, _| n + 1);
+        assert_eq!(r.into_inner(), State::Data(new(END_OF_INPUT, b"-"), 2));
+        let r = sep_by_fold(new(END_OF_INPUT, b"a;b;c"), ..3, 0, any, |i| token(i, b';'), |n, _| n + 1);
+        assert_eq!(r.into_inner(), State::Data(new(END_OF_INPUT, b";c"), 2));
+        let r = sep_by_fold(new(END_OF_INPUT, b"a;b"), 3.., 0, any, |i| token(i, b';'), |n, _| n + 1);
+        assert_eq!(r.into_inner(), State::Incomplete(1));
+
+        let r = sep_by_fold(new(DEFAULT, b"a;c;"), .., 0, any, |i| token(i, b';'), |n, _| n + 1);
+        assert_eq!(r.into_inner(), State::Incomplete(1));
+        let r = sep_by_fold(new(DEFAULT, b"a;bc"), .., 0, any, |i| token(i, b';'), |n, _| n + 1);
+        assert_eq!(r.into_inner(), State::Data(new(DEFAULT, b"c"), 2));
+    }
+
+    #[test]
+    fn sep_by_with_seps_range() {
+        let r = sep_by_with_seps(new(END_OF_INPUT, b"a;b,c"), ..2, any, |i| any(i));
+        assert_eq!(r.into_inner(), State::Data(new(END_OF_INPUT, b";b,c"), (vec![b'a'], vec![])));
+        let r = sep_by_with_seps(new(END_OF_INPUT, b"a;b,c"), ..3, any, |i| any(i));
+        assert_eq!(r.into_inner(), State::Data(new(END_OF_INPUT, b",c"), (vec![b'a', b'b'], vec![b';'])));
+        let r = sep_by_with_seps(new(END_OF_INPUT, b"a;b"), 3.., any, |i| token(i, b';'));
+        assert_eq!(r.into_inner(), State::Incomplete(1));
+    }
+
+    #[test]
+    fn end_by_range() {
+        let r: ParseResult<_, Vec<_>, _> = end_by(new(END_OF_INPUT, b"a;b;c;"), ..2, any, |i| token(i, b';'));
+        assert_eq!(r.into_inner(), State::Data(new(END_OF_INPUT, b"b;c;"), vec![b'a']));
+        let r: ParseResult<_, Vec<_>, _> = end_by(new(END_OF_INPUT, b"a;b;c"), 3, any, |i| token(i, b';'));
+        assert_eq!(r.into_inner(), State::Incomplete(1));
+        let r: ParseResult<_, Vec<_>, _> = end_by(new(END_OF_INPUT, b"a;b;c;d"), 3, any, |i| token(i, b';'));
+        assert_eq!(r.into_inner(), State::Data(new(END_OF_INPUT, b"d"), vec![b'a', b'b', b'c']));
+    }
+
+    #[test]
+    fn sep_end_by_range() {
+        let r: ParseResult<_, Vec<_>, _> = sep_end_by(new(END_OF_INPUT, b"a;b;c;"), ..2, any, |i| token(i, b';'));
+        assert_eq!(r.into_inner(), State::Data(new(END_OF_INPUT, b"b;c;"), vec![b'a']));
+        let r: ParseResult<_, Vec<_>, _> = sep_end_by(new(END_OF_INPUT, b"a;b"), 3, any, |i| token(i, b';'));
+        assert_eq!(r.into_inner(), State::Incomplete(1));
+        let r: ParseResult<_, Vec<_>, _> = sep_end_by(new(END_OF_INPUT, b"a;b;c;d"), 3, any, |i| token(i, b';'));
+        assert_eq!(r.into_inner(), State::Data(new(END_OF_INPUT, b"d"), vec![b'a', b'b', b'c']));
+    }
+
     #[test]
     #[should_panic]
     fn panic_many_range_lt() {
@@ -5,10 +5,14 @@ mod macros;
 
 pub mod bounded;
 
+use std::cmp::Ordering;
 use std::iter::FromIterator;
+use std::marker::PhantomData;
 
-use {ParseResult, Input};
+use {ParseResult, SimpleResult, Input};
 
+use parsers::{Error, ParseErrorKind};
+use primitives::input;
 use primitives::State;
 use primitives::{IntoInner, InputBuffer, InputClone};
 
@@ -37,6 +41,59 @@ pub fn count<'a, I, T, E, F, U>(i: Input<'a, I>, num: usize, p: F) -> ParseResul
     bounded::many(i, num, p)
 }
 
+/// Applies the parser ``p`` exactly ``num`` times, discarding all produced values.
+///
+/// ```
+/// use chomp::{ParseError, Error, parse_only, token, take_remainder};
+/// use chomp::combinators::skip_count;
+///
+/// let p = |i| skip_count(i, 2, |i| token(i, b'a')).then(take_remainder);
+///
+/// assert_eq!(parse_only(&p, b"a  "), Err(ParseError::Error(b"  ", Error::expected(b'a'))));
+/// assert_eq!(parse_only(&p, b"aaa"), Ok(&b"a"[..]));
+/// ```
+#[inline]
+pub fn skip_count<'a, I, T, E, F>(i: Input<'a, I>, num: usize, p: F) -> ParseResult<'a, I, (), E>
+  where T: 'a,
+        F: FnMut(Input<'a, I>) -> ParseResult<'a, I, T, E> {
+    bounded::skip_many(i, num, p)
+}
+
+/// Applies the parser `p` exactly as many times as there are elements in the array `A`, storing
+/// the items in a stack-allocated array instead of collecting them into a `T: FromIterator`.
+///
+/// The array is first filled with `Default::default()` values which are overwritten as the
+/// items are parsed, this means `T` needs to implement `Default`.
+///
+/// ```
+/// use chomp::{parse_only, any};
+/// use chomp::combinators::count_fixed;
+///
+/// let r: Result<[u8; 3], _> = parse_only(|i| count_fixed(i, any), b"abcd");
+///
+/// assert_eq!(r, Ok([b'a', b'b', b'c']));
+/// ```
+#[inline]
+pub fn count_fixed<'a, I, A, T, E, F>(i: Input<'a, I>, mut p: F) -> ParseResult<'a, I, A, E>
+  where I: Copy,
+        T: 'a,
+        A: 'a + Default + AsMut<[T]>,
+        F: FnMut(Input<'a, I>) -> ParseResult<'a, I, T, E> {
+    let mut arr = A::default();
+
+    let r = {
+        let mut slots = arr.as_mut().iter_mut();
+        let n         = slots.len();
+
+        bounded::skip_many(i, n, |i| p(i).map(|t| {
+            // Cannot fail, skip_many never applies the parser more than n times
+            *slots.next().unwrap() = t;
+        }))
+    };
+
+    r.map(|_| arr)
+}
+
 /// Tries the parser ``f``, on success it yields the parsed value, on failure ``default`` will be
 /// yielded instead.
 ///
@@ -67,6 +124,91 @@ pub fn option<'a, I, T, E, F>(i: Input<'a, I>, f: F, default: T) -> ParseResult<
     }
 }
 
+/// Tries the parser ``f``, on success it yields the parsed value wrapped in `Some`, on failure
+/// `None` will be yielded instead.
+///
+/// Incomplete state is propagated. Backtracks on error.
+///
+/// ```
+/// use chomp::{Input, U8Result, parse_only, token};
+/// use chomp::combinators::optional;
+///
+/// fn f(i: Input<u8>) -> U8Result<Option<u8>> {
+///     optional(i, |i| token(i, b'a'))
+/// }
+///
+/// assert_eq!(parse_only(f, b"abc"), Ok(Some(b'a')));
+/// assert_eq!(parse_only(f, b"bbc"), Ok(None));
+/// ```
+#[inline]
+pub fn optional<'a, I, T, E, F>(i: Input<'a, I>, f: F) -> ParseResult<'a, I, Option<T>, E>
+  where I: 'a + Copy,
+        T: 'a,
+        F: FnOnce(Input<'a, I>) -> ParseResult<'a, I, T, E> {
+    option(i, |i| f(i).map(Some), None)
+}
+
+/// Tries the parser ``f``, on success it yields the parsed value, on failure the value produced
+/// by applying ``g`` to the error of ``f`` is yielded instead.
+///
+/// Incomplete state is propagated. Backtracks on error. If `f` is incomplete on the last slice of
+/// the input the fallback value is built from `E::unexpected()`, like `option` yields its default
+/// value in that case.
+///
+/// ```
+/// use chomp::{Error, Input, ParseResult, parse_only, token};
+/// use chomp::combinators::option_or_else;
+///
+/// fn f(i: Input<u8>) -> ParseResult<u8, Result<u8, Error<u8>>, Error<u8>> {
+///     option_or_else(i, |i| token(i, b'a').map(Ok), Err)
+/// }
+///
+/// assert_eq!(parse_only(f, b"abc"), Ok(Ok(b'a')));
+/// assert_eq!(parse_only(f, b"bbc"), Ok(Err(Error::expected(b'a'))));
+/// ```
+#[inline]
+pub fn option_or_else<'a, I, T, E, F, G>(i: Input<'a, I>, f: F, g: G) -> ParseResult<'a, I, T, E>
+  where I: 'a + Copy,
+        E: ParseErrorKind<I>,
+        F: FnOnce(Input<'a, I>) -> ParseResult<'a, I, T, E>,
+        G: FnOnce(E) -> T {
+    match f(i.clone()).into_inner() {
+        State::Data(b, d)    => b.ret(d),
+        State::Error(_, e)   => i.ret(g(e)),
+        State::Incomplete(n) => if i.is_last_slice() {
+            i.ret(g(E::unexpected()))
+        } else {
+            i.incomplete(n)
+        },
+    }
+}
+
+/// Applies the parser `f` only if `flag` is true, yielding its value wrapped in `Some`. If `flag`
+/// is false `None` is yielded without consuming any input.
+///
+/// Errors and incomplete state from `f` are propagated.
+///
+/// ```
+/// use chomp::{parse_only, any, take};
+/// use chomp::combinators::cond;
+///
+/// // The first byte signals if an optional two-byte field is present
+/// let p = |i| any(i).bind(|i, flag| cond(i, flag == 1, |i| take(i, 2)));
+///
+/// assert_eq!(parse_only(&p, b"\x01ab"), Ok(Some(&b"ab"[..])));
+/// assert_eq!(parse_only(&p, b"\x00ab"), Ok(None));
+/// ```
+#[inline]
+pub fn cond<'a, I, T, E, F>(i: Input<'a, I>, flag: bool, f: F) -> ParseResult<'a, I, Option<T>, E>
+  where T: 'a,
+        F: FnOnce(Input<'a, I>) -> ParseResult<'a, I, T, E> {
+    if flag {
+        f(i).map(Some)
+    } else {
+        i.ret(None)
+    }
+}
+
 /// Tries to match the parser ``f``, if ``f`` fails it tries ``g``. Returns the success value of
 /// the first match, otherwise the error of the last one if both fail.
 ///
@@ -101,6 +243,173 @@ pub fn or<'a, I, T, E, F, G>(i: Input<'a, I>, f: F, g: G) -> ParseResult<'a, I,
     }
 }
 
+/// Tries to match the parser ``f``, if ``f`` fails it tries ``g``. Returns the success value of
+/// the first match, otherwise the error of the last one if both fail.
+///
+/// Unlike `or`, if both parsers fail at the same position the expected tokens of both errors are
+/// merged, making the error report all the tokens which would have been acceptable.
+///
+/// Incomplete state is propagated from the first one to report incomplete.
+///
+/// ```
+/// use chomp::{ParseError, Error, parse_only, token};
+/// use chomp::combinators::or_merged;
+///
+/// let p = |i| or_merged(i, |i| token(i, b'a'), |i| token(i, b'b'));
+///
+/// assert_eq!(parse_only(&p, b"abc"), Ok(b'a'));
+/// assert_eq!(parse_only(&p, b"bcd"), Ok(b'b'));
+/// assert_eq!(parse_only(&p, b"cde"), Err(ParseError::Error(b"cde", Error::expected(b'a').merge(Error::expected(b'b')))));
+/// ```
+#[inline]
+pub fn or_merged<'a, I, T, E, F, G>(i: Input<'a, I>, f: F, g: G) -> ParseResult<'a, I, T, E>
+  where I: Copy,
+        T: 'a,
+        E: ParseErrorKind<I>,
+        F: FnOnce(Input<'a, I>) -> ParseResult<'a, I, T, E>,
+        G: FnOnce(Input<'a, I>) -> ParseResult<'a, I, T, E> {
+    match f(i.clone()).into_inner() {
+        State::Data(b, d)    => b.ret(d),
+        State::Error(b, e)   => match g(i.clone()).into_inner() {
+            State::Data(c, d)    => c.ret(d),
+            State::Error(c, n)   => if b.len() == c.len() {
+                i.replace(c).err(e.merge(n))
+            } else {
+                i.replace(c).err(n)
+            },
+            State::Incomplete(n) => i.incomplete(n),
+        },
+        State::Incomplete(n) => if i.is_last_slice() {
+            g(i)
+        } else {
+            i.incomplete(n)
+        },
+    }
+}
+
+/// Tries to match the parser ``f``, if ``f`` fails it tries ``g``. Returns the success value of
+/// the first match, otherwise the error which occurred farthest into the input if both fail.
+///
+/// If both parsers fail at the same position the expected tokens of both errors are merged like
+/// `or_merged`. Nesting `or_farthest` keeps the set of tokens which were acceptable at the
+/// farthest failure of all the alternatives, which usually is the most relevant error.
+///
+/// Incomplete state is propagated from the first one to report incomplete.
+///
+/// Farthest failure reporting is opt-in, `or` and the `<|>` operator of `parse!` keep reporting
+/// the error of the last alternative.
+///
+/// ```
+/// use chomp::{ParseError, Error, parse_only, token};
+/// use chomp::combinators::or_farthest;
+///
+/// let p = |i| or_farthest(i, |i| token(i, b'a').then(|i| token(i, b'b')), |i| token(i, b'c'));
+///
+/// assert_eq!(parse_only(&p, b"ab"), Ok(b'b'));
+/// assert_eq!(parse_only(&p, b"c"), Ok(b'c'));
+/// assert_eq!(parse_only(&p, b"ac"), Err(ParseError::Error(b"c", Error::expected(b'b'))));
+/// ```
+#[inline]
+pub fn or_farthest<'a, I, T, E, F, G>(i: Input<'a, I>, f: F, g: G) -> ParseResult<'a, I, T, E>
+  where I: Copy,
+        T: 'a,
+        E: ParseErrorKind<I>,
+        F: FnOnce(Input<'a, I>) -> ParseResult<'a, I, T, E>,
+        G: FnOnce(Input<'a, I>) -> ParseResult<'a, I, T, E> {
+    match f(i.clone()).into_inner() {
+        State::Data(b, d)    => b.ret(d),
+        State::Error(b, e)   => match g(i.clone()).into_inner() {
+            State::Data(c, d)    => c.ret(d),
+            // The error with the shortest remainder is the farthest into the input
+            State::Error(c, n)   => match b.len().cmp(&c.len()) {
+                Ordering::Less    => i.replace(b).err(e),
+                Ordering::Equal   => i.replace(c).err(e.merge(n)),
+                Ordering::Greater => i.replace(c).err(n),
+            },
+            State::Incomplete(n) => i.incomplete(n),
+        },
+        State::Incomplete(n) => if i.is_last_slice() {
+            g(i)
+        } else {
+            i.incomplete(n)
+        },
+    }
+}
+
+/// Value yielded by `combinators::either`, indicating which of the two parsers matched.
+#[derive(Copy, Clone, Debug, Eq, PartialEq, Ord, PartialOrd, Hash)]
+pub enum Either<L, R> {
+    /// The first parser matched.
+    Left(L),
+    /// The second parser matched.
+    Right(R),
+}
+
+/// Tries to match the parser ``f``, if ``f`` fails it tries ``g``. Returns the success value of
+/// the first match wrapped in `Either`, otherwise the error of the last one if both fail.
+///
+/// Unlike `or` the parsers can yield different types.
+///
+/// Incomplete state is propagated from the first one to report incomplete.
+///
+/// ```
+/// use chomp::{parse_only, token};
+/// use chomp::ascii::decimal;
+/// use chomp::combinators::{either, Either};
+///
+/// let p = |i| either(i, decimal::<u32>, |i| token(i, b'x'));
+///
+/// assert_eq!(parse_only(&p, b"123"), Ok(Either::Left(123)));
+/// assert_eq!(parse_only(&p, b"x"), Ok(Either::Right(b'x')));
+/// ```
+#[inline]
+pub fn either<'a, I, L, R, E, F, G>(i: Input<'a, I>, f: F, g: G) -> ParseResult<'a, I, Either<L, R>, E>
+  where L: 'a,
+        R: 'a,
+        F: FnOnce(Input<'a, I>) -> ParseResult<'a, I, L, E>,
+        G: FnOnce(Input<'a, I>) -> ParseResult<'a, I, R, E> {
+    or(i, |i| f(i).map(Either::Left), |i| g(i).map(Either::Right))
+}
+
+/// Tries both the parser `f` and the parser `g` on the same input, yielding the result of the one
+/// which consumed the most input. If both consumed the same amount of input the result of `f` is
+/// used. Returns the error of `g` if both fail.
+///
+/// Unlike `or` this does not depend on the order of the alternatives, which makes it useful for
+/// grammars where one alternative is a prefix of another.
+///
+/// Incomplete state is propagated from either parser if end of input has not been reached, since
+/// more input might yield a longer match.
+///
+/// ```
+/// use chomp::{parse_only, string};
+/// use chomp::combinators::or_longest;
+///
+/// let p = |i| or_longest(i, |i| string(i, b"<"), |i| string(i, b"<="));
+///
+/// assert_eq!(parse_only(p, b"<= 2"), Ok(&b"<="[..]));
+/// assert_eq!(parse_only(p, b"< 2"), Ok(&b"<"[..]));
+/// ```
+#[inline]
+pub fn or_longest<'a, I, T, E, F, G>(i: Input<'a, I>, f: F, g: G) -> ParseResult<'a, I, T, E>
+  where I: Copy,
+        F: FnOnce(Input<'a, I>) -> ParseResult<'a, I, T, E>,
+        G: FnOnce(Input<'a, I>) -> ParseResult<'a, I, T, E> {
+    match (f(i.clone()).into_inner(), g(i.clone()).into_inner()) {
+        (State::Incomplete(n), _) | (_, State::Incomplete(n)) if ! i.is_last_slice() => i.incomplete(n),
+        (State::Data(b, d), State::Data(c, e)) => if c.buffer().len() < b.buffer().len() {
+            c.ret(e)
+        } else {
+            b.ret(d)
+        },
+        (State::Data(b, d), _)                       => b.ret(d),
+        (_, State::Data(b, d))                       => b.ret(d),
+        (_, State::Error(b, e))                      => i.replace(b).err(e),
+        (State::Error(b, e), State::Incomplete(_))   => i.replace(b).err(e),
+        (State::Incomplete(n), State::Incomplete(_)) => i.incomplete(n),
+    }
+}
+
 /// Parses many instances of ``f`` until it does no longer match, collecting all matches into the
 /// type `T: FromIterator`.
 ///
@@ -156,192 +465,928 @@ pub fn many1<'a, I, T, E, F, U>(i: Input<'a, I>, f: F) -> ParseResult<'a, I, T,
     bounded::many(i, 1.., f)
 }
 
-/// Applies the parser `R` zero or more times, separated by the parser `F`. All matches from `R`
-/// will be collected into the type `T: FromIterator`.
-///
-/// If the separator or parser registers error or incomplete this parser stops and yields the
-/// collected value.
+/// Applies the parser `R` zero or more times, separated by the parser `F`. All matches from `R`
+/// will be collected into the type `T: FromIterator`.
+///
+/// If the separator or parser registers error or incomplete this parser stops and yields the
+/// collected value.
+///
+/// Incomplete will be propagated from `R` if end of input has not been read.
+///
+/// ```
+/// use chomp::{parse_only, sep_by, token};
+/// use chomp::ascii::decimal;
+///
+/// let r: Result<Vec<u8>, _> = parse_only(|i| sep_by(i, decimal, |i| token(i, b';')), b"91;03;20");
+///
+/// assert_eq!(r, Ok(vec![91, 03, 20]));
+/// ```
+#[inline]
+pub fn sep_by<'a, I, T, E, R, F, U, N, V>(i: Input<'a, I>, p: R, sep: F) -> ParseResult<'a, I, T, E>
+  where I: Copy,
+        U: 'a,
+        V: 'a,
+        N: 'a,
+        T: FromIterator<U>,
+        E: From<N>,
+        R: FnMut(Input<'a, I>) -> ParseResult<'a, I, U, E>,
+        F: FnMut(Input<'a, I>) -> ParseResult<'a, I, V, N> {
+    bounded::sep_by(i, .., p, sep)
+}
+
+
+/// Applies the parser `R` one or more times, separated by the parser `F`. All matches from `R`
+/// will be collected into the type `T: FromIterator`.
+///
+/// If the separator or parser registers error or incomplete this parser stops and yields the
+/// collected value if at least one item has been read.
+///
+/// Incomplete will be propagated from `R` if end of input has not been read.
+///
+/// ```
+/// use chomp::{parse_only, sep_by1, token};
+/// use chomp::ascii::decimal;
+///
+/// let r: Result<Vec<u8>, _> = parse_only(|i| sep_by1(i, decimal, |i| token(i, b';')), b"91;03;20");
+///
+/// assert_eq!(r, Ok(vec![91, 03, 20]));
+/// ```
+#[inline]
+pub fn sep_by1<'a, I, T, E, R, F, U, N, V>(i: Input<'a, I>, p: R, sep: F) -> ParseResult<'a, I, T, E>
+  where I: Copy,
+        U: 'a,
+        V: 'a,
+        N: 'a,
+        T: FromIterator<U>,
+        E: From<N>,
+        R: FnMut(Input<'a, I>) -> ParseResult<'a, I, U, E>,
+        F: FnMut(Input<'a, I>) -> ParseResult<'a, I, V, N> {
+    bounded::sep_by(i, 1.., p, sep)
+}
+
+/// Applies the parser `R` zero or more times, separated by the parser `F`. All matches from `R`
+/// and `F` will be collected into separate `Vec`s, in the order they appeared.
+///
+/// If the separator or parser registers error or incomplete this parser stops and yields the
+/// collected values.
+///
+/// Incomplete will be propagated from `R` if end of input has not been read.
+///
+/// ```
+/// use chomp::{parse_only, satisfy};
+/// use chomp::ascii::decimal;
+/// use chomp::combinators::sep_by_with_seps;
+///
+/// let r = parse_only(|i| sep_by_with_seps(i, decimal::<u8>, |i| satisfy(i, |c| c == b'+' || c == b'-')), b"1+2-3");
+///
+/// assert_eq!(r, Ok((vec![1, 2, 3], vec![b'+', b'-'])));
+/// ```
+#[inline]
+pub fn sep_by_with_seps<'a, I, E, R, F, U, N, V>(i: Input<'a, I>, p: R, sep: F) -> ParseResult<'a, I, (Vec<U>, Vec<V>), E>
+  where U: 'a,
+        V: 'a,
+        N: 'a,
+        E: From<N>,
+        R: FnMut(Input<'a, I>) -> ParseResult<'a, I, U, E>,
+        F: FnMut(Input<'a, I>) -> ParseResult<'a, I, V, N> {
+    bounded::sep_by_with_seps(i, .., p, sep)
+}
+
+/// Applies the parser `R` zero or more times, each match followed by the parser `F`. All matches
+/// from `R` will be collected into the type `T: FromIterator`.
+///
+/// If the separator or parser registers error or incomplete this parser stops and yields the
+/// collected value.
+///
+/// Incomplete will be propagated from `R` if end of input has not been read.
+///
+/// ```
+/// use chomp::{parse_only, token};
+/// use chomp::ascii::decimal;
+/// use chomp::combinators::end_by;
+///
+/// let r: Result<Vec<u8>, _> = parse_only(|i| end_by(i, decimal, |i| token(i, b';')), b"91;03;20;");
+///
+/// assert_eq!(r, Ok(vec![91, 03, 20]));
+/// ```
+#[inline]
+pub fn end_by<'a, I, T, E, R, F, U, N, V>(i: Input<'a, I>, p: R, sep: F) -> ParseResult<'a, I, T, E>
+  where I: Copy,
+        U: 'a,
+        V: 'a,
+        N: 'a,
+        T: FromIterator<U>,
+        E: From<N>,
+        R: FnMut(Input<'a, I>) -> ParseResult<'a, I, U, E>,
+        F: FnMut(Input<'a, I>) -> ParseResult<'a, I, V, N> {
+    bounded::end_by(i, .., p, sep)
+}
+
+/// Applies the parser `R` zero or more times, separated by the parser `F` and optionally followed
+/// by a trailing `F`. All matches from `R` will be collected into the type `T: FromIterator`.
+///
+/// If the separator or parser registers error or incomplete this parser stops and yields the
+/// collected value.
+///
+/// Incomplete will be propagated from `R` if end of input has not been read.
+///
+/// ```
+/// use chomp::{parse_only, token};
+/// use chomp::ascii::decimal;
+/// use chomp::combinators::sep_end_by;
+///
+/// let p = |i| sep_end_by(i, decimal, |i| token(i, b';'));
+///
+/// assert_eq!(parse_only(&p, b"91;03;20"), Ok(vec![91u8, 03, 20]));
+/// assert_eq!(parse_only(&p, b"91;03;20;"), Ok(vec![91u8, 03, 20]));
+/// ```
+#[inline]
+pub fn sep_end_by<'a, I, T, E, R, F, U, N, V>(i: Input<'a, I>, p: R, sep: F) -> ParseResult<'a, I, T, E>
+  where I: Copy,
+        U: 'a,
+        V: 'a,
+        N: 'a,
+        T: FromIterator<U>,
+        E: From<N>,
+        R: FnMut(Input<'a, I>) -> ParseResult<'a, I, U, E>,
+        F: FnMut(Input<'a, I>) -> ParseResult<'a, I, V, N> {
+    bounded::sep_end_by(i, .., p, sep)
+}
+
+/// Applies the parser `R` multiple times until the parser `F` succeeds and returns a
+/// `T: FromIterator` populated by the values yielded by `R`. Consumes the matched part of `F`.
+///
+/// This parser is considered incomplete if the parser `R` is considered incomplete.
+///
+/// Errors from `R` are propagated.
+///
+/// ```
+/// use chomp::{parse_only, many_till, any, token};
+///
+/// let r: Result<Vec<u8>, _> = parse_only(|i| many_till(i, any, |i| token(i, b';')), b"abc;def");
+///
+/// assert_eq!(r, Ok(vec![b'a', b'b', b'c']));
+/// ```
+#[inline]
+pub fn many_till<'a, I, T, E, R, F, U, N, V>(i: Input<'a, I>, p: R, end: F) -> ParseResult<'a, I, T, E>
+  where I: Copy,
+        U: 'a,
+        V: 'a,
+        N: 'a,
+        T: FromIterator<U>,
+        R: FnMut(Input<'a, I>) -> ParseResult<'a, I, U, E>,
+        F: FnMut(Input<'a, I>) -> ParseResult<'a, I, V, N> {
+    bounded::many_till(i, .., p, end)
+}
+
+/// Applies the parser `R` multiple times until the parser `F` succeeds and returns a tuple of a
+/// `T: FromIterator` populated by the values yielded by `R` and the value yielded by `F`. Consumes
+/// the matched part of `F`.
+///
+/// This parser is considered incomplete if the parser `R` is considered incomplete.
+///
+/// Errors from `R` are propagated.
+///
+/// ```
+/// use chomp::{parse_only, any, string};
+/// use chomp::combinators::{many_till_with_end, or};
+///
+/// let p = |i| many_till_with_end(i, any, |i| or(i, |i| string(i, b"</a>"), |i| string(i, b"</b>")));
+///
+/// let r: Result<(Vec<u8>, _), _> = parse_only(p, b"abc</b>def");
+///
+/// assert_eq!(r, Ok((vec![b'a', b'b', b'c'], &b"</b>"[..])));
+/// ```
+#[inline]
+pub fn many_till_with_end<'a, I, T, E, R, F, U, N, V>(i: Input<'a, I>, p: R, end: F) -> ParseResult<'a, I, (T, V), E>
+  where I: Copy,
+        U: 'a,
+        V: 'a,
+        N: 'a,
+        T: FromIterator<U>,
+        R: FnMut(Input<'a, I>) -> ParseResult<'a, I, U, E>,
+        F: FnMut(Input<'a, I>) -> ParseResult<'a, I, V, N> {
+    bounded::many_till_with_end(i, .., p, end)
+}
+
+/// Runs the given parser until it fails, discarding matched input.
+///
+/// Incomplete state will be propagated.
+///
+/// This is more efficient compared to using ``many`` and then just discarding the result as
+/// ``many`` allocates a separate data structure to contain the data before proceeding.
+///
+/// ```
+/// use chomp::{parse_only, skip_many, token};
+///
+/// let r = parse_only(|i| skip_many(i, |i| token(i, b'a')).then(|i| token(i, b'b')), b"aaaabc");
+///
+/// assert_eq!(r, Ok(b'b'));
+/// ```
+#[inline]
+pub fn skip_many<'a, I, T, E, F>(i: Input<'a, I>, f: F) -> ParseResult<'a, I, (), E>
+  where T: 'a,
+        F: FnMut(Input<'a, I>) -> ParseResult<'a, I, T, E> {
+    bounded::skip_many(i, .., f)
+}
+
+/// Runs the given parser until it fails, discarding matched input, expects at least one match.
+///
+/// Incomplete state will be propagated. Will propagate the error if it occurs during the first
+/// attempt.
+///
+/// This is more efficient compared to using ``many1`` and then just discarding the result as
+/// ``many1`` allocates a separate data structure to contain the data before proceeding.
+///
+/// ```
+/// use chomp::{ParseError, Error, parse_only, skip_many1, token};
+///
+/// let p = |i| skip_many1(i, |i| token(i, b'a')).bind(|i, _| token(i, b'b'));
+///
+/// assert_eq!(parse_only(&p, b"aaaabc"), Ok(b'b'));
+/// assert_eq!(parse_only(&p, b"abc"), Ok(b'b'));
+///
+/// assert_eq!(parse_only(&p, b"bc"), Err(ParseError::Error(b"bc", Error::expected(b'a'))));
+/// ```
+#[inline]
+pub fn skip_many1<'a, I, T, E, F>(i: Input<'a, I>, f: F) -> ParseResult<'a, I, (), E>
+  where T: 'a, F: FnMut(Input<'a, I>) -> ParseResult<'a, I, T, E> {
+    bounded::skip_many(i, 1.., f)
+}
+
+/// Returns the result of the given parser as well as the slice which matched it.
+///
+/// ```
+/// use chomp::{parse_only, matched_by};
+/// use chomp::ascii::decimal;
+///
+/// assert_eq!(parse_only(|i| matched_by(i, decimal), b"123"), Ok((&b"123"[..], 123u32)));
+/// ```
+#[inline]
+pub fn matched_by<'a, I, T, E, F>(i: Input<'a, I>, f: F) -> ParseResult<'a, I, (&'a [I], T), E>
+  where T: 'a,
+        F: FnOnce(Input<'a, I>) -> ParseResult<'a, I, T, E> {
+    let buf = i.buffer();
+
+    match f(i.clone()).into_inner() {
+        State::Data(b, t) => {
+            // b is remainder, find out how much the parser used
+            let diff = buf.len() - b.buffer().len();
+            let n    = &buf[..diff];
+
+            b.ret((n, t))
+        },
+        State::Error(b, e)   => i.replace(b).err(e),
+        State::Incomplete(n) => i.incomplete(n),
+    }
+}
+
+/// Parses using `f` and returns only the slice of input matched, discarding the value produced
+/// by `f`.
+///
+/// ```
+/// use chomp::parse_only;
+/// use chomp::combinators::recognize;
+/// use chomp::ascii::decimal;
+///
+/// assert_eq!(parse_only(|i| recognize(i, decimal::<u32>), b"123 "), Ok(&b"123"[..]));
+/// ```
+#[inline]
+pub fn recognize<'a, I, T, E, F>(i: Input<'a, I>, f: F) -> ParseResult<'a, I, &'a [I], E>
+  where T: 'a,
+        F: FnOnce(Input<'a, I>) -> ParseResult<'a, I, T, E> {
+    matched_by(i, f).map(|(b, _)| b)
+}
+
+/// Result of a single step of `combinators::iterate`.
+#[derive(Copy, Clone, Debug, Eq, PartialEq, Ord, PartialOrd, Hash)]
+pub enum Step<S, T> {
+    /// Continue iterating with the new state.
+    Continue(S),
+    /// Stop iterating, yielding the final value.
+    Break(T),
+}
+
+/// Repeatedly applies the parser `f` with the current state, starting with `init`, until it yields
+/// `Step::Break`. The state provided by `Step::Continue` is passed to the next application of `f`.
+///
+/// Errors and incomplete state from `f` are propagated.
+///
+/// Note: Will loop forever if `f` keeps yielding `Step::Continue` without consuming any input.
+///
+/// ```
+/// use chomp::{parse_only, any, take};
+/// use chomp::combinators::{iterate, Step};
+///
+/// // Chunks prefixed by their length, terminated by a zero-length chunk
+/// let p = |i| iterate(i, Vec::new(), |i, mut v: Vec<u8>| any(i).bind(|i, n| if n == 0 {
+///     i.ret(Step::Break(v))
+/// } else {
+///     take(i, n as usize).map(|b| { v.extend(b); Step::Continue(v) })
+/// }));
+///
+/// assert_eq!(parse_only(p, b"\x02ab\x01c\x00"), Ok(b"abc".to_vec()));
+/// ```
+#[inline]
+pub fn iterate<'a, I, S, T, E, F>(i: Input<'a, I>, init: S, mut f: F) -> ParseResult<'a, I, T, E>
+  where I: Copy,
+        S: 'a,
+        T: 'a,
+        F: FnMut(Input<'a, I>, S) -> ParseResult<'a, I, Step<S, T>, E> {
+    let mut i     = i;
+    let mut state = init;
+
+    loop {
+        match f(i.clone(), state).into_inner() {
+            State::Data(b, Step::Continue(s)) => {
+                i     = b;
+                state = s;
+            },
+            State::Data(b, Step::Break(t))    => return b.ret(t),
+            State::Error(b, e)                => return i.replace(b).err(e),
+            State::Incomplete(n)              => return i.incomplete(n),
+        }
+    }
+}
+
+/// Iterator applying a parser repeatedly to its input, created by `combinators::parse_iter`.
+///
+/// Iteration stops once the parser fails, use `finish` to obtain the remaining input once
+/// iteration is complete.
+pub struct ParseIter<'a, I, T, E, F>
+  where I: 'a,
+        T: 'a,
+        E: 'a,
+        F: FnMut(Input<'a, I>) -> ParseResult<'a, I, T, E> {
+    /// Parser to apply for each item
+    parser: F,
+    /// Remaining input
+    buf:    Input<'a, I>,
+    /// `Some(n)` if the parser stopped due to an incomplete state
+    needed: Option<usize>,
+    /// If the parser has failed and iteration is done
+    done:   bool,
+    _t:     PhantomData<(T, E)>,
+}
+
+impl<'a, I, T, E, F> ParseIter<'a, I, T, E, F>
+  where I: 'a + Copy,
+        T: 'a,
+        E: 'a,
+        F: FnMut(Input<'a, I>) -> ParseResult<'a, I, T, E> {
+    /// Consumes the iterator and yields the remaining input.
+    ///
+    /// Incomplete state is propagated if the parser stopped due to lack of input and end of input
+    /// has not been reached, same as `many`. Iteration can be ended early by calling this method
+    /// before the iterator has been exhausted.
+    #[inline]
+    pub fn finish(self) -> ParseResult<'a, I, (), E> {
+        match self.needed {
+            Some(n) if ! self.buf.is_last_slice() => self.buf.incomplete(n),
+            _                                     => self.buf.ret(()),
+        }
+    }
+}
+
+impl<'a, I, T, E, F> Iterator for ParseIter<'a, I, T, E, F>
+  where I: 'a + Copy,
+        T: 'a,
+        E: 'a,
+        F: FnMut(Input<'a, I>) -> ParseResult<'a, I, T, E> {
+    type Item = T;
+
+    #[inline]
+    fn next(&mut self) -> Option<T> {
+        if self.done {
+            return None;
+        }
+
+        match (self.parser)(self.buf.clone()).into_inner() {
+            State::Data(b, t)    => {
+                self.buf = b;
+
+                Some(t)
+            },
+            State::Error(_, _)   => {
+                self.done = true;
+
+                None
+            },
+            State::Incomplete(n) => {
+                self.done   = true;
+                self.needed = Some(n);
+
+                None
+            },
+        }
+    }
+}
+
+/// Creates an iterator which lazily applies the parser `f` to the input, yielding each parsed
+/// value until `f` fails.
+///
+/// Unlike `many` no values are collected, allowing for incremental processing and stopping early.
+/// Use `ParseIter::finish` to resume parsing with the remaining input.
+///
+/// ```
+/// use chomp::{parse_only, token};
+/// use chomp::ascii::decimal;
+/// use chomp::combinators::parse_iter;
+///
+/// let p = |i| {
+///     let mut iter = parse_iter(i, |i| decimal::<u32>(i).bind(|i, n| token(i, b',').map(|_| n)));
+///     // Stop at the first zero
+///     let sum: u32 = iter.by_ref().take_while(|&n| n != 0).fold(0, |a, n| a + n);
+///
+///     iter.finish().map(|_| sum)
+/// };
+///
+/// assert_eq!(parse_only(p, b"1,2,3,0,4,"), Ok(6));
+/// ```
+#[inline]
+pub fn parse_iter<'a, I, T, E, F>(i: Input<'a, I>, f: F) -> ParseIter<'a, I, T, E, F>
+  where I: 'a + Copy,
+        T: 'a,
+        E: 'a,
+        F: FnMut(Input<'a, I>) -> ParseResult<'a, I, T, E> {
+    ParseIter {
+        parser: f,
+        buf:    i,
+        needed: None,
+        done:   false,
+        _t:     PhantomData,
+    }
+}
+
+/// Applies the parser `F` without consuming any input.
+///
+/// ```
+/// use chomp::{parse_only, take};
+/// use chomp::combinators::look_ahead;
+///
+/// let p = |i| look_ahead(i, |i| take(i, 4)).bind(|i, t| take(i, 7).map(|u| (t, u)));
+///
+/// assert_eq!(parse_only(p, b"testing"), Ok((&b"test"[..], &b"testing"[..])));
+/// ```
+#[inline]
+pub fn look_ahead<'a, I, T, E, F>(i: Input<'a, I>, f: F) -> ParseResult<'a, I, T, E>
+  where F: FnOnce(Input<'a, I>) -> ParseResult<'a, I, T, E> {
+    match f(i.clone()).into_inner() {
+        State::Data(_, t)    => i.ret(t),
+        State::Error(b, t)   => i.replace(b).err(t),
+        State::Incomplete(n) => i.incomplete(n),
+    }
+}
+
+/// Applies the parser `F`, on error the error is reported at the position where `F` started
+/// instead of where it failed.
+///
+/// Streams like `buffer::Source` consume all input up to the point of failure when a parser
+/// fails, wrapping the parser in `attempt` makes sure no input is consumed on error so that the
+/// next parser can retry from the same position.
+///
+/// Incomplete state is propagated.
+///
+/// ```
+/// use chomp::{string, or};
+/// use chomp::buffer::{IntoStream, Stream};
+/// use chomp::combinators::attempt;
+///
+/// let mut s = (&b"abd"[..]).into_stream();
+///
+/// assert!(s.parse(|i| attempt(i, |i| string(i, b"abc"))).is_err());
+/// assert_eq!(s.parse(|i| or(i, |i| string(i, b"abc"), |i| string(i, b"abd"))), Ok(&b"abd"[..]));
+/// ```
+#[inline]
+pub fn attempt<'a, I, T, E, F>(i: Input<'a, I>, f: F) -> ParseResult<'a, I, T, E>
+  where F: FnOnce(Input<'a, I>) -> ParseResult<'a, I, T, E> {
+    match f(i.clone()).into_inner() {
+        State::Data(b, t)    => b.ret(t),
+        State::Error(_, e)   => i.err(e),
+        State::Incomplete(n) => i.incomplete(n),
+    }
+}
+
+/// Applies the parser `F`, if it fails input is skipped until the synchronization parser `S`
+/// matches, after which parsing resumes. Yields `Ok` with the parsed value on success, or `Err`
+/// with the error of `F` if recovery was necessary.
+///
+/// Skipping starts at the position where `F` failed, and the input matched by `S` is consumed. If
+/// `S` never matches all remaining input is skipped.
+///
+/// Recovery always makes progress, so that it can be used in loops like `many`: if `S` matches
+/// without any input being consumed at least one item is skipped before trying `S` again, and if
+/// the last slice of the input ends without any input being consumed an unexpected error is
+/// returned.
+///
+/// Incomplete state is propagated.
+///
+/// ```
+/// use chomp::{Error, parse_only, token};
+/// use chomp::ascii::decimal;
+/// use chomp::combinators::{many, recover};
+///
+/// let p = |i| many(i, |i| recover(i, |i| decimal::<u32>(i).bind(|i, n| token(i, b';').map(|_| n)), |i| token(i, b';')));
+///
+/// assert_eq!(parse_only(p, b"12;ab;3;"), Ok(vec![Ok(12), Err(Error::new()), Ok(3)]));
+/// ```
+#[inline]
+pub fn recover<'a, I, T, E, F, S, V, N>(i: Input<'a, I>, f: F, mut sync: S) -> ParseResult<'a, I, Result<T, E>, N>
+  where I: Copy,
+        T: 'a,
+        E: 'a,
+        V: 'a,
+        N: 'a + ParseErrorKind<I>,
+        F: FnOnce(Input<'a, I>) -> ParseResult<'a, I, T, E>,
+        S: FnMut(Input<'a, I>) -> ParseResult<'a, I, V, N> {
+    match f(i.clone()).into_inner() {
+        State::Data(b, t)    => b.ret(Ok(t)),
+        State::Error(b, e)   => {
+            let mut b = b;
+
+            loop {
+                match sync(i.clone().replace(b)).into_inner() {
+                    // Only stop if the input has advanced, otherwise skip an item and try again
+                    State::Data(r, _)    => if r.buffer().len() < i.buffer().len() {
+                        return r.ret(Err(e));
+                    },
+                    State::Error(_, _)   => {},
+                    State::Incomplete(n) => if ! i.is_last_slice() {
+                        return i.incomplete(n);
+                    },
+                }
+
+                if b.len() == 0 {
+                    return if ! i.is_last_slice() {
+                        i.incomplete(1)
+                    } else if i.buffer().len() == 0 {
+                        i.err(N::unexpected())
+                    } else {
+                        i.replace(b).ret(Err(e))
+                    };
+                }
+
+                b = &b[1..];
+            }
+        },
+        State::Incomplete(n) => i.incomplete(n),
+    }
+}
+
+/// Succeeds without consuming any input if the parser `F` fails, errors with an unexpected error
+/// if `F` succeeds.
+///
+/// Incomplete state is propagated unless the input is the last slice, in which case `F` is
+/// considered to have failed.
+///
+/// ```
+/// use chomp::{parse_only, string, satisfy};
+/// use chomp::ascii::is_alphanumeric;
+/// use chomp::combinators::not_followed_by;
+///
+/// let p = |i| string(i, b"if").bind(|i, s| not_followed_by(i, |i| satisfy(i, is_alphanumeric)).map(|_| s));
+///
+/// assert_eq!(parse_only(p, b"if x"), Ok(&b"if"[..]));
+/// assert!(parse_only(p, b"ifx").is_err());
+/// ```
+#[inline]
+pub fn not_followed_by<'a, I, T, E, F>(i: Input<'a, I>, f: F) -> SimpleResult<'a, I, ()>
+  where I: 'a + Copy,
+        T: 'a,
+        E: 'a,
+        F: FnOnce(Input<'a, I>) -> ParseResult<'a, I, T, E> {
+    match f(i.clone()).into_inner() {
+        State::Data(_, _)    => i.err(Error::unexpected()),
+        State::Error(_, _)   => i.ret(()),
+        State::Incomplete(n) => if i.is_last_slice() {
+            i.ret(())
+        } else {
+            i.incomplete(n)
+        },
+    }
+}
+
+/// Applies the parser `f`, attaching the context label `label` to any error it produces.
 ///
-/// Incomplete will be propagated from `R` if end of input has not been read.
+/// Nested uses of `context` accumulate a stack of labels on the error, innermost first, which can
+/// be retrieved using `Error::context`.
 ///
 /// ```
-/// use chomp::{parse_only, sep_by, token};
-/// use chomp::ascii::decimal;
+/// use chomp::{Error, ParseError, parse_only, token};
+/// use chomp::combinators::context;
 ///
-/// let r: Result<Vec<u8>, _> = parse_only(|i| sep_by(i, decimal, |i| token(i, b';')), b"91;03;20");
+/// let elem  = |i| context(i, "array element", |i| token(i, b'a'));
+/// let array = |i| context(i, "array", |i| token(i, b'[').then(elem).bind(|i, a| token(i, b']').map(|_| a)));
 ///
-/// assert_eq!(r, Ok(vec![91, 03, 20]));
+/// let e = Error::expected(b'a').add_context("array element").add_context("array");
+///
+/// assert_eq!(parse_only(array, b"[b]"), Err(ParseError::Error(b"b]", e)));
 /// ```
 #[inline]
-pub fn sep_by<'a, I, T, E, R, F, U, N, V>(i: Input<'a, I>, p: R, sep: F) -> ParseResult<'a, I, T, E>
-  where I: Copy,
-        U: 'a,
-        V: 'a,
-        N: 'a,
-        T: FromIterator<U>,
-        E: From<N>,
-        R: FnMut(Input<'a, I>) -> ParseResult<'a, I, U, E>,
-        F: FnMut(Input<'a, I>) -> ParseResult<'a, I, V, N> {
-    bounded::sep_by(i, .., p, sep)
-}
+pub fn context<'a, I, T, E, F>(i: Input<'a, I>, label: &'static str, f: F) -> ParseResult<'a, I, T, E>
+  where T: 'a,
+        E: ParseErrorKind<I>,
+        F: FnOnce(Input<'a, I>) -> ParseResult<'a, I, T, E> {
+    match f(i.clone()).into_inner() {
+        State::Data(b, t)    => b.ret(t),
+        State::Error(b, e)   => {
+            let i = i.replace(b);
+            let n = i.offset();
 
+            i.err(e.add_context(label).at_position(n))
+        },
+        State::Incomplete(n) => i.incomplete(n),
+    }
+}
 
-/// Applies the parser `R` one or more times, separated by the parser `F`. All matches from `R`
-/// will be collected into the type `T: FromIterator`.
+/// Applies the parser `f` while limiting how far ahead it may look, if `f` attempts to inspect
+/// more than `n` items before succeeding or failing an unexpected error is returned.
 ///
-/// If the separator or parser registers error or incomplete this parser stops and yields the
-/// collected value if at least one item has been read.
+/// This puts a hard bound on how much input a parser needs to buffer, which is useful when
+/// parsing untrusted input.
 ///
-/// Incomplete will be propagated from `R` if end of input has not been read.
+/// Incomplete state is propagated as long as the requested amount of input does not exceed `n`.
 ///
 /// ```
-/// use chomp::{parse_only, sep_by1, token};
-/// use chomp::ascii::decimal;
+/// use chomp::{parse_only, take_while};
+/// use chomp::combinators::max_lookahead;
 ///
-/// let r: Result<Vec<u8>, _> = parse_only(|i| sep_by1(i, decimal, |i| token(i, b';')), b"91;03;20");
+/// let p = |i| max_lookahead(i, 4, |i| take_while(i, |c| c != b' '));
 ///
-/// assert_eq!(r, Ok(vec![91, 03, 20]));
+/// assert_eq!(parse_only(&p, b"abc def"), Ok(&b"abc"[..]));
+/// assert!(parse_only(&p, b"abcdef ").is_err());
 /// ```
 #[inline]
-pub fn sep_by1<'a, I, T, E, R, F, U, N, V>(i: Input<'a, I>, p: R, sep: F) -> ParseResult<'a, I, T, E>
+pub fn max_lookahead<'a, I, T, E, F>(i: Input<'a, I>, n: usize, f: F) -> ParseResult<'a, I, T, E>
   where I: Copy,
-        U: 'a,
-        V: 'a,
-        N: 'a,
-        T: FromIterator<U>,
-        E: From<N>,
-        R: FnMut(Input<'a, I>) -> ParseResult<'a, I, U, E>,
-        F: FnMut(Input<'a, I>) -> ParseResult<'a, I, V, N> {
-    bounded::sep_by(i, 1.., p, sep)
+        T: 'a,
+        E: ParseErrorKind<I>,
+        F: FnOnce(Input<'a, I>) -> ParseResult<'a, I, T, E> {
+    let buf = i.buffer();
+
+    if buf.len() <= n {
+        return match f(i.clone()).into_inner() {
+            State::Data(b, t)    => b.ret(t),
+            State::Error(b, e)   => i.replace(b).err(e),
+            State::Incomplete(m) => if buf.len() + m > n {
+                i.err(E::unexpected())
+            } else {
+                i.incomplete(m)
+            },
+        };
+    }
+
+    // Restrict the parser to the first n items, not marking it as the last slice so that any
+    // attempt to read beyond the window is reported as incomplete
+    match f(input::new_at(input::DEFAULT, i.offset(), &buf[..n])).into_inner() {
+        State::Data(b, t)    => i.replace(&buf[n - b.buffer().len()..]).ret(t),
+        State::Error(b, e)   => i.replace(&buf[n - b.len()..]).err(e),
+        State::Incomplete(_) => i.err(E::unexpected()),
+    }
 }
 
-/// Applies the parser `R` multiple times until the parser `F` succeeds and returns a
-/// `T: FromIterator` populated by the values yielded by `R`. Consumes the matched part of `F`.
+/// Parses a length using `l` and then applies the parser `f` to exactly that many items,
+/// yielding the result of `f`.
 ///
-/// This parser is considered incomplete if the parser `R` is considered incomplete.
+/// `f` sees the window of `n` items as the last slice of input and is never able to read past
+/// it. If `f` does not consume the whole window an unexpected error is reported at the first
+/// unconsumed item, and if `f` attempts to read past the window an unexpected error is reported at
+/// the end of the window.
 ///
-/// Errors from `R` are propagated.
+/// If the buffer is shorter than the length this parser is considered incomplete.
 ///
 /// ```
-/// use chomp::{parse_only, many_till, any, token};
+/// use chomp::{parse_only, any, take_while};
+/// use chomp::combinators::length_value;
 ///
-/// let r: Result<Vec<u8>, _> = parse_only(|i| many_till(i, any, |i| token(i, b';')), b"abc;def");
+/// let p = |i| length_value(i, |i| any(i).map(|n| n as usize), |i| take_while(i, |c| c != b'!'));
 ///
-/// assert_eq!(r, Ok(vec![b'a', b'b', b'c']));
+/// assert_eq!(parse_only(&p, b"\x03abcdef"), Ok(&b"abc"[..]));
+/// assert!(parse_only(&p, b"\x03a!cdef").is_err());
 /// ```
 #[inline]
-pub fn many_till<'a, I, T, E, R, F, U, N, V>(i: Input<'a, I>, p: R, end: F) -> ParseResult<'a, I, T, E>
+pub fn length_value<'a, I, T, E, L, F>(i: Input<'a, I>, l: L, f: F) -> ParseResult<'a, I, T, E>
   where I: Copy,
-        U: 'a,
-        V: 'a,
-        N: 'a,
-        T: FromIterator<U>,
-        R: FnMut(Input<'a, I>) -> ParseResult<'a, I, U, E>,
-        F: FnMut(Input<'a, I>) -> ParseResult<'a, I, V, N> {
-    bounded::many_till(i, .., p, end)
+        T: 'a,
+        E: ParseErrorKind<I>,
+        L: FnOnce(Input<'a, I>) -> ParseResult<'a, I, usize, E>,
+        F: FnOnce(Input<'a, I>) -> ParseResult<'a, I, T, E> {
+    l(i).bind(|i, n| {
+        let buf = i.buffer();
+
+        if buf.len() < n {
+            return i.incomplete(n - buf.len());
+        }
+
+        match f(input::new_at(input::END_OF_INPUT, i.offset(), &buf[..n])).into_inner() {
+            State::Data(b, t)    => if b.buffer().is_empty() {
+                i.replace(&buf[n..]).ret(t)
+            } else {
+                i.replace(&buf[n - b.buffer().len()..]).err(E::unexpected())
+            },
+            State::Error(b, e)   => i.replace(&buf[n - b.len()..]).err(e),
+            State::Incomplete(_) => i.replace(&buf[n..]).err(E::unexpected()),
+        }
+    })
 }
 
-/// Runs the given parser until it fails, discarding matched input.
-///
-/// Incomplete state will be propagated.
+/// Parses a length using `l` and then yields a slice of exactly that many items.
 ///
-/// This is more efficient compared to using ``many`` and then just discarding the result as
-/// ``many`` allocates a separate data structure to contain the data before proceeding.
+/// If the buffer is shorter than the length this parser is considered incomplete.
 ///
 /// ```
-/// use chomp::{parse_only, skip_many, token};
-///
-/// let r = parse_only(|i| skip_many(i, |i| token(i, b'a')).then(|i| token(i, b'b')), b"aaaabc");
+/// use chomp::{parse_only, any};
+/// use chomp::combinators::length_data;
 ///
-/// assert_eq!(r, Ok(b'b'));
+/// assert_eq!(parse_only(|i| length_data(i, |i| any(i).map(|n| n as usize)), b"\x02abc"), Ok(&b"ab"[..]));
 /// ```
 #[inline]
-pub fn skip_many<'a, I, T, E, F>(i: Input<'a, I>, f: F) -> ParseResult<'a, I, (), E>
-  where T: 'a,
-        F: FnMut(Input<'a, I>) -> ParseResult<'a, I, T, E> {
-    bounded::skip_many(i, .., f)
+pub fn length_data<'a, I, E, L>(i: Input<'a, I>, l: L) -> ParseResult<'a, I, &'a [I], E>
+  where I: Copy,
+        L: FnOnce(Input<'a, I>) -> ParseResult<'a, I, usize, E> {
+    l(i).bind(|i, n| {
+        let buf = i.buffer();
+
+        if buf.len() < n {
+            i.incomplete(n - buf.len())
+        } else {
+            i.replace(&buf[n..]).ret(&buf[..n])
+        }
+    })
 }
 
-/// Runs the given parser until it fails, discarding matched input, expects at least one match.
+/// Trait for running a tuple of parsers in sequence, implemented for tuples of up to 8 parsers.
 ///
-/// Incomplete state will be propagated. Will propagate the error if it occurs during the first
-/// attempt.
+/// Use `combinators::seq` instead of calling this trait method directly.
+pub trait Sequence<'a, I, E>
+  where I: 'a,
+        E: 'a {
+    /// The tuple of values produced by the parsers.
+    type Output;
+
+    /// Applies all the parsers in order, threading the input through each of them and collecting
+    /// the produced values into a tuple.
+    ///
+    /// Propagates the first error or incomplete state.
+    fn parse_seq(self, i: Input<'a, I>) -> ParseResult<'a, I, Self::Output, E>;
+}
+
+macro_rules! impl_sequence {
+    ( $($p:ident : $t:ident),+ ) => {
+        impl<'a, I, E, $($p, $t),+> Sequence<'a, I, E> for ($($p,)+)
+          where I: 'a,
+                E: 'a,
+                $($t: 'a, $p: FnOnce(Input<'a, I>) -> ParseResult<'a, I, $t, E>),+ {
+            type Output = ($($t,)+);
+
+            #[inline]
+            #[allow(non_snake_case)]
+            fn parse_seq(self, i: Input<'a, I>) -> ParseResult<'a, I, Self::Output, E> {
+                let ($($p,)+) = self;
+
+                impl_sequence!(@BIND(i) $($p : $t),+ => ($($t,)+))
+            }
+        }
+    };
+    ( @BIND($input:expr) $p:ident : $t:ident => $ret:expr ) => {
+        $p($input).map(|$t| $ret)
+    };
+    ( @BIND($input:expr) $p:ident : $t:ident, $($tail_p:ident : $tail_t:ident),+ => $ret:expr ) => {
+        $p($input).bind(|i, $t| impl_sequence!(@BIND(i) $($tail_p : $tail_t),+ => $ret))
+    };
+}
+
+impl_sequence!(A: T1);
+impl_sequence!(A: T1, B: T2);
+impl_sequence!(A: T1, B: T2, C: T3);
+impl_sequence!(A: T1, B: T2, C: T3, D: T4);
+impl_sequence!(A: T1, B: T2, C: T3, D: T4, F: T5);
+impl_sequence!(A: T1, B: T2, C: T3, D: T4, F: T5, G: T6);
+impl_sequence!(A: T1, B: T2, C: T3, D: T4, F: T5, G: T6, H: T7);
+impl_sequence!(A: T1, B: T2, C: T3, D: T4, F: T5, G: T6, H: T7, J: T8);
+
+/// Applies the tuple of parsers `s` in order, yielding a tuple of all their values.
 ///
-/// This is more efficient compared to using ``many1`` and then just discarding the result as
-/// ``many1`` allocates a separate data structure to contain the data before proceeding.
+/// Propagates the first error or incomplete state encountered. All the parsers must share the same
+/// error type.
 ///
 /// ```
-/// use chomp::{ParseError, Error, parse_only, skip_many1, token};
-///
-/// let p = |i| skip_many1(i, |i| token(i, b'a')).bind(|i, _| token(i, b'b'));
+/// use chomp::{parse_only, token, take};
+/// use chomp::ascii::decimal;
+/// use chomp::combinators::seq;
 ///
-/// assert_eq!(parse_only(&p, b"aaaabc"), Ok(b'b'));
-/// assert_eq!(parse_only(&p, b"abc"), Ok(b'b'));
+/// let p = |i| seq(i, (decimal::<u32>, |i| token(i, b':'), |i| take(i, 2)));
 ///
-/// assert_eq!(parse_only(&p, b"bc"), Err(ParseError::Error(b"bc", Error::expected(b'a'))));
+/// assert_eq!(parse_only(p, b"123:ab"), Ok((123, b':', &b"ab"[..])));
 /// ```
-#[inline]
-pub fn skip_many1<'a, I, T, E, F>(i: Input<'a, I>, f: F) -> ParseResult<'a, I, (), E>
-  where T: 'a, F: FnMut(Input<'a, I>) -> ParseResult<'a, I, T, E> {
-    bounded::skip_many(i, 1.., f)
-}
-
-/// Returns the result of the given parser as well as the slice which matched it.
+///
+/// It can also be used inside of `parse!` to bind multiple values at once:
 ///
 /// ```
-/// use chomp::{parse_only, matched_by};
-/// use chomp::ascii::decimal;
+/// # #[macro_use] extern crate chomp;
+/// # fn main() {
+/// use chomp::{Input, U8Result, parse_only, token, any};
+/// use chomp::combinators::seq;
 ///
-/// assert_eq!(parse_only(|i| matched_by(i, decimal), b"123"), Ok((&b"123"[..], 123u32)));
+/// fn pair(i: Input<u8>) -> U8Result<(u8, u8)> {
+///     parse!{i;
+///         let (a, _, b) = seq((any, |i| token(i, b'='), any));
+///
+///         ret (a, b)
+///     }
+/// }
+///
+/// assert_eq!(parse_only(pair, b"a=b"), Ok((b'a', b'b')));
+/// # }
 /// ```
 #[inline]
-pub fn matched_by<'a, I, T, E, F>(i: Input<'a, I>, f: F) -> ParseResult<'a, I, (&'a [I], T), E>
-  where T: 'a,
-        F: FnOnce(Input<'a, I>) -> ParseResult<'a, I, T, E> {
-    let buf = i.buffer();
+pub fn seq<'a, I, E, S>(i: Input<'a, I>, s: S) -> ParseResult<'a, I, S::Output, E>
+  where I: 'a,
+        E: 'a,
+        S: Sequence<'a, I, E> {
+    s.parse_seq(i)
+}
 
-    match f(i.clone()).into_inner() {
-        State::Data(b, t) => {
-            // b is remainder, find out how much the parser used
-            let diff = buf.len() - b.buffer().len();
-            let n    = &buf[..diff];
+/// Trait for trying a tuple of alternative parsers, implemented for tuples of up to 8 parsers.
+///
+/// Use `combinators::longest` instead of calling this trait method directly.
+pub trait Alternatives<'a, I, T, E>
+  where I: 'a,
+        T: 'a,
+        E: 'a {
+    /// Applies all the parsers to the same input, yielding the result of the one which consumed
+    /// the most input.
+    fn parse_longest(self, i: Input<'a, I>) -> ParseResult<'a, I, T, E>;
+}
 
-            b.ret((n, t))
-        },
-        State::Error(b, e)   => i.replace(b).err(e),
-        State::Incomplete(n) => i.incomplete(n),
-    }
+macro_rules! impl_alternatives {
+    ( $($p:ident),+ ) => {
+        impl<'a, I, T, E, $($p),+> Alternatives<'a, I, T, E> for ($($p,)+)
+          where I: 'a + Copy,
+                T: 'a,
+                E: 'a,
+                $($p: FnOnce(Input<'a, I>) -> ParseResult<'a, I, T, E>),+ {
+            #[inline]
+            #[allow(non_snake_case)]
+            fn parse_longest(self, i: Input<'a, I>) -> ParseResult<'a, I, T, E> {
+                let ($($p,)+) = self;
+
+                impl_alternatives!(@OR(i) $($p),+)
+            }
+        }
+    };
+    ( @OR($input:expr) $p:ident ) => {
+        $p($input)
+    };
+    ( @OR($input:expr) $p:ident, $($tail:ident),+ ) => {
+        or_longest($input, $p, |i| impl_alternatives!(@OR(i) $($tail),+))
+    };
 }
 
-/// Applies the parser `F` without consuming any input.
+impl_alternatives!(A);
+impl_alternatives!(A, B);
+impl_alternatives!(A, B, C);
+impl_alternatives!(A, B, C, D);
+impl_alternatives!(A, B, C, D, F);
+impl_alternatives!(A, B, C, D, F, G);
+impl_alternatives!(A, B, C, D, F, G, H);
+impl_alternatives!(A, B, C, D, F, G, H, J);
+
+/// Applies all parsers in the tuple `a` to the same input, yielding the result of the one which
+/// consumed the most input. The earliest parser in the tuple wins if multiple parsers consumed
+/// the same amount of input.
+///
+/// This is the n-ary version of `or_longest`. All the parsers must share the same value and error
+/// type.
 ///
 /// ```
-/// use chomp::{parse_only, take};
-/// use chomp::combinators::look_ahead;
+/// use chomp::{parse_only, string};
+/// use chomp::combinators::longest;
 ///
-/// let p = |i| look_ahead(i, |i| take(i, 4)).bind(|i, t| take(i, 7).map(|u| (t, u)));
+/// let p = |i| longest(i, (|i| string(i, b"<"), |i| string(i, b"<<"), |i| string(i, b"<=")));
 ///
-/// assert_eq!(parse_only(p, b"testing"), Ok((&b"test"[..], &b"testing"[..])));
+/// assert_eq!(parse_only(p, b"<<="), Ok(&b"<<"[..]));
+/// assert_eq!(parse_only(p, b"<=<"), Ok(&b"<="[..]));
+/// assert_eq!(parse_only(p, b"< <"), Ok(&b"<"[..]));
 /// ```
 #[inline]
-pub fn look_ahead<'a, I, T, E, F>(i: Input<'a, I>, f: F) -> ParseResult<'a, I, T, E>
-  where F: FnOnce(Input<'a, I>) -> ParseResult<'a, I, T, E> {
-    match f(i.clone()).into_inner() {
-        State::Data(_, t)    => i.ret(t),
-        State::Error(b, t)   => i.replace(b).err(t),
-        State::Incomplete(n) => i.incomplete(n),
-    }
+pub fn longest<'a, I, T, E, A>(i: Input<'a, I>, a: A) -> ParseResult<'a, I, T, E>
+  where I: 'a,
+        T: 'a,
+        E: 'a,
+        A: Alternatives<'a, I, T, E> {
+    a.parse_longest(i)
 }
 
 #[cfg(test)]
 mod test {
     use ParseResult;
     use primitives::State;
-    use primitives::input::{new, DEFAULT, END_OF_INPUT};
+    use primitives::input::{new, new_at, DEFAULT, END_OF_INPUT};
     use primitives::IntoInner;
     use super::*;
 
-    use parsers::{any, take, token, string};
+    use parsers::{any, take, take_while, token, string, Error};
 
     #[test]
     fn option_test() {
@@ -362,6 +1407,41 @@ mod test {
         assert_eq!(option(new(END_OF_INPUT, b"a"), |i| token(i, b' ').map_err(|_| "token_err"), b'-').into_inner(), State::Data(new(END_OF_INPUT, b"a"), b'-'));
     }
 
+    #[test]
+    fn optional_test() {
+        assert_eq!(optional(new(DEFAULT, b""), any).into_inner(), State::Incomplete(1));
+        assert_eq!(optional(new(DEFAULT, b"a"), any).into_inner(), State::Data(new(DEFAULT, b""), Some(b'a')));
+        assert_eq!(optional(new(DEFAULT, b"a"), |i| take(i, 2)).into_inner(), State::Incomplete(1));
+        assert_eq!(optional(new(DEFAULT, b"a"), |i| token(i, b' ').map_err(|_| "token_err")).into_inner(), State::Data(new(DEFAULT, b"a"), None));
+
+        assert_eq!(optional(new(END_OF_INPUT, b""), any).into_inner(), State::Data(new(END_OF_INPUT, b""), None));
+        assert_eq!(optional(new(END_OF_INPUT, b"a"), any).into_inner(), State::Data(new(END_OF_INPUT, b""), Some(b'a')));
+        assert_eq!(optional(new(END_OF_INPUT, b"a"), |i| take(i, 2)).into_inner(), State::Data(new(END_OF_INPUT, b"a"), None));
+        assert_eq!(optional(new(END_OF_INPUT, b"a"), |i| token(i, b' ').map_err(|_| "token_err")).into_inner(), State::Data(new(END_OF_INPUT, b"a"), None));
+    }
+
+    #[test]
+    fn option_or_else_test() {
+        assert_eq!(option_or_else(new(DEFAULT, b"abc"), any, |_| b'-').into_inner(), State::Data(new(DEFAULT, b"bc"), b'a'));
+        assert_eq!(option_or_else(new(DEFAULT, b""), any, |_| b'-').into_inner(), State::Incomplete(1));
+        assert_eq!(option_or_else(new(DEFAULT, b"a"), |i| take(i, 2).map(Ok), Err).into_inner(), State::Incomplete(1));
+        assert_eq!(option_or_else(new(DEFAULT, b"a"), |i| token(i, b' ').map(Ok), Err).into_inner(), State::Data(new(DEFAULT, b"a"), Err(Error::expected(b' '))));
+        assert_eq!(option_or_else(new(DEFAULT, b"ab"), |i| any(i).then(|i| i.err(Error::unexpected())).map(Ok), Err).into_inner(), State::Data(new(DEFAULT, b"ab"), Err::<u8, _>(Error::unexpected())));
+
+        assert_eq!(option_or_else(new(END_OF_INPUT, b""), any, |_| b'-').into_inner(), State::Data(new(END_OF_INPUT, b""), b'-'));
+        assert_eq!(option_or_else(new(END_OF_INPUT, b"a"), |i| take(i, 2).map(Ok), Err).into_inner(), State::Data(new(END_OF_INPUT, b"a"), Err(Error::unexpected())));
+        assert_eq!(option_or_else(new(END_OF_INPUT, b"a"), |i| token(i, b' ').map(Ok), Err).into_inner(), State::Data(new(END_OF_INPUT, b"a"), Err(Error::expected(b' '))));
+    }
+
+    #[test]
+    fn cond_test() {
+        assert_eq!(cond(new(DEFAULT, b"abc"), true, any).into_inner(), State::Data(new(DEFAULT, b"bc"), Some(b'a')));
+        assert_eq!(cond(new(DEFAULT, b"abc"), false, any).into_inner(), State::Data(new(DEFAULT, b"abc"), None));
+        assert_eq!(cond(new(DEFAULT, b""), true, any).into_inner(), State::Incomplete(1));
+        assert_eq!(cond(new(DEFAULT, b""), false, any).into_inner(), State::Data(new(DEFAULT, b""), None));
+        assert_eq!(cond(new(DEFAULT, b"abc"), true, |i| token(i, b'b')).into_inner(), State::Error(&b"abc"[..], Error::expected(b'b')));
+    }
+
     #[test]
     fn or_test() {
         assert_eq!(or(new(DEFAULT, b""), any, any).into_inner(), State::Incomplete(1));
@@ -381,6 +1461,77 @@ mod test {
         assert_eq!(or(new(END_OF_INPUT, b"c"), |i| token(i, b'a').map_err(|_| "a err"), |i| token(i, b'b').map_err(|_| "b err")).into_inner(), State::Error(b"c", "b err"));
     }
 
+    #[test]
+    fn or_merged_test() {
+        assert_eq!(or_merged(new(DEFAULT, b"abc"), |i| token(i, b'a'), |i| token(i, b'b')).into_inner(), State::Data(new(DEFAULT, b"bc"), b'a'));
+        assert_eq!(or_merged(new(DEFAULT, b"bbc"), |i| token(i, b'a'), |i| token(i, b'b')).into_inner(), State::Data(new(DEFAULT, b"bc"), b'b'));
+        assert_eq!(or_merged(new(DEFAULT, b"cbc"), |i| token(i, b'a'), |i| token(i, b'b')).into_inner(), State::Error(&b"cbc"[..], Error::expected(b'a').merge(Error::expected(b'b'))));
+        assert_eq!(or_merged(new(DEFAULT, b"acd"), |i| token(i, b'a').then(|i| token(i, b'b')), |i| token(i, b'c')).into_inner(), State::Error(&b"acd"[..], Error::expected(b'c')));
+        assert_eq!(or_merged(new(DEFAULT, b""), |i| token(i, b'a'), |i| token(i, b'b')).into_inner(), State::Incomplete(1));
+        assert_eq!(or_merged(new(DEFAULT, b"c"), |i| string(i, b"a"), |i| string(i, b"bb")).into_inner(), State::Incomplete(1));
+
+        assert_eq!(or_merged(new(END_OF_INPUT, b"a"), |i| string(i, b"ab"), |i| string(i, b"a")).into_inner(), State::Data(new(END_OF_INPUT, b""), &b"a"[..]));
+    }
+
+    #[test]
+    fn or_farthest_test() {
+        assert_eq!(or_farthest(new(DEFAULT, b"abc"), |i| token(i, b'a'), |i| token(i, b'b')).into_inner(), State::Data(new(DEFAULT, b"bc"), b'a'));
+        assert_eq!(or_farthest(new(DEFAULT, b"bbc"), |i| token(i, b'a'), |i| token(i, b'b')).into_inner(), State::Data(new(DEFAULT, b"bc"), b'b'));
+        assert_eq!(or_farthest(new(DEFAULT, b"cbc"), |i| token(i, b'a'), |i| token(i, b'b')).into_inner(), State::Error(&b"cbc"[..], Error::expected(b'a').merge(Error::expected(b'b'))));
+        assert_eq!(or_farthest(new(DEFAULT, b"acd"), |i| token(i, b'a').then(|i| token(i, b'b')), |i| token(i, b'c')).into_inner(), State::Error(&b"cd"[..], Error::expected(b'b')));
+        assert_eq!(or_farthest(new(DEFAULT, b"bcd"), |i| token(i, b'a'), |i| token(i, b'b').then(|i| token(i, b'd'))).into_inner(), State::Error(&b"cd"[..], Error::expected(b'd')));
+        assert_eq!(or_farthest(new(DEFAULT, b"a"), |i| string(i, b"ab"), |i| string(i, b"a")).into_inner(), State::Incomplete(1));
+        assert_eq!(or_farthest(new(DEFAULT, b"c"), |i| string(i, b"a"), |i| string(i, b"cd")).into_inner(), State::Incomplete(1));
+
+        assert_eq!(or_farthest(new(END_OF_INPUT, b"a"), |i| string(i, b"ab"), |i| string(i, b"a")).into_inner(), State::Data(new(END_OF_INPUT, b""), &b"a"[..]));
+    }
+
+    #[test]
+    #[cfg(not(feature="noop_error"))]
+    fn or_merged_expected_tokens() {
+        let r = or_merged(new(DEFAULT, b"d"), |i| token(i, b'a'), |i| or_merged(i, |i| token(i, b'b'), |i| token(i, b'c')));
+
+        match r.into_inner() {
+            State::Error(b, e) => {
+                assert_eq!(b, &b"d"[..]);
+                assert_eq!(e.expected_tokens(), &[b'a', b'b', b'c'][..]);
+                assert_eq!(e.expected_token(), None);
+            },
+            s => panic!("Expected error, got {:?}", s),
+        }
+    }
+
+    #[test]
+    fn either_test() {
+        assert_eq!(either(new(DEFAULT, b"abc"), any, |i| string(i, b"ab")).into_inner(), State::Data(new(DEFAULT, b"bc"), Either::Left(b'a')));
+        assert_eq!(either(new(DEFAULT, b"abc"), |i| token(i, b'b'), |i| string(i, b"ab")).into_inner(), State::Data(new(DEFAULT, b"c"), Either::Right(&b"ab"[..])));
+        assert_eq!(either(new(DEFAULT, b"abc"), |i| token(i, b'b'), |i| token(i, b'c')).into_inner(), State::Error(&b"abc"[..], Error::expected(b'c')));
+        assert_eq!(either(new(DEFAULT, b""), any, any).into_inner(), State::Incomplete(1));
+        assert_eq!(either(new(END_OF_INPUT, b"a"), |i| string(i, b"ab"), any).into_inner(), State::Data(new(END_OF_INPUT, b""), Either::Right(b'a')));
+    }
+
+    #[test]
+    fn or_longest_test() {
+        assert_eq!(or_longest(new(DEFAULT, b"abc"), |i| string(i, b"a"), |i| string(i, b"ab")).into_inner(), State::Data(new(DEFAULT, b"c"), &b"ab"[..]));
+        assert_eq!(or_longest(new(DEFAULT, b"abc"), |i| string(i, b"ab"), |i| string(i, b"a")).into_inner(), State::Data(new(DEFAULT, b"c"), &b"ab"[..]));
+        assert_eq!(or_longest(new(DEFAULT, b"abc"), |i| any(i).map(|_| 1), |i| any(i).map(|_| 2)).into_inner(), State::Data(new(DEFAULT, b"bc"), 1));
+        assert_eq!(or_longest(new(DEFAULT, b"abc"), |i| string(i, b"b"), |i| string(i, b"a")).into_inner(), State::Data(new(DEFAULT, b"bc"), &b"a"[..]));
+        assert_eq!(or_longest(new(DEFAULT, b"abc"), |i| string(i, b"a"), |i| string(i, b"b")).into_inner(), State::Data(new(DEFAULT, b"bc"), &b"a"[..]));
+        assert_eq!(or_longest(new(DEFAULT, b"abc"), |i| string(i, b"b").map_err(|_| "a err"), |i| string(i, b"ac").map_err(|_| "b err")).into_inner(), State::Error(&b"bc"[..], "b err"));
+        assert_eq!(or_longest(new(DEFAULT, b"ab"), |i| string(i, b"a"), |i| string(i, b"abc")).into_inner(), State::Incomplete(1));
+
+        assert_eq!(or_longest(new(END_OF_INPUT, b"ab"), |i| string(i, b"a"), |i| string(i, b"abc")).into_inner(), State::Data(new(END_OF_INPUT, b"b"), &b"a"[..]));
+        assert_eq!(or_longest(new(END_OF_INPUT, b"ab"), |i| string(i, b"abc"), |i| string(i, b"a")).into_inner(), State::Data(new(END_OF_INPUT, b"b"), &b"a"[..]));
+        assert_eq!(or_longest(new(END_OF_INPUT, b"ab"), |i| string(i, b"abc"), |i| string(i, b"abd")).into_inner(), State::Incomplete(1));
+    }
+
+    #[test]
+    fn longest_test() {
+        assert_eq!(longest(new(DEFAULT, b"abc"), (any,)).into_inner(), State::Data(new(DEFAULT, b"bc"), b'a'));
+        assert_eq!(longest(new(DEFAULT, b"abcd"), (|i| string(i, b"a"), |i| string(i, b"abc"), |i| string(i, b"ab"))).into_inner(), State::Data(new(DEFAULT, b"d"), &b"abc"[..]));
+        assert_eq!(longest(new(DEFAULT, b"abcd"), (|i| string(i, b"b"), |i| string(i, b"c"), |i| string(i, b"ab"))).into_inner(), State::Data(new(DEFAULT, b"cd"), &b"ab"[..]));
+    }
+
     #[test]
     fn many_test() {
         let r: State<_, Vec<_>, _> = many(new(DEFAULT, b""), |i| token(i, b'a')).into_inner();
@@ -460,6 +1611,40 @@ mod test {
         assert_eq!(r, State::Data(new(END_OF_INPUT, b"a"), vec![b'a', b'a', b'a']));
     }
 
+    #[test]
+    fn skip_count_test() {
+        assert_eq!(skip_count(new(DEFAULT, b""), 3, |i| token(i, b'a')).into_inner(), State::Incomplete(1));
+        assert_eq!(skip_count(new(DEFAULT, b"aa"), 3, |i| token(i, b'a')).into_inner(), State::Incomplete(1));
+        assert_eq!(skip_count(new(DEFAULT, b"aaa"), 3, |i| token(i, b'a')).into_inner(), State::Data(new(DEFAULT, b""), ()));
+        assert_eq!(skip_count(new(DEFAULT, b"aaaa"), 3, |i| token(i, b'a')).into_inner(), State::Data(new(DEFAULT, b"a"), ()));
+        assert_eq!(skip_count(new(DEFAULT, b"aab"), 3, |i| token(i, b'a')).into_inner(), State::Error(&b"b"[..], Error::expected(b'a')));
+
+        assert_eq!(skip_count(new(END_OF_INPUT, b"aa"), 3, |i| token(i, b'a')).into_inner(), State::Incomplete(1));
+        assert_eq!(skip_count(new(END_OF_INPUT, b"aaaa"), 3, |i| token(i, b'a')).into_inner(), State::Data(new(END_OF_INPUT, b"a"), ()));
+        assert_eq!(skip_count(new(END_OF_INPUT, b"a"), 0, |i| token(i, b'a')).into_inner(), State::Data(new(END_OF_INPUT, b"a"), ()));
+    }
+
+    #[test]
+    fn count_fixed_test() {
+        let r: State<_, [u8; 3], _> = count_fixed(new(DEFAULT, b""), |i| token(i, b'a')).into_inner();
+        assert_eq!(r, State::Incomplete(1));
+        let r: State<_, [u8; 3], _> = count_fixed(new(DEFAULT, b"aa"), |i| token(i, b'a')).into_inner();
+        assert_eq!(r, State::Incomplete(1));
+        let r: State<_, [u8; 3], _> = count_fixed(new(DEFAULT, b"aaa"), |i| token(i, b'a')).into_inner();
+        assert_eq!(r, State::Data(new(DEFAULT, b""), [b'a', b'a', b'a']));
+        let r: State<_, [u8; 3], _> = count_fixed(new(DEFAULT, b"aaaa"), |i| token(i, b'a')).into_inner();
+        assert_eq!(r, State::Data(new(DEFAULT, b"a"), [b'a', b'a', b'a']));
+        let r: State<_, [u8; 3], _> = count_fixed(new(DEFAULT, b"aab"), |i| token(i, b'a')).into_inner();
+        assert_eq!(r, State::Error(b"b", Error::expected(b'a')));
+
+        let r: State<_, [u8; 3], _> = count_fixed(new(END_OF_INPUT, b"aa"), |i| token(i, b'a')).into_inner();
+        assert_eq!(r, State::Incomplete(1));
+        let r: State<_, [u8; 3], _> = count_fixed(new(END_OF_INPUT, b"aaaa"), |i| token(i, b'a')).into_inner();
+        assert_eq!(r, State::Data(new(END_OF_INPUT, b"a"), [b'a', b'a', b'a']));
+        let r: State<_, [u8; 0], Error<u8>> = count_fixed(new(END_OF_INPUT, b"a"), |i| token(i, b'a')).into_inner();
+        assert_eq!(r, State::Data(new(END_OF_INPUT, b"a"), []));
+    }
+
     #[test]
     fn skip_many1_test() {
         assert_eq!(skip_many1(new(DEFAULT, b"aabc"), |i| token(i, b'a')).into_inner(), State::Data(new(DEFAULT, b"bc"), ()));
@@ -487,6 +1672,19 @@ mod test {
         assert_eq!(r.into_inner(), State::Error(b"bcd", "the error"));
     }
 
+    #[test]
+    fn many_till_with_end_test() {
+        let r: ParseResult<_, (Vec<_>, _), _> = many_till_with_end(new(DEFAULT, b"abcd"), any, |i| token(i, b'c'));
+        assert_eq!(r.into_inner(), State::Data(new(DEFAULT, b"d"), (vec![b'a', b'b'], b'c')));
+        let r: ParseResult<_, (Vec<_>, _), _> = many_till_with_end(new(DEFAULT, b"cd"), any, |i| token(i, b'c'));
+        assert_eq!(r.into_inner(), State::Data(new(DEFAULT, b"d"), (vec![], b'c')));
+        let r: ParseResult<_, (Vec<_>, _), _> = many_till_with_end(new(DEFAULT, b"abd"), any, |i| token(i, b'c'));
+        assert_eq!(r.into_inner(), State::Incomplete(1));
+
+        let r: ParseResult<_, (Vec<u8>, _), _> = many_till_with_end(new(DEFAULT, b"abcd"), |i| i.err(()), |i| token(i, b'c'));
+        assert_eq!(r.into_inner(), State::Error(b"abcd", ()));
+    }
+
     #[test]
     fn matched_by_test() {
         assert_eq!(matched_by(new(DEFAULT, b"abc"), any).into_inner(), State::Data(new(DEFAULT, b"bc"), (&b"a"[..], b'a')));
@@ -495,6 +1693,51 @@ mod test {
         assert_eq!(matched_by(new(DEFAULT, b""), any).into_inner(), State::Incomplete(1));
     }
 
+    #[test]
+    fn recognize_test() {
+        assert_eq!(recognize(new(DEFAULT, b"abc"), any).into_inner(), State::Data(new(DEFAULT, b"bc"), &b"a"[..]));
+        assert_eq!(recognize(new(DEFAULT, b"abc"), |i| i.err::<(), _>("my error")).into_inner(), State::Error(&b"abc"[..], "my error"));
+        assert_eq!(recognize(new(DEFAULT, b"abc"), |i| any(i).map_err(|_| "any error").then(|i| i.err::<(), _>("my error"))).into_inner(), State::Error(&b"bc"[..], "my error"));
+        assert_eq!(recognize(new(DEFAULT, b""), any).into_inner(), State::Incomplete(1));
+    }
+
+    #[test]
+    fn iterate_test() {
+        let p = |i, n: u32| any(i).map(|c| if c == b';' { Step::Break(n) } else { Step::Continue(n + 1) });
+
+        assert_eq!(iterate(new(DEFAULT, b"abc;d"), 0, p).into_inner(), State::Data(new(DEFAULT, b"d"), 3));
+        assert_eq!(iterate(new(DEFAULT, b";d"), 0, p).into_inner(), State::Data(new(DEFAULT, b"d"), 0));
+        assert_eq!(iterate(new(DEFAULT, b"abc"), 0, p).into_inner(), State::Incomplete(1));
+        assert_eq!(iterate(new(END_OF_INPUT, b"abc"), 0, p).into_inner(), State::Incomplete(1));
+
+        let p = |i, n: u32| token(i, b'a').map(|_| if n == 2 { Step::Break(n) } else { Step::Continue(n + 1) });
+
+        assert_eq!(iterate(new(DEFAULT, b"aaab"), 0, p).into_inner(), State::Data(new(DEFAULT, b"b"), 2));
+        assert_eq!(iterate(new(DEFAULT, b"aba"), 0, p).into_inner(), State::Error(&b"ba"[..], Error::expected(b'a')));
+    }
+
+    #[test]
+    fn parse_iter_test() {
+        let mut iter = parse_iter(new(DEFAULT, b"aab"), |i| token(i, b'a'));
+        assert_eq!(iter.next(), Some(b'a'));
+        assert_eq!(iter.next(), Some(b'a'));
+        assert_eq!(iter.next(), None);
+        assert_eq!(iter.next(), None);
+        assert_eq!(iter.finish().into_inner(), State::Data(new(DEFAULT, b"b"), ()));
+
+        let mut iter = parse_iter(new(DEFAULT, b"aab"), |i| token(i, b'a'));
+        assert_eq!(iter.next(), Some(b'a'));
+        assert_eq!(iter.finish().into_inner(), State::Data(new(DEFAULT, b"ab"), ()));
+
+        let mut iter = parse_iter(new(DEFAULT, b"aa"), |i| token(i, b'a'));
+        assert_eq!(iter.by_ref().collect::<Vec<_>>(), vec![b'a', b'a']);
+        assert_eq!(iter.finish().into_inner(), State::Incomplete(1));
+
+        let mut iter = parse_iter(new(END_OF_INPUT, b"aa"), |i| token(i, b'a'));
+        assert_eq!(iter.by_ref().collect::<Vec<_>>(), vec![b'a', b'a']);
+        assert_eq!(iter.finish().into_inner(), State::Data(new(END_OF_INPUT, b""), ()));
+    }
+
     #[test]
     fn sep_by_test() {
         assert_eq!(sep_by(new(END_OF_INPUT, b""), any, |i| token(i, b';')).into_inner(), State::Data(new(END_OF_INPUT, b""), vec![]));
@@ -574,10 +1817,231 @@ mod test {
         assert_eq!(r.into_inner(), State::Incomplete(2));
     }
 
+    #[test]
+    fn sep_by_with_seps_test() {
+        let sep = |i| or(i, |i| token(i, b';'), |i| token(i, b','));
+
+        assert_eq!(sep_by_with_seps(new(END_OF_INPUT, b""), any, sep).into_inner(), State::Data(new(END_OF_INPUT, b""), (vec![], vec![])));
+        assert_eq!(sep_by_with_seps(new(END_OF_INPUT, b"a"), any, sep).into_inner(), State::Data(new(END_OF_INPUT, b""), (vec![b'a'], vec![])));
+        assert_eq!(sep_by_with_seps(new(END_OF_INPUT, b"a;b,c"), any, sep).into_inner(), State::Data(new(END_OF_INPUT, b""), (vec![b'a', b'b', b'c'], vec![b';', b','])));
+        assert_eq!(sep_by_with_seps(new(END_OF_INPUT, b"a;b,"), any, sep).into_inner(), State::Data(new(END_OF_INPUT, b","), (vec![b'a', b'b'], vec![b';'])));
+        assert_eq!(sep_by_with_seps(new(END_OF_INPUT, b"ab"), any, sep).into_inner(), State::Data(new(END_OF_INPUT, b"b"), (vec![b'a'], vec![])));
+
+        assert_eq!(sep_by_with_seps(new(DEFAULT, b"a;b,"), any, sep).into_inner(), State::Incomplete(1));
+        assert_eq!(sep_by_with_seps(new(DEFAULT, b"a;bc"), any, sep).into_inner(), State::Data(new(DEFAULT, b"c"), (vec![b'a', b'b'], vec![b';'])));
+    }
+
+    #[test]
+    fn end_by_test() {
+        let r: ParseResult<_, Vec<_>, _> = end_by(new(END_OF_INPUT, b""), any, |i| token(i, b';'));
+        assert_eq!(r.into_inner(), State::Data(new(END_OF_INPUT, b""), vec![]));
+        let r: ParseResult<_, Vec<_>, _> = end_by(new(END_OF_INPUT, b"a"), any, |i| token(i, b';'));
+        assert_eq!(r.into_inner(), State::Data(new(END_OF_INPUT, b"a"), vec![]));
+        let r: ParseResult<_, Vec<_>, _> = end_by(new(END_OF_INPUT, b"a;c;"), any, |i| token(i, b';'));
+        assert_eq!(r.into_inner(), State::Data(new(END_OF_INPUT, b""), vec![b'a', b'c']));
+        let r: ParseResult<_, Vec<_>, _> = end_by(new(END_OF_INPUT, b"a;c"), any, |i| token(i, b';'));
+        assert_eq!(r.into_inner(), State::Data(new(END_OF_INPUT, b"c"), vec![b'a']));
+        let r: ParseResult<_, Vec<_>, _> = end_by(new(END_OF_INPUT, b"a--c--d"), any, |i| string(i, b"--"));
+        assert_eq!(r.into_inner(), State::Data(new(END_OF_INPUT, b"d"), vec![b'a', b'c']));
+
+        let r: ParseResult<_, Vec<_>, _> = end_by(new(DEFAULT, b"a;c;"), any, |i| token(i, b';'));
+        assert_eq!(r.into_inner(), State::Incomplete(1));
+        let r: ParseResult<_, Vec<_>, _> = end_by(new(DEFAULT, b"a;cd"), any, |i| token(i, b';'));
+        assert_eq!(r.into_inner(), State::Data(new(DEFAULT, b"cd"), vec![b'a']));
+    }
+
+    #[test]
+    fn sep_end_by_test() {
+        let r: ParseResult<_, Vec<_>, _> = sep_end_by(new(END_OF_INPUT, b""), any, |i| token(i, b';'));
+        assert_eq!(r.into_inner(), State::Data(new(END_OF_INPUT, b""), vec![]));
+        let r: ParseResult<_, Vec<_>, _> = sep_end_by(new(END_OF_INPUT, b";"), |i| token(i, b'a'), |i| token(i, b';'));
+        assert_eq!(r.into_inner(), State::Data(new(END_OF_INPUT, b";"), vec![]));
+        let r: ParseResult<_, Vec<_>, _> = sep_end_by(new(END_OF_INPUT, b"a"), any, |i| token(i, b';'));
+        assert_eq!(r.into_inner(), State::Data(new(END_OF_INPUT, b""), vec![b'a']));
+        let r: ParseResult<_, Vec<_>, _> = sep_end_by(new(END_OF_INPUT, b"a;c"), any, |i| token(i, b';'));
+        assert_eq!(r.into_inner(), State::Data(new(END_OF_INPUT, b""), vec![b'a', b'c']));
+        let r: ParseResult<_, Vec<_>, _> = sep_end_by(new(END_OF_INPUT, b"a;c;"), any, |i| token(i, b';'));
+        assert_eq!(r.into_inner(), State::Data(new(END_OF_INPUT, b""), vec![b'a', b'c']));
+        let r: ParseResult<_, Vec<_>, _> = sep_end_by(new(END_OF_INPUT, b"a;c;;"), any, |i| token(i, b';'));
+        assert_eq!(r.into_inner(), State::Data(new(END_OF_INPUT, b""), vec![b'a', b'c', b';']));
+        let r: ParseResult<_, Vec<_>, _> = sep_end_by(new(END_OF_INPUT, b"a--c-"), any, |i| string(i, b"--"));
+        assert_eq!(r.into_inner(), State::Data(new(END_OF_INPUT, b"-"), vec![b'a', b'c']));
+
+        let r: ParseResult<_, Vec<_>, _> = sep_end_by(new(DEFAULT, b"a;c;"), any, |i| token(i, b';'));
+        assert_eq!(r.into_inner(), State::Incomplete(1));
+        let r: ParseResult<_, Vec<_>, _> = sep_end_by(new(DEFAULT, b"a;bc"), any, |i| token(i, b';'));
+        assert_eq!(r.into_inner(), State::Data(new(DEFAULT, b"c"), vec![b'a', b'b']));
+    }
+
+    #[test]
+    fn seq_test() {
+        assert_eq!(seq(new(DEFAULT, b"abc"), (any,)).into_inner(), State::Data(new(DEFAULT, b"bc"), (b'a',)));
+        assert_eq!(seq(new(DEFAULT, b"abc"), (any, |i| take(i, 2))).into_inner(), State::Data(new(DEFAULT, b""), (b'a', &b"bc"[..])));
+        assert_eq!(seq(new(DEFAULT, b"abc"), (any, |i| token(i, b'b'), |i| string(i, b"c"))).into_inner(), State::Data(new(DEFAULT, b""), (b'a', b'b', &b"c"[..])));
+        assert_eq!(seq(new(DEFAULT, b"abc"), (any, |i| token(i, b'c'), any)).into_inner(), State::Error(&b"bc"[..], Error::expected(b'c')));
+        assert_eq!(seq(new(DEFAULT, b"ab"), (any, |i| take(i, 2))).into_inner(), State::Incomplete(1));
+        assert_eq!(seq(new(END_OF_INPUT, b"abcdefgh"), (any, any, any, any, any, any, any, any)).into_inner(), State::Data(new(END_OF_INPUT, b""), (b'a', b'b', b'c', b'd', b'e', b'f', b'g', b'h')));
+    }
+
     #[test]
     fn look_ahead_test() {
         assert_eq!(look_ahead(new(DEFAULT, b"abc"), any).into_inner(), State::Data(new(DEFAULT, b"abc"), b'a'));
         assert_eq!(look_ahead(new(DEFAULT, b"a"), |i| string(i, b"abc")).into_inner(), State::Incomplete(2));
         assert_eq!(look_ahead(new(DEFAULT, b"aa"), |i| token(i, b'a').then(|i| token(i, b'b')).map_err(|_| "err")).into_inner(), State::Error(b"a", "err"));
     }
+
+    #[test]
+    fn attempt_test() {
+        assert_eq!(attempt(new(DEFAULT, b"abc"), any).into_inner(), State::Data(new(DEFAULT, b"bc"), b'a'));
+        assert_eq!(attempt(new(DEFAULT, b"a"), |i| string(i, b"abc")).into_inner(), State::Incomplete(2));
+        assert_eq!(attempt(new(DEFAULT, b"abd"), |i| string(i, b"abc").map_err(|_| "err")).into_inner(), State::Error(&b"abd"[..], "err"));
+        assert_eq!(attempt(new(END_OF_INPUT, b"abd"), |i| string(i, b"abc").map_err(|_| "err")).into_inner(), State::Error(&b"abd"[..], "err"));
+    }
+
+    #[test]
+    fn context_test() {
+        assert_eq!(context(new(DEFAULT, b"abc"), "a", any).into_inner(), State::Data(new(DEFAULT, b"bc"), b'a'));
+        assert_eq!(context(new(DEFAULT, b""), "a", any).into_inner(), State::Incomplete(1));
+        assert_eq!(context(new(DEFAULT, b"abc"), "b", |i| token(i, b'b')).into_inner(), State::Error(&b"abc"[..], Error::expected(b'b').add_context("b")));
+    }
+
+    #[test]
+    fn custom_error_test() {
+        use parsers::ParseErrorKind;
+
+        #[derive(Debug, Eq, PartialEq)]
+        struct MyError {
+            expected: Vec<u8>,
+            context:  Vec<&'static str>,
+            offset:   Option<usize>,
+        }
+
+        impl ParseErrorKind<u8> for MyError {
+            fn expected(t: u8) -> Self {
+                MyError { expected: vec![t], context: vec![], offset: None }
+            }
+
+            fn unexpected() -> Self {
+                MyError { expected: vec![], context: vec![], offset: None }
+            }
+
+            fn merge(mut self, other: Self) -> Self {
+                self.expected.extend(other.expected);
+
+                self
+            }
+
+            fn add_context(mut self, label: &'static str) -> Self {
+                self.context.push(label);
+
+                self
+            }
+
+            fn at_position(mut self, offset: usize) -> Self {
+                self.offset = Some(offset);
+
+                self
+            }
+        }
+
+        let t = |i, c| token(i, c).map_err(|_| MyError::expected(c));
+        let r = context(new_at(DEFAULT, 10, b"abc"), "ab", |i| t(i, b'a').then(|i| or_merged(i, |i| t(i, b'c'), |i| t(i, b'd'))));
+
+        assert_eq!(r.into_inner(), State::Error(&b"bc"[..], MyError { expected: vec![b'c', b'd'], context: vec!["ab"], offset: Some(11) }));
+
+        let r = length_value(new(DEFAULT, b"\x02abc"), |i| any(i).map(|n| n as usize).map_err(|_| MyError::unexpected()), |i| t(i, b'a'));
+
+        assert_eq!(r.into_inner(), State::Error(&b"bc"[..], MyError::unexpected()));
+    }
+
+    #[test]
+    #[cfg(not(feature="noop_error"))]
+    fn context_stack() {
+        let r = context(new(DEFAULT, b"abc"), "outer", |i| any(i).then(|i| context(i, "inner", |i| token(i, b'c'))));
+
+        match r.into_inner() {
+            State::Error(b, e) => {
+                assert_eq!(b, &b"bc"[..]);
+                assert_eq!(e.context(), &["inner", "outer"][..]);
+                assert_eq!(e.expected_token(), Some(&b'c'));
+            },
+            s => panic!("Expected error, got {:?}", s),
+        }
+    }
+
+    #[test]
+    fn max_lookahead_test() {
+        let p = |i| take_while(i, |c| c != b' ');
+
+        assert_eq!(max_lookahead(new(DEFAULT, b"ab cd"), 3, p).into_inner(), State::Data(new(DEFAULT, b" cd"), &b"ab"[..]));
+        assert_eq!(max_lookahead(new(DEFAULT, b"abc d"), 3, p).into_inner(), State::Error(&b"abc d"[..], Error::unexpected()));
+        assert_eq!(max_lookahead(new(DEFAULT, b"ab"), 3, p).into_inner(), State::Incomplete(1));
+        assert_eq!(max_lookahead(new(DEFAULT, b"abc"), 3, p).into_inner(), State::Error(&b"abc"[..], Error::unexpected()));
+        assert_eq!(max_lookahead(new(DEFAULT, b"abcdef"), 3, |i| token(i, b'a').then(|i| token(i, b'c'))).into_inner(), State::Error(&b"bcdef"[..], Error::expected(b'c')));
+        assert_eq!(max_lookahead(new(DEFAULT, b"a"), 3, |i| take(i, 4)).into_inner(), State::Error(&b"a"[..], Error::unexpected()));
+        assert_eq!(max_lookahead(new(DEFAULT, b"a"), 3, |i| take(i, 3)).into_inner(), State::Incomplete(2));
+
+        assert_eq!(max_lookahead(new(END_OF_INPUT, b"abc"), 3, p).into_inner(), State::Data(new(END_OF_INPUT, b""), &b"abc"[..]));
+        assert_eq!(max_lookahead(new(END_OF_INPUT, b"abcd"), 3, p).into_inner(), State::Error(&b"abcd"[..], Error::unexpected()));
+        assert_eq!(max_lookahead(new(END_OF_INPUT, b"abcd"), 3, |i| take(i, 3)).into_inner(), State::Data(new(END_OF_INPUT, b"d"), &b"abc"[..]));
+    }
+
+    #[test]
+    fn length_value_test() {
+        let len = |i| any(i).map(|n| n as usize);
+        let p   = |i| take_while(i, |c| c != b'!');
+
+        assert_eq!(length_value(new(DEFAULT, b"\x02ab!"), len, p).into_inner(), State::Data(new(DEFAULT, b"!"), &b"ab"[..]));
+        assert_eq!(length_value(new(DEFAULT, b"\x00ab"), len, p).into_inner(), State::Data(new(DEFAULT, b"ab"), &b""[..]));
+        assert_eq!(length_value(new(DEFAULT, b"\x03a!b!"), len, p).into_inner(), State::Error(&b"!b!"[..], Error::unexpected()));
+        assert_eq!(length_value(new(DEFAULT, b"\x03abc"), len, |i| take(i, 4)).into_inner(), State::Error(&b""[..], Error::unexpected()));
+        assert_eq!(length_value(new(DEFAULT, b"\x03abcd"), len, |i| token(i, b'a').then(|i| token(i, b'c'))).into_inner(), State::Error(&b"bcd"[..], Error::expected(b'c')));
+        assert_eq!(length_value(new(DEFAULT, b"\x03ab"), len, p).into_inner(), State::Incomplete(1));
+        assert_eq!(length_value(new(END_OF_INPUT, b"\x05ab"), len, p).into_inner(), State::Incomplete(3));
+        assert_eq!(length_value(new(DEFAULT, b""), len, p).into_inner(), State::Incomplete(1));
+        assert_eq!(length_value(new(END_OF_INPUT, b"\x02ab"), len, p).into_inner(), State::Data(new(END_OF_INPUT, b""), &b"ab"[..]));
+
+        assert_eq!(length_data(new(DEFAULT, b"\x02abc"), len).into_inner(), State::Data(new(DEFAULT, b"c"), &b"ab"[..]));
+        assert_eq!(length_data(new(DEFAULT, b"\x00abc"), len).into_inner(), State::Data(new(DEFAULT, b"abc"), &b""[..]));
+        assert_eq!(length_data(new(DEFAULT, b"\x04abc"), len).into_inner(), State::Incomplete(1));
+    }
+
+    #[test]
+    fn recover_test() {
+        let p = |i| string(i, b"ab").map_err(|_| "err");
+        let s = |i| token(i, b';');
+
+        assert_eq!(recover(new(DEFAULT, b"ab;"), p, s).into_inner(), State::Data(new(DEFAULT, b";"), Ok(&b"ab"[..])));
+        assert_eq!(recover(new(DEFAULT, b"ac;d"), p, s).into_inner(), State::Data(new(DEFAULT, b"d"), Err("err")));
+        assert_eq!(recover(new(DEFAULT, b"c;d;"), p, s).into_inner(), State::Data(new(DEFAULT, b"d;"), Err("err")));
+        assert_eq!(recover(new(DEFAULT, b"a"), p, s).into_inner(), State::Incomplete(1));
+        assert_eq!(recover(new(DEFAULT, b"acd"), p, s).into_inner(), State::Incomplete(1));
+
+        assert_eq!(recover(new(END_OF_INPUT, b"ac;d"), p, s).into_inner(), State::Data(new(END_OF_INPUT, b"d"), Err("err")));
+        assert_eq!(recover(new(END_OF_INPUT, b"acd"), p, s).into_inner(), State::Data(new(END_OF_INPUT, b""), Err("err")));
+
+        // Synchronization matching without consuming anything still makes progress
+        let p = |i| token(i, b'a').map_err(|_| "err");
+        let s = |i| option(i, |i| token(i, b';'), b';');
+
+        assert_eq!(recover(new(END_OF_INPUT, b"b;c"), p, s).into_inner(), State::Data(new(END_OF_INPUT, b"c"), Err("err")));
+        assert_eq!(recover(new(END_OF_INPUT, b"bc"), p, s).into_inner(), State::Data(new(END_OF_INPUT, b"c"), Err("err")));
+        assert_eq!(recover(new(END_OF_INPUT, b"b"), p, s).into_inner(), State::Data(new(END_OF_INPUT, b""), Err("err")));
+        assert_eq!(recover(new(END_OF_INPUT, b""), |i| i.err::<u8, _>("err"), s).into_inner(), State::Error(&b""[..], Error::unexpected()));
+
+        let r: State<_, Vec<Result<u8, &str>>, _> = many(new(END_OF_INPUT, b"ab"), |i| recover(i, p, s)).into_inner();
+        assert_eq!(r, State::Data(new(END_OF_INPUT, b""), vec![Ok(b'a'), Err("err")]));
+    }
+
+    #[test]
+    fn not_followed_by_test() {
+        assert_eq!(not_followed_by(new(DEFAULT, b"abc"), |i| token(i, b'b')).into_inner(), State::Data(new(DEFAULT, b"abc"), ()));
+        assert_eq!(not_followed_by(new(DEFAULT, b"abc"), |i| token(i, b'a')).into_inner(), State::Error(&b"abc"[..], Error::unexpected()));
+        assert_eq!(not_followed_by(new(DEFAULT, b"a"), |i| string(i, b"abc")).into_inner(), State::Incomplete(2));
+        assert_eq!(not_followed_by(new(DEFAULT, b""), any).into_inner(), State::Incomplete(1));
+
+        assert_eq!(not_followed_by(new(END_OF_INPUT, b"a"), |i| string(i, b"abc")).into_inner(), State::Data(new(END_OF_INPUT, b"a"), ()));
+        assert_eq!(not_followed_by(new(END_OF_INPUT, b""), any).into_inner(), State::Data(new(END_OF_INPUT, b""), ()));
+        assert_eq!(not_followed_by(new(END_OF_INPUT, b"abc"), |i| string(i, b"ab")).into_inner(), State::Error(&b"abc"[..], Error::unexpected()));
+    }
 }
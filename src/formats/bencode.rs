@@ -0,0 +1,216 @@
+//! Parser for bencode, the encoding used by BitTorrent metainfo files and tracker responses.
+//!
+//! # Example
+//!
+//! ```
+//! use chomp::parse_only;
+//! use chomp::formats::bencode::{value, BValue};
+//!
+//! let r = parse_only(value, b"d4:name5:chomp6:piecesli1ei-2eee").unwrap();
+//!
+//! assert_eq!(r.get(b"name"), Some(&BValue::Bytes(b"chomp")));
+//! assert_eq!(r.get(b"pieces"), Some(&BValue::List(vec![BValue::Int(1), BValue::Int(-2)])));
+//! ```
+
+use std::collections::BTreeMap;
+use std::str;
+
+use {Input, U8Result};
+use primitives::InputBuffer;
+use ascii::is_digit;
+use combinators::{many_till, option, recognize};
+use parsers::{Error, take, take_while1, token};
+
+/// Maximum nesting depth of lists and dictionaries parsed by `value`, a list or dictionary nested
+/// deeper results in an unexpected error.
+pub const MAX_DEPTH: usize = 100;
+
+/// A bencoded value.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum BValue<'a> {
+    /// Integer, `i<number>e`.
+    Int(i64),
+    /// Byte string, `<length>:<bytes>`.
+    Bytes(&'a [u8]),
+    /// List of values, `l<values>e`.
+    List(Vec<BValue<'a>>),
+    /// Dictionary of byte string keys and values, `d<key-value pairs>e`.
+    Dict(BTreeMap<&'a [u8], BValue<'a>>),
+}
+
+impl<'a> BValue<'a> {
+    /// Returns the value of `key` if this value is a dictionary containing it.
+    #[inline]
+    pub fn get(&self, key: &[u8]) -> Option<&BValue<'a>> {
+        match *self {
+            BValue::Dict(ref d) => d.get(key),
+            _                   => None,
+        }
+    }
+}
+
+/// Parses any bencoded value.
+///
+/// If a dictionary contains the same key multiple times the last value is kept. Lists and
+/// dictionaries nested more than `MAX_DEPTH` levels deep result in an unexpected error at the
+/// start of the first list or dictionary which is too deep.
+#[inline]
+pub fn value(i: Input<u8>) -> U8Result<BValue> {
+    nested_value(i, MAX_DEPTH)
+}
+
+/// Internal parser for a value which may contain at most `depth` levels of lists and
+/// dictionaries.
+fn nested_value(i: Input<u8>, depth: usize) -> U8Result<BValue> {
+    let b = i.buffer();
+
+    match b.first() {
+        Some(&b'i')              => integer(i).map(BValue::Int),
+        Some(&b'l') if depth > 0 => list(i, depth - 1).map(BValue::List),
+        Some(&b'd') if depth > 0 => dict(i, depth - 1).map(BValue::Dict),
+        Some(&c) if is_digit(c)  => bytes(i).map(BValue::Bytes),
+        Some(_)                  => i.err(Error::unexpected()),
+        None                     => i.incomplete(1),
+    }
+}
+
+/// Parses a bencoded integer.
+///
+/// Leading zeros and negative zero result in an unexpected error, as do values out of range for
+/// `i64`.
+///
+/// ```
+/// use chomp::parse_only;
+/// use chomp::formats::bencode::integer;
+///
+/// assert_eq!(parse_only(integer, b"i-42e"), Ok(-42));
+/// assert!(parse_only(integer, b"i042e").is_err());
+/// ```
+#[inline]
+pub fn integer(i: Input<u8>) -> U8Result<i64> {
+    token(i, b'i')
+        .then(|i| recognize(i, |i| option(i, |i| token(i, b'-').map(|_| ()), ()).then(|i| take_while1(i, is_digit))))
+        .bind(|i, b| match number(b) {
+            Some(n) if b != b"-0" => i.ret(n),
+            _                     => i.err(Error::unexpected()),
+        })
+        .skip(|i| token(i, b'e'))
+}
+
+/// Parses a bencoded byte string.
+///
+/// If the buffer is shorter than the length of the string this parser is considered incomplete.
+///
+/// ```
+/// use chomp::parse_only;
+/// use chomp::formats::bencode::bytes;
+///
+/// assert_eq!(parse_only(bytes, b"4:spam"), Ok(&b"spam"[..]));
+/// ```
+#[inline]
+pub fn bytes<'a>(i: Input<'a, u8>) -> U8Result<'a, &'a [u8]> {
+    take_while1(i, is_digit)
+        .bind(|i, b| match number(b) {
+            Some(n) if n as u64 <= usize::max_value() as u64 => i.ret(n as usize),
+            _                                                  => i.err(Error::unexpected()),
+        })
+        .skip(|i| token(i, b':'))
+        .bind(take)
+}
+
+/// Internal parser for the contents of a list, containing values with at most `depth` levels of
+/// nesting.
+#[inline]
+fn list(i: Input<u8>, depth: usize) -> U8Result<Vec<BValue>> {
+    token(i, b'l').then(|i| many_till(i, |i| nested_value(i, depth), |i| token(i, b'e')))
+}
+
+/// Internal parser for the contents of a dictionary, containing values with at most `depth`
+/// levels of nesting.
+#[inline]
+fn dict<'a>(i: Input<'a, u8>, depth: usize) -> U8Result<'a, BTreeMap<&'a [u8], BValue<'a>>> {
+    token(i, b'd').then(|i| many_till(i, |i| bytes(i).bind(|i, k| nested_value(i, depth).map(|v| (k, v))), |i| token(i, b'e')))
+}
+
+/// Internal function converting an optionally negative number without leading zeros to an `i64`.
+#[inline]
+fn number(b: &[u8]) -> Option<i64> {
+    let d = if b[0] == b'-' { &b[1..] } else { b };
+
+    if d.len() > 1 && d[0] == b'0' {
+        return None;
+    }
+
+    // Only ASCII digits and '-' have been matched
+    str::from_utf8(b).ok().and_then(|s| s.parse().ok())
+}
+
+#[cfg(test)]
+mod test {
+    use std::collections::BTreeMap;
+
+    use super::{value, integer, bytes, BValue, MAX_DEPTH};
+
+    use primitives::input::{new, DEFAULT, END_OF_INPUT};
+    use primitives::{IntoInner, State};
+    use parsers::Error;
+
+    #[test]
+    fn integer_test() {
+        assert_eq!(integer(new(END_OF_INPUT, b"i0e")).into_inner(), State::Data(new(END_OF_INPUT, b""), 0));
+        assert_eq!(integer(new(END_OF_INPUT, b"i123e!")).into_inner(), State::Data(new(END_OF_INPUT, b"!"), 123));
+        assert_eq!(integer(new(END_OF_INPUT, b"i-9223372036854775808e")).into_inner(), State::Data(new(END_OF_INPUT, b""), -9223372036854775808));
+        assert_eq!(integer(new(END_OF_INPUT, b"i9223372036854775808e")).into_inner(), State::Error(&b"e"[..], Error::unexpected()));
+        assert_eq!(integer(new(END_OF_INPUT, b"i-0e")).into_inner(), State::Error(&b"e"[..], Error::unexpected()));
+        assert_eq!(integer(new(END_OF_INPUT, b"i01e")).into_inner(), State::Error(&b"e"[..], Error::unexpected()));
+        assert_eq!(integer(new(END_OF_INPUT, b"ie")).into_inner(), State::Error(&b"e"[..], Error::unexpected()));
+        assert_eq!(integer(new(END_OF_INPUT, b"i1")).into_inner(), State::Incomplete(1));
+        assert_eq!(integer(new(DEFAULT, b"i12")).into_inner(), State::Incomplete(1));
+    }
+
+    #[test]
+    fn bytes_test() {
+        assert_eq!(bytes(new(END_OF_INPUT, b"0:")).into_inner(), State::Data(new(END_OF_INPUT, b""), &b""[..]));
+        assert_eq!(bytes(new(END_OF_INPUT, b"3:abcd")).into_inner(), State::Data(new(END_OF_INPUT, b"d"), &b"abc"[..]));
+        assert_eq!(bytes(new(END_OF_INPUT, b"03:abc")).into_inner(), State::Error(&b":abc"[..], Error::unexpected()));
+        assert_eq!(bytes(new(END_OF_INPUT, b"3abc")).into_inner(), State::Error(&b"abc"[..], Error::expected(b':')));
+        assert_eq!(bytes(new(DEFAULT, b"5:abc")).into_inner(), State::Incomplete(2));
+    }
+
+    #[test]
+    fn value_test() {
+        let mut d = BTreeMap::new();
+        d.insert(&b"a"[..], BValue::List(vec![BValue::Int(1), BValue::Bytes(b"x")]));
+        d.insert(&b"b"[..], BValue::Dict(BTreeMap::new()));
+
+        assert_eq!(value(new(END_OF_INPUT, b"d1:ali1e1:xe1:bdee!")).into_inner(), State::Data(new(END_OF_INPUT, b"!"), BValue::Dict(d)));
+        assert_eq!(value(new(END_OF_INPUT, b"le")).into_inner(), State::Data(new(END_OF_INPUT, b""), BValue::List(vec![])));
+        assert_eq!(value(new(END_OF_INPUT, b"lli1eee")).into_inner(), State::Data(new(END_OF_INPUT, b""), BValue::List(vec![BValue::List(vec![BValue::Int(1)])])));
+        assert_eq!(value(new(END_OF_INPUT, b"li1ex")).into_inner(), State::Error(&b"x"[..], Error::unexpected()));
+        assert_eq!(value(new(END_OF_INPUT, b"di1ei2ee")).into_inner(), State::Error(&b"i1ei2ee"[..], Error::unexpected()));
+        assert_eq!(value(new(END_OF_INPUT, b"x")).into_inner(), State::Error(&b"x"[..], Error::unexpected()));
+        assert_eq!(value(new(DEFAULT, b"li1e")).into_inner(), State::Incomplete(1));
+        assert_eq!(value(new(DEFAULT, b"")).into_inner(), State::Incomplete(1));
+    }
+
+    #[test]
+    fn value_depth() {
+        let nested = |depth: usize| {
+            let mut b = b"l".repeat(depth);
+            b.extend(b"e".repeat(depth));
+            b
+        };
+
+        let b = nested(MAX_DEPTH);
+        assert!(match value(new(END_OF_INPUT, &b)).into_inner() {
+            State::Data(r, BValue::List(_)) => r == new(END_OF_INPUT, b""),
+            _                               => false,
+        });
+
+        let b = nested(MAX_DEPTH + 1);
+        assert_eq!(value(new(END_OF_INPUT, &b)).into_inner(), State::Error(&b[MAX_DEPTH..], Error::unexpected()));
+
+        let b = b"d1:a".repeat(100000);
+        assert_eq!(value(new(END_OF_INPUT, &b)).into_inner(), State::Error(&b[4 * MAX_DEPTH..], Error::unexpected()));
+    }
+}
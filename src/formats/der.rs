@@ -0,0 +1,209 @@
+//! Primitives for parsing ASN.1 values encoded using the Distinguished Encoding Rules (DER), eg.
+//! X.509 certificates.
+//!
+//! Every DER value is encoded as a tag, a definite length and the contents, which are parsed
+//! using `tag`, `length` and `tlv` respectively.
+//!
+//! # Example
+//!
+//! ```
+//! use chomp::parse_only;
+//! use chomp::binary::be_u16;
+//! use chomp::formats::der::{tlv, Class, Tag};
+//!
+//! // SEQUENCE { INTEGER 0x1234 }
+//! let r = parse_only(|i| tlv(i, |i| tlv(i, be_u16)), b"\x30\x04\x02\x02\x12\x34");
+//!
+//! let sequence = Tag { class: Class::Universal, constructed: true, number: 16 };
+//! let integer  = Tag { class: Class::Universal, constructed: false, number: 2 };
+//!
+//! assert_eq!(r, Ok((sequence, (integer, 0x1234))));
+//! ```
+
+use {Input, U8Result};
+use primitives::InputBuffer;
+use combinators::length_value;
+use parsers::{Error, any, take};
+
+/// The class of a tag.
+#[derive(Copy, Clone, Debug, Eq, PartialEq, Hash)]
+pub enum Class {
+    /// Types defined by ASN.1 itself, eg. `INTEGER` or `SEQUENCE`.
+    Universal,
+    /// Types specific to an application.
+    Application,
+    /// Types specific to the enclosing structure, eg. `[0]`.
+    ContextSpecific,
+    /// Types specific to an organization.
+    Private,
+}
+
+/// The identifier of a DER value.
+#[derive(Copy, Clone, Debug, Eq, PartialEq, Hash)]
+pub struct Tag {
+    /// The class of the tag number.
+    pub class:       Class,
+    /// If the contents consist of nested values instead of a primitive value.
+    pub constructed: bool,
+    /// The tag number.
+    pub number:      u32,
+}
+
+/// Parses a tag, in either the low or high tag number form.
+///
+/// High tag numbers which are not minimally encoded or which do not fit in a `u32` result in an
+/// unexpected error at the start of the tag.
+///
+/// ```
+/// use chomp::parse_only;
+/// use chomp::formats::der::{tag, Class, Tag};
+///
+/// assert_eq!(parse_only(tag, b"\xa0"), Ok(Tag { class: Class::ContextSpecific, constructed: true, number: 0 }));
+/// ```
+pub fn tag(i: Input<u8>) -> U8Result<Tag> {
+    let b = i.buffer();
+
+    any(i).bind(|i, c| {
+        let class = match c >> 6 {
+            0 => Class::Universal,
+            1 => Class::Application,
+            2 => Class::ContextSpecific,
+            _ => Class::Private,
+        };
+
+        let t = Tag { class: class, constructed: c & 0x20 != 0, number: (c & 0x1f) as u32 };
+
+        if t.number != 0x1f {
+            return i.ret(t);
+        }
+
+        let d = i.buffer();
+
+        match d.iter().position(|&c| c & 0x80 == 0) {
+            // Subsequent bytes of the number in base 128, most significant first
+            Some(n) if d[0] != 0x80 && n < 5 => {
+                let v = d[..n + 1].iter().fold(0u64, |v, &c| v << 7 | (c & 0x7f) as u64);
+
+                if v < 0x1f || v > u32::max_value() as u64 {
+                    i.replace(b).err(Error::unexpected())
+                } else {
+                    i.replace(&d[n + 1..]).ret(Tag { number: v as u32, ..t })
+                }
+            },
+            Some(_)                          => i.replace(b).err(Error::unexpected()),
+            None if d.len() >= 5             => i.replace(b).err(Error::unexpected()),
+            None                             => i.incomplete(1),
+        }
+    })
+}
+
+/// Parses a definite length, in either the short or long form.
+///
+/// The indefinite length form, lengths which are not minimally encoded, and lengths which do not
+/// fit in a `usize` result in an unexpected error at the start of the length.
+///
+/// ```
+/// use chomp::parse_only;
+/// use chomp::formats::der::length;
+///
+/// assert_eq!(parse_only(length, b"\x7f"), Ok(127));
+/// assert_eq!(parse_only(length, b"\x82\x01\x00"), Ok(256));
+/// assert!(parse_only(length, b"\x81\x7f").is_err());
+/// ```
+pub fn length(i: Input<u8>) -> U8Result<usize> {
+    let b = i.buffer();
+
+    any(i).bind(|i, c| {
+        if c < 0x80 {
+            return i.ret(c as usize);
+        }
+
+        let n = (c & 0x7f) as usize;
+
+        if n == 0 || n > 8 {
+            return i.replace(b).err(Error::unexpected());
+        }
+
+        take(i, n).bind(|i, d| {
+            let v = d.iter().fold(0u64, |v, &c| v << 8 | c as u64);
+
+            if d[0] == 0 || v < 0x80 || v > usize::max_value() as u64 {
+                i.replace(b).err(Error::unexpected())
+            } else {
+                i.ret(v as usize)
+            }
+        })
+    })
+}
+
+/// Parses a tag and length, and then applies the parser `f` to exactly the contents of the value,
+/// yielding the tag and the result of `f`.
+///
+/// See `combinators::length_value` for how `f` is restricted to the contents.
+///
+/// ```
+/// use chomp::{parse_only, take_remainder};
+/// use chomp::formats::der::tlv;
+///
+/// // OCTET STRING
+/// let r = parse_only(|i| tlv(i, take_remainder).map(|(t, v)| (t.number, v)), b"\x04\x03abc");
+///
+/// assert_eq!(r, Ok((4, &b"abc"[..])));
+/// ```
+#[inline]
+pub fn tlv<'a, T, F>(i: Input<'a, u8>, f: F) -> U8Result<'a, (Tag, T)>
+  where T: 'a,
+        F: FnOnce(Input<'a, u8>) -> U8Result<'a, T> {
+    tag(i).bind(|i, t| length_value(i, length, f).map(|v| (t, v)))
+}
+
+#[cfg(test)]
+mod test {
+    use super::{tag, length, tlv, Class, Tag};
+
+    use primitives::input::{new, DEFAULT, END_OF_INPUT};
+    use primitives::{IntoInner, State};
+    use parsers::{Error, any, take_remainder};
+
+    #[test]
+    fn tag_test() {
+        let t = |class, constructed, number| Tag { class: class, constructed: constructed, number: number };
+
+        assert_eq!(tag(new(END_OF_INPUT, b"\x02!")).into_inner(), State::Data(new(END_OF_INPUT, b"!"), t(Class::Universal, false, 2)));
+        assert_eq!(tag(new(END_OF_INPUT, b"\x30")).into_inner(), State::Data(new(END_OF_INPUT, b""), t(Class::Universal, true, 16)));
+        assert_eq!(tag(new(END_OF_INPUT, b"\x5e")).into_inner(), State::Data(new(END_OF_INPUT, b""), t(Class::Application, false, 30)));
+        assert_eq!(tag(new(END_OF_INPUT, b"\xa3")).into_inner(), State::Data(new(END_OF_INPUT, b""), t(Class::ContextSpecific, true, 3)));
+        assert_eq!(tag(new(END_OF_INPUT, b"\xdf\x1f!")).into_inner(), State::Data(new(END_OF_INPUT, b"!"), t(Class::Private, false, 31)));
+        assert_eq!(tag(new(END_OF_INPUT, b"\x1f\x81\x00")).into_inner(), State::Data(new(END_OF_INPUT, b""), t(Class::Universal, false, 128)));
+        assert_eq!(tag(new(END_OF_INPUT, b"\x1f\x8f\xff\xff\xff\x7f")).into_inner(), State::Data(new(END_OF_INPUT, b""), t(Class::Universal, false, 0xffffffff)));
+
+        assert_eq!(tag(new(END_OF_INPUT, b"\x1f\x1e")).into_inner(), State::Error(&b"\x1f\x1e"[..], Error::unexpected()));
+        assert_eq!(tag(new(END_OF_INPUT, b"\x1f\x80\x7f")).into_inner(), State::Error(&b"\x1f\x80\x7f"[..], Error::unexpected()));
+        assert_eq!(tag(new(END_OF_INPUT, b"\x1f\x90\x80\x80\x80\x00")).into_inner(), State::Error(&b"\x1f\x90\x80\x80\x80\x00"[..], Error::unexpected()));
+        assert_eq!(tag(new(END_OF_INPUT, b"\x1f\x81\x81\x81\x81\x81")).into_inner(), State::Error(&b"\x1f\x81\x81\x81\x81\x81"[..], Error::unexpected()));
+        assert_eq!(tag(new(DEFAULT, b"\x1f\x81")).into_inner(), State::Incomplete(1));
+        assert_eq!(tag(new(DEFAULT, b"")).into_inner(), State::Incomplete(1));
+    }
+
+    #[test]
+    fn length_test() {
+        assert_eq!(length(new(END_OF_INPUT, b"\x00")).into_inner(), State::Data(new(END_OF_INPUT, b""), 0));
+        assert_eq!(length(new(END_OF_INPUT, b"\x81\x80!")).into_inner(), State::Data(new(END_OF_INPUT, b"!"), 128));
+        assert_eq!(length(new(END_OF_INPUT, b"\x83\x01\x00\x00")).into_inner(), State::Data(new(END_OF_INPUT, b""), 65536));
+        assert_eq!(length(new(END_OF_INPUT, b"\x80")).into_inner(), State::Error(&b"\x80"[..], Error::unexpected()));
+        assert_eq!(length(new(END_OF_INPUT, b"\xff")).into_inner(), State::Error(&b"\xff"[..], Error::unexpected()));
+        assert_eq!(length(new(END_OF_INPUT, b"\x82\x00\x80")).into_inner(), State::Error(&b"\x82\x00\x80"[..], Error::unexpected()));
+        assert_eq!(length(new(END_OF_INPUT, b"\x81\x05")).into_inner(), State::Error(&b"\x81\x05"[..], Error::unexpected()));
+        assert_eq!(length(new(DEFAULT, b"\x82\x01")).into_inner(), State::Incomplete(1));
+    }
+
+    #[test]
+    fn tlv_test() {
+        let octets = Tag { class: Class::Universal, constructed: false, number: 4 };
+
+        assert_eq!(tlv(new(END_OF_INPUT, b"\x04\x02ab!"), take_remainder).into_inner(), State::Data(new(END_OF_INPUT, b"!"), (octets, &b"ab"[..])));
+        assert_eq!(tlv(new(END_OF_INPUT, b"\x04\x00!"), take_remainder).into_inner(), State::Data(new(END_OF_INPUT, b"!"), (octets, &b""[..])));
+        assert_eq!(tlv(new(END_OF_INPUT, b"\x04\x02ab!"), any).into_inner(), State::Error(&b"b!"[..], Error::unexpected()));
+        assert_eq!(tlv(new(DEFAULT, b"\x04\x03ab"), take_remainder).into_inner(), State::Incomplete(1));
+    }
+}
@@ -0,0 +1,13 @@
+//! Parsers for common data formats built on top of the other parsers, each enabled by the feature
+//! of the same name.
+//!
+//! * `protobuf`: Protocol Buffers wire format primitives.
+//! * `bencode`: Bencode values, as used by BitTorrent.
+//! * `der`: ASN.1 DER tags, lengths and values.
+
+#[cfg(feature = "protobuf")]
+pub mod protobuf;
+#[cfg(feature = "bencode")]
+pub mod bencode;
+#[cfg(feature = "der")]
+pub mod der;
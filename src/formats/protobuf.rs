@@ -0,0 +1,262 @@
+//! Primitives for parsing the Protocol Buffers wire format.
+//!
+//! A message is a sequence of fields, each starting with a key containing the field number and
+//! the `WireType` of the payload which follows it. Known fields are parsed using the payload
+//! parsers `varint`, `fixed32`, `fixed64` and `length_delimited`, unknown fields are skipped using
+//! `skip_field`.
+//!
+//! # Example
+//!
+//! ```
+//! use chomp::parse_only;
+//! use chomp::combinators::many;
+//! use chomp::formats::protobuf::{field_key, length_delimited, skip_field, varint};
+//!
+//! // Collects field 1 (varint) and field 2 (string), skipping all other fields
+//! let r: Result<Vec<_>, _> = parse_only(|i| many(i, |i| field_key(i).bind(|i, (n, t)| match n {
+//!     1 => varint(i).map(|v| Some(v.to_string())),
+//!     2 => length_delimited(i).map(|s| Some(String::from_utf8_lossy(s).into_owned())),
+//!     _ => skip_field(i, (n, t)).map(|_| None),
+//! })), b"\x08\x96\x01\x1d\x00\x00\x00\x00\x12\x02hi");
+//!
+//! assert_eq!(r, Ok(vec![Some("150".to_owned()), None, Some("hi".to_owned())]));
+//! ```
+
+use {Input, U8Result};
+use primitives::{InputBuffer, InputClone, IntoInner, State};
+use binary::{le_u32, le_u64, varint_u32, varint_u64};
+use combinators::length_data;
+use parsers::{Error, take};
+
+/// Maximum nesting depth of groups skipped by `skip_field`, including the outermost group.
+pub const MAX_GROUP_DEPTH: usize = 100;
+
+/// The encoding of a field payload.
+#[derive(Copy, Clone, Debug, Eq, PartialEq, Hash)]
+pub enum WireType {
+    /// Variable-length integer, parsed using `varint`.
+    Varint,
+    /// Eight bytes, parsed using `fixed64`.
+    Fixed64,
+    /// Varint length followed by that many bytes, parsed using `length_delimited`.
+    LengthDelimited,
+    /// Start of a deprecated group, which ends with an `EndGroup` key with the same field number.
+    StartGroup,
+    /// End of a deprecated group.
+    EndGroup,
+    /// Four bytes, parsed using `fixed32`.
+    Fixed32,
+}
+
+/// Parses a field key, returning the field number and wire type.
+///
+/// A field number of zero or an unknown wire type results in an unexpected error at the start of
+/// the key.
+///
+/// ```
+/// use chomp::parse_only;
+/// use chomp::formats::protobuf::{field_key, WireType};
+///
+/// assert_eq!(parse_only(field_key, b"\x12"), Ok((2, WireType::LengthDelimited)));
+/// ```
+#[inline]
+pub fn field_key(i: Input<u8>) -> U8Result<(u32, WireType)> {
+    let b = i.buffer();
+
+    varint_u32(i).bind(|i, k| {
+        let t = match k & 7 {
+            0 => WireType::Varint,
+            1 => WireType::Fixed64,
+            2 => WireType::LengthDelimited,
+            3 => WireType::StartGroup,
+            4 => WireType::EndGroup,
+            5 => WireType::Fixed32,
+            _ => return i.replace(b).err(Error::unexpected()),
+        };
+
+        if k >> 3 == 0 {
+            i.replace(b).err(Error::unexpected())
+        } else {
+            i.ret((k >> 3, t))
+        }
+    })
+}
+
+/// Parses a `Varint` payload.
+///
+/// Signed `int32` and `int64` values are obtained by casting, while `sint32` and `sint64` values
+/// are zigzag-encoded, see `binary::zigzag_i32` and `binary::zigzag_i64`.
+#[inline]
+pub fn varint(i: Input<u8>) -> U8Result<u64> {
+    varint_u64(i)
+}
+
+/// Parses a `Fixed32` payload, a little-endian `u32`.
+#[inline]
+pub fn fixed32(i: Input<u8>) -> U8Result<u32> {
+    le_u32(i)
+}
+
+/// Parses a `Fixed64` payload, a little-endian `u64`.
+#[inline]
+pub fn fixed64(i: Input<u8>) -> U8Result<u64> {
+    le_u64(i)
+}
+
+/// Parses a `LengthDelimited` payload, returning the bytes following the length.
+///
+/// If the buffer is shorter than the length this parser is considered incomplete.
+///
+/// ```
+/// use chomp::parse_only;
+/// use chomp::formats::protobuf::length_delimited;
+///
+/// assert_eq!(parse_only(length_delimited, b"\x03abcd"), Ok(&b"abc"[..]));
+/// ```
+#[inline]
+pub fn length_delimited<'a>(i: Input<'a, u8>) -> U8Result<'a, &'a [u8]> {
+    length_data(i, |i| varint_u64(i).map(|n| n as usize))
+}
+
+/// Skips the payload of the field with the given key, eg. for fields unknown to the decoder.
+///
+/// A `StartGroup` key skips all fields up to and including the matching `EndGroup` key, while an
+/// `EndGroup` key without a preceding `StartGroup` results in an unexpected error. Groups nested
+/// deeper than `MAX_GROUP_DEPTH` also result in an unexpected error, at the start of the key
+/// opening the group which is one level too deep.
+///
+/// ```
+/// use chomp::parse_only;
+/// use chomp::formats::protobuf::{field_key, skip_field, varint};
+///
+/// // Field 1 is a group containing field 2, which is followed by field 3
+/// let r = parse_only(|i| field_key(i).bind(skip_field).then(field_key).then(varint), b"\x0b\x10\x01\x0c\x18\x05");
+///
+/// assert_eq!(r, Ok(5));
+/// ```
+pub fn skip_field(i: Input<u8>, key: (u32, WireType)) -> U8Result<()> {
+    match key.1 {
+        WireType::Varint          => varint_u64(i).map(|_| ()),
+        WireType::Fixed64         => take(i, 8).map(|_| ()),
+        WireType::LengthDelimited => length_delimited(i).map(|_| ()),
+        WireType::Fixed32         => take(i, 4).map(|_| ()),
+        WireType::EndGroup        => i.err(Error::unexpected()),
+        WireType::StartGroup      => skip_group(i, key.0),
+    }
+}
+
+/// Internal parser skipping the fields of the group with field number `n`, up to and including
+/// its `EndGroup` key.
+///
+/// Fields are skipped in a loop, keeping the field numbers of the open groups on a stack, so that
+/// neither the number of fields in a group nor the nesting depth grow the call stack.
+fn skip_group(mut i: Input<u8>, n: u32) -> U8Result<()> {
+    let mut groups = vec![n];
+
+    loop {
+        match field_key(i.clone()).into_inner() {
+            State::Data(j, (m, WireType::EndGroup))   => if Some(&m) == groups.last() {
+                groups.pop();
+
+                if groups.is_empty() {
+                    return j.ret(());
+                }
+
+                i = j;
+            } else {
+                return j.err(Error::unexpected());
+            },
+            State::Data(j, (m, WireType::StartGroup)) => if groups.len() < MAX_GROUP_DEPTH {
+                groups.push(m);
+
+                i = j;
+            } else {
+                return i.err(Error::unexpected());
+            },
+            State::Data(j, k)                         => match skip_field(j, k).into_inner() {
+                State::Data(j, ())   => i = j,
+                State::Error(b, e)   => return i.replace(b).err(e),
+                State::Incomplete(n) => return i.incomplete(n),
+            },
+            State::Error(b, e)                        => return i.replace(b).err(e),
+            State::Incomplete(n)                      => return i.incomplete(n),
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::{field_key, varint, fixed32, fixed64, length_delimited, skip_field, WireType,
+                MAX_GROUP_DEPTH};
+
+    use primitives::input::{new, DEFAULT, END_OF_INPUT};
+    use primitives::{IntoInner, State};
+    use parsers::Error;
+
+    #[test]
+    fn field_key_test() {
+        assert_eq!(field_key(new(END_OF_INPUT, b"\x08")).into_inner(), State::Data(new(END_OF_INPUT, b""), (1, WireType::Varint)));
+        assert_eq!(field_key(new(END_OF_INPUT, b"\x11")).into_inner(), State::Data(new(END_OF_INPUT, b""), (2, WireType::Fixed64)));
+        assert_eq!(field_key(new(END_OF_INPUT, b"\x1b")).into_inner(), State::Data(new(END_OF_INPUT, b""), (3, WireType::StartGroup)));
+        assert_eq!(field_key(new(END_OF_INPUT, b"\x1c")).into_inner(), State::Data(new(END_OF_INPUT, b""), (3, WireType::EndGroup)));
+        assert_eq!(field_key(new(END_OF_INPUT, b"\xfd\x07")).into_inner(), State::Data(new(END_OF_INPUT, b""), (127, WireType::Fixed32)));
+        assert_eq!(field_key(new(END_OF_INPUT, b"\x0e\x00")).into_inner(), State::Error(&b"\x0e\x00"[..], Error::unexpected()));
+        assert_eq!(field_key(new(END_OF_INPUT, b"\x80\x00")).into_inner(), State::Error(&b"\x80\x00"[..], Error::unexpected()));
+        assert_eq!(field_key(new(DEFAULT, b"\x80")).into_inner(), State::Incomplete(1));
+    }
+
+    #[test]
+    fn payload_test() {
+        assert_eq!(varint(new(END_OF_INPUT, b"\x96\x01")).into_inner(), State::Data(new(END_OF_INPUT, b""), 150));
+        assert_eq!(fixed32(new(END_OF_INPUT, b"\x01\x00\x00\x00")).into_inner(), State::Data(new(END_OF_INPUT, b""), 1));
+        assert_eq!(fixed64(new(END_OF_INPUT, b"\x01\x00\x00\x00\x00\x00\x00\x01")).into_inner(), State::Data(new(END_OF_INPUT, b""), 0x0100000000000001));
+        assert_eq!(length_delimited(new(END_OF_INPUT, b"\x00a")).into_inner(), State::Data(new(END_OF_INPUT, b"a"), &b""[..]));
+        assert_eq!(length_delimited(new(END_OF_INPUT, b"\x02ab")).into_inner(), State::Data(new(END_OF_INPUT, b""), &b"ab"[..]));
+        assert_eq!(length_delimited(new(DEFAULT, b"\x03ab")).into_inner(), State::Incomplete(1));
+    }
+
+    #[test]
+    fn skip_field_test() {
+        assert_eq!(skip_field(new(END_OF_INPUT, b"\x96\x01!"), (1, WireType::Varint)).into_inner(), State::Data(new(END_OF_INPUT, b"!"), ()));
+        assert_eq!(skip_field(new(END_OF_INPUT, b"12345678!"), (1, WireType::Fixed64)).into_inner(), State::Data(new(END_OF_INPUT, b"!"), ()));
+        assert_eq!(skip_field(new(END_OF_INPUT, b"1234!"), (1, WireType::Fixed32)).into_inner(), State::Data(new(END_OF_INPUT, b"!"), ()));
+        assert_eq!(skip_field(new(END_OF_INPUT, b"\x01a!"), (1, WireType::LengthDelimited)).into_inner(), State::Data(new(END_OF_INPUT, b"!"), ()));
+        assert_eq!(skip_field(new(END_OF_INPUT, b"!"), (1, WireType::EndGroup)).into_inner(), State::Error(&b"!"[..], Error::unexpected()));
+
+        // Nested groups with a group of the same number inside
+        let b = b"\x10\x01\x0b\x10\x02\x0c\x0c!";
+        assert_eq!(skip_field(new(END_OF_INPUT, b), (1, WireType::StartGroup)).into_inner(), State::Data(new(END_OF_INPUT, b"!"), ()));
+        assert_eq!(skip_field(new(END_OF_INPUT, b"\x14"), (1, WireType::StartGroup)).into_inner(), State::Error(&b""[..], Error::unexpected()));
+        assert_eq!(skip_field(new(DEFAULT, &b[..6]), (1, WireType::StartGroup)).into_inner(), State::Incomplete(1));
+        assert_eq!(skip_field(new(DEFAULT, b"123"), (1, WireType::Fixed32)).into_inner(), State::Incomplete(1));
+    }
+
+    #[test]
+    fn skip_field_large_group() {
+        // Group 1 containing 50000 varint fields followed by its end key
+        let mut b = b"\x10\x01".repeat(50000);
+        b.extend(b"\x0c!");
+
+        assert_eq!(skip_field(new(END_OF_INPUT, &b), (1, WireType::StartGroup)).into_inner(), State::Data(new(END_OF_INPUT, b"!"), ()));
+    }
+
+    #[test]
+    fn skip_field_group_depth() {
+        // Group 1 followed by the start keys of nested groups and all their end keys
+        let nested = |depth: usize| {
+            let mut b = b"\x0b".repeat(depth - 1);
+            b.extend(b"\x0c".repeat(depth));
+            b.push(b'!');
+            b
+        };
+
+        let b = nested(MAX_GROUP_DEPTH);
+        assert_eq!(skip_field(new(END_OF_INPUT, &b), (1, WireType::StartGroup)).into_inner(), State::Data(new(END_OF_INPUT, b"!"), ()));
+
+        let b = nested(MAX_GROUP_DEPTH + 1);
+        assert_eq!(skip_field(new(END_OF_INPUT, &b), (1, WireType::StartGroup)).into_inner(), State::Error(&b[MAX_GROUP_DEPTH - 1..], Error::unexpected()));
+
+        let b = nested(100000);
+        assert_eq!(skip_field(new(END_OF_INPUT, &b), (1, WireType::StartGroup)).into_inner(), State::Error(&b[MAX_GROUP_DEPTH - 1..], Error::unexpected()));
+    }
+}
@@ -1,3 +1,7 @@
+use std::cmp::Ordering;
+use std::hash::{Hash, Hasher};
+use std::mem;
+
 use parse_result::{ParseResult, State};
 use parse_result;
 
@@ -59,6 +63,19 @@ pub trait InputBuffer<'a> {
     /// Only used by fundamental parsers and combinators.
     #[inline(always)]
     fn is_last_slice(&self) -> bool;
+
+    /// Returns the absolute offset of the start of the buffer, counted in items from the start of
+    /// the input.
+    ///
+    /// The offset is only meaningful as long as the buffer is replaced with parts of the same
+    /// input, eg. a suffix after consuming items or a previously saved buffer when backtracking.
+    /// A buffer starting before the start of the input results in an offset of 0.
+    ///
+    /// # Primitive
+    ///
+    /// Only used by fundamental parsers and combinators.
+    #[inline(always)]
+    fn offset(&self) -> usize;
 }
 
 /// Linear type containing the parser state, this type is threaded though `bind` and is also the
@@ -73,9 +90,12 @@ pub trait InputBuffer<'a> {
 /// where ``Fn*`` is the appropriate closure/function trait, `I` the input token type (usually
 /// something like `u8`), `...` additional parameters to the parser, `T` the carried type and `E`
 /// the potential error type.
+///
+/// The absolute offset of the buffer is not considered when comparing or hashing inputs, two
+/// inputs are equal if they have the same state and remaining buffer.
 #[must_use]
-#[derive(Debug, Eq, PartialEq, Ord, PartialOrd, Hash)]
-pub struct Input<'a, I: 'a>(InputMode, &'a [I]);
+#[derive(Debug)]
+pub struct Input<'a, I: 'a>(InputMode, &'a [I], usize);
 
 /// **Primitive:** Creates a new input from the given state and buffer.
 ///
@@ -83,7 +103,48 @@ pub struct Input<'a, I: 'a>(InputMode, &'a [I]);
 ///
 /// Only used by fundamental parsers and combinators.
 pub fn new<I>(state: InputMode, buffer: &[I]) -> Input<I> {
-    Input(state, buffer)
+    Input(state, buffer, 0)
+}
+
+/// **Primitive:** Creates a new input from the given state and buffer, where the buffer starts at
+/// the absolute offset `offset` of the input.
+///
+/// # Primitive
+///
+/// Only used by fundamental parsers and combinators.
+pub fn new_at<I>(state: InputMode, offset: usize, buffer: &[I]) -> Input<I> {
+    Input(state, buffer, offset)
+}
+
+impl<'a, I: 'a + PartialEq> PartialEq for Input<'a, I> {
+    #[inline]
+    fn eq(&self, other: &Self) -> bool {
+        self.0 == other.0 && self.1 == other.1
+    }
+}
+
+impl<'a, I: 'a + Eq> Eq for Input<'a, I> {}
+
+impl<'a, I: 'a + PartialOrd> PartialOrd for Input<'a, I> {
+    #[inline]
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        (self.0, self.1).partial_cmp(&(other.0, other.1))
+    }
+}
+
+impl<'a, I: 'a + Ord> Ord for Input<'a, I> {
+    #[inline]
+    fn cmp(&self, other: &Self) -> Ordering {
+        (self.0, self.1).cmp(&(other.0, other.1))
+    }
+}
+
+impl<'a, I: 'a + Hash> Hash for Input<'a, I> {
+    #[inline]
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.0.hash(state);
+        self.1.hash(state);
+    }
 }
 
 impl<'a, I> Input<'a, I> {
@@ -187,7 +248,7 @@ impl<'a, I> Input<'a, I> {
 impl<'a, I: 'a> InputClone for Input<'a, I> {
     #[inline(always)]
     fn clone(&self) -> Self {
-        Input(self.0, self.1)
+        Input(self.0, self.1, self.2)
     }
 }
 
@@ -237,18 +298,36 @@ impl<'a, I: 'a> InputBuffer<'a> for Input<'a, I> {
 
     #[inline(always)]
     fn replace(self, b: &'a [Self::Item]) -> Self {
-        Input(self.0, b)
+        // The new buffer is a part of the same input, so the offset moves by the distance
+        // between the starts of the buffers, saturating at the start of the input
+        let delta = match mem::size_of::<I>() as isize {
+            0    => 0,
+            size => (b.as_ptr() as isize).wrapping_sub(self.1.as_ptr() as isize) / size,
+        };
+
+        let offset = if delta < 0 {
+            self.2.saturating_sub(delta.wrapping_neg() as usize)
+        } else {
+            self.2.saturating_add(delta as usize)
+        };
+
+        Input(self.0, b, offset)
     }
 
     #[inline(always)]
     fn is_last_slice(&self) -> bool {
         self.0.contains(END_OF_INPUT)
     }
+
+    #[inline(always)]
+    fn offset(&self) -> usize {
+        self.2
+    }
 }
 
 #[cfg(test)]
 mod test {
-    use super::{new, Input, InputBuffer, DEFAULT, END_OF_INPUT};
+    use super::{new, new_at, Input, InputBuffer, InputClone, DEFAULT, END_OF_INPUT};
     use parse_result::ParseResult;
     use primitives::{IntoInner, State};
 
@@ -260,8 +339,8 @@ mod test {
         let r1: ParseResult<u8, u32, ()> = i1.ret::<_, ()>(23u32);
         let r2: ParseResult<u8, i32, ()> = i2.ret::<_, ()>(23i32);
 
-        assert_eq!(r1.into_inner(), State::Data(Input(END_OF_INPUT, b"in1"), 23u32));
-        assert_eq!(r2.into_inner(), State::Data(Input(DEFAULT, b"in2"), 23i32));
+        assert_eq!(r1.into_inner(), State::Data(Input(END_OF_INPUT, b"in1", 0), 23u32));
+        assert_eq!(r2.into_inner(), State::Data(Input(DEFAULT, b"in2", 0), 23i32));
     }
 
     #[test]
@@ -294,4 +373,20 @@ mod test {
 
         assert_eq!(i.is_last_slice(), true);
     }
+
+    #[test]
+    fn offset() {
+        let b = b"foobar";
+        let i = new_at(DEFAULT, 10, &b[..]);
+
+        assert_eq!(i.offset(), 10);
+
+        let i = i.replace(&b[3..]);
+
+        assert_eq!(i.offset(), 13);
+        assert_eq!(i.clone().replace(&b[1..]).offset(), 11);
+        assert_eq!(i.replace(&b[6..]).offset(), 16);
+        assert_eq!(new(DEFAULT, &b[2..]).replace(&b[..]).offset(), 0);
+        assert_eq!(new(DEFAULT, &b[..]), new_at(DEFAULT, 3, &b[..]));
+    }
 }
@@ -205,6 +205,20 @@
 extern crate bitflags;
 extern crate conv;
 extern crate debugtrace;
+#[cfg(feature = "unicode")]
+extern crate unicode_xid;
+#[cfg(feature = "graphemes")]
+extern crate unicode_segmentation;
+#[cfg(feature = "case_folding")]
+extern crate caseless;
+#[cfg(feature = "memchr")]
+extern crate memchr;
+#[cfg(feature = "mmap")]
+extern crate memmap;
+#[cfg(feature = "async")]
+extern crate futures;
+#[cfg(feature = "tokio")]
+extern crate tokio_io;
 
 #[macro_use]
 mod macros;
@@ -213,9 +227,13 @@ mod parse;
 mod parse_result;
 
 pub mod ascii;
+pub mod binary;
 pub mod buffer;
 pub mod parsers;
 pub mod combinators;
+pub mod formats;
+pub mod report;
+pub mod unicode;
 
 pub use combinators::{
     count,
@@ -234,25 +252,36 @@ pub use parsers::{
     any,
     eof,
     not_token,
+    none_of,
     peek,
     peek_next,
+    peek_token,
     satisfy,
     satisfy_with,
+    satisfy_map,
     scan,
     string,
+    string_by,
     run_scanner,
+    run_scanner_mut,
     take,
     take_remainder,
     take_till,
+    take_until,
     take_while,
     take_while1,
+    take_while_m_n,
     token,
 };
 pub use parsers::Error;
 pub use input::Input;
 pub use parse::{
+    Diagnostics,
+    ErrorSink,
     ParseError,
+    parse_collecting_errors,
     parse_only,
+    parse_with_diagnostics,
 };
 pub use parse_result::{
     ParseResult,
@@ -281,7 +310,7 @@ pub mod primitives {
     ///
     /// Only used by fundamental parsers and combinators.
     pub mod input {
-        pub use input::{DEFAULT, END_OF_INPUT, new};
+        pub use input::{DEFAULT, END_OF_INPUT, new, new_at};
     }
 
     /// ParseResult utilities.
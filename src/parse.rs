@@ -1,5 +1,14 @@
+use std::error;
+use std::fmt;
+use std::io;
+use std::mem;
+
+use std::marker::PhantomData;
+
 use {Input, ParseResult};
-use primitives::{IntoInner, State};
+use combinators::recover;
+use parsers::ParseErrorKind;
+use primitives::{InputBuffer, InputClone, IntoInner, State};
 use primitives::input;
 
 /// Simple error type returned from `parse_only`.
@@ -12,6 +21,77 @@ pub enum ParseError<'a, I, E>
     Incomplete(usize),
 }
 
+impl<'a, I, E> ParseError<'a, I, E> {
+    /// Returns the offset of the error from the start of `input`, counted in items, for reporting
+    /// errors like "error at byte 10243".
+    ///
+    /// `input` should be the slice passed to `parse_only`. Yields `None` for `Incomplete` and for
+    /// errors which do not point into `input`.
+    ///
+    /// ```
+    /// use chomp::{parse_only, token};
+    ///
+    /// let input = b"aab";
+    /// let r     = parse_only(|i| token(i, b'a').then(|i| token(i, b'a')).then(|i| token(i, b'a')), input);
+    ///
+    /// assert_eq!(r.unwrap_err().offset(input), Some(2));
+    /// ```
+    #[inline]
+    pub fn offset(&self, input: &[I]) -> Option<usize> {
+        match *self {
+            ParseError::Error(b, _) => {
+                let start = input.as_ptr() as usize;
+                let pos   = b.as_ptr() as usize;
+
+                if pos < start || pos + mem::size_of_val(b) > start + mem::size_of_val(input) {
+                    return None;
+                }
+
+                match mem::size_of::<I>() {
+                    0    => Some(0),
+                    size => Some((pos - start) / size),
+                }
+            },
+            ParseError::Incomplete(_) => None,
+        }
+    }
+}
+
+impl<'a, I, E> fmt::Display for ParseError<'a, I, E>
+  where E: fmt::Display {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match *self {
+            ParseError::Error(_, ref e) => write!(f, "parse error: {}", e),
+            ParseError::Incomplete(n)   => write!(f, "incomplete input, {} more items required", n),
+        }
+    }
+}
+
+impl<'a, I, E> error::Error for ParseError<'a, I, E>
+  where I: fmt::Debug,
+        E: error::Error {
+    fn description(&self) -> &str {
+        match *self {
+            ParseError::Error(_, _)   => "parse error",
+            ParseError::Incomplete(_) => "incomplete input",
+        }
+    }
+}
+
+/// Converts the error into an `io::Error` of kind `InvalidData`, or `UnexpectedEof` if the input
+/// was incomplete. The error is converted to a message since it borrows the input.
+impl<'a, I, E> From<ParseError<'a, I, E>> for io::Error
+  where E: fmt::Display {
+    fn from(e: ParseError<'a, I, E>) -> Self {
+        let kind = match e {
+            ParseError::Error(_, _)   => io::ErrorKind::InvalidData,
+            ParseError::Incomplete(_) => io::ErrorKind::UnexpectedEof,
+        };
+
+        io::Error::new(kind, e.to_string())
+    }
+}
+
 /// Runs the given parser on the supplied finite input.
 ///
 /// ```
@@ -60,13 +140,148 @@ pub fn parse_only<'a, I, T, E, F>(parser: F, input: &'a [I]) -> Result<T, ParseE
     }
 }
 
+/// Error sink used by `parse_collecting_errors` to record errors which were recovered from.
+pub struct ErrorSink<'a, I, E>
+  where I: 'a {
+    errors: Vec<(usize, E)>,
+    _input: PhantomData<&'a [I]>,
+}
+
+impl<'a, I, E> ErrorSink<'a, I, E>
+  where I: 'a {
+    /// Applies the parser `F`, recovering using `combinators::recover` if it fails. On failure the
+    /// error is recorded together with its offset and `None` is yielded, otherwise `Some` with the
+    /// parsed value.
+    ///
+    /// Incomplete state is propagated.
+    #[inline]
+    pub fn recover<T, F, S, V, N>(&mut self, i: Input<'a, I>, f: F, sync: S) -> ParseResult<'a, I, Option<T>, N>
+      where I: Copy,
+            T: 'a,
+            E: 'a,
+            V: 'a,
+            N: 'a + ParseErrorKind<I>,
+            F: FnOnce(Input<'a, I>) -> ParseResult<'a, I, T, E>,
+            S: FnMut(Input<'a, I>) -> ParseResult<'a, I, V, N> {
+        recover(i, |i| match f(i.clone()).into_inner() {
+            State::Data(b, t)    => b.ret(t),
+            State::Error(b, e)   => {
+                // The input might be a window of the complete input, use its absolute offset
+                let offset = i.clone().replace(b).offset();
+
+                i.replace(b).err((offset, e))
+            },
+            State::Incomplete(n) => i.incomplete(n),
+        }, sync).map(|r| match r {
+            Ok(t)  => Some(t),
+            Err(e) => {
+                self.errors.push(e);
+
+                None
+            },
+        })
+    }
+}
+
+/// Runs the given parser on the supplied finite input, collecting all errors recovered from using
+/// the supplied `ErrorSink`.
+///
+/// Yields the result of the parser along with all recovered errors and their offsets from the
+/// start of the input in the order they occurred. This allows a whole document to be parsed in one
+/// pass, producing a best-effort value as well as every error encountered.
+///
+/// ```
+/// use chomp::{Error, parse_collecting_errors, token};
+/// use chomp::ascii::decimal;
+/// use chomp::combinators::many;
+///
+/// let (r, errors) = parse_collecting_errors(|i, errors| many(i, |i| errors.recover(i,
+///     |i| decimal::<u32>(i).bind(|i, n| token(i, b';').map(|_| n)),
+///     |i| token(i, b';'))), b"12;ab;3;x;");
+///
+/// assert_eq!(r, Ok(vec![Some(12), None, Some(3), None]));
+/// assert_eq!(errors, vec![(3, Error::new()), (8, Error::new())]);
+/// ```
+pub fn parse_collecting_errors<'a, I, T, E, N, F>(parser: F, input: &'a [I]) -> (Result<T, ParseError<'a, I, N>>, Vec<(usize, E)>)
+  where T: 'a,
+        E: 'a,
+        N: 'a,
+        F: FnOnce(Input<'a, I>, &mut ErrorSink<'a, I, E>) -> ParseResult<'a, I, T, N> {
+    let mut sink = ErrorSink {
+        errors: Vec::new(),
+        _input: PhantomData,
+    };
+
+    let r = parse_only(|i| parser(i, &mut sink), input);
+
+    (r, sink.errors)
+}
+
+/// Handle used by `parse_with_diagnostics` to let parsers emit non-fatal diagnostics, like
+/// warnings about deprecated syntax or suspicious values, without failing.
+pub struct Diagnostics<'a, I, W>
+  where I: 'a {
+    diagnostics: Vec<(usize, W)>,
+    _input:      PhantomData<&'a [I]>,
+}
+
+impl<'a, I, W> Diagnostics<'a, I, W>
+  where I: 'a {
+    /// Records the diagnostic `w` at the current position of the input, succeeding without
+    /// consuming any input.
+    ///
+    /// ```
+    /// use chomp::{parse_with_diagnostics, Error};
+    /// use chomp::ascii::decimal;
+    ///
+    /// let (r, warnings) = parse_with_diagnostics(|i, d| decimal::<u32>(i).bind(|i, n| {
+    ///     if n > 100 {
+    ///         d.warn::<Error<u8>>(i, "value is larger than 100").map(|_| n)
+    ///     } else {
+    ///         i.ret(n)
+    ///     }
+    /// }), b"123");
+    ///
+    /// assert_eq!(r, Ok(123));
+    /// assert_eq!(warnings, vec![(3, "value is larger than 100")]);
+    /// ```
+    #[inline]
+    pub fn warn<E>(&mut self, i: Input<'a, I>, w: W) -> ParseResult<'a, I, (), E> {
+        self.diagnostics.push((i.offset(), w));
+
+        i.ret(())
+    }
+}
+
+/// Runs the given parser on the supplied finite input, allowing it to emit diagnostics using the
+/// supplied `Diagnostics` handle.
+///
+/// Yields the result of the parser along with all emitted diagnostics and their offsets from the
+/// start of the input in the order they were emitted. Diagnostics emitted by branches which were
+/// later backtracked are kept.
+pub fn parse_with_diagnostics<'a, I, T, E, W, F>(parser: F, input: &'a [I]) -> (Result<T, ParseError<'a, I, E>>, Vec<(usize, W)>)
+  where T: 'a,
+        E: 'a,
+        F: FnOnce(Input<'a, I>, &mut Diagnostics<'a, I, W>) -> ParseResult<'a, I, T, E> {
+    let mut diagnostics = Diagnostics {
+        diagnostics: Vec::new(),
+        _input:      PhantomData,
+    };
+
+    let r = parse_only(|i| parser(i, &mut diagnostics), input);
+
+    (r, diagnostics.diagnostics)
+}
+
 #[cfg(test)]
 mod test {
     use primitives::InputBuffer;
 
     use super::{
         ParseError,
+        parse_collecting_errors,
         parse_only,
+        parse_with_diagnostics,
     };
 
     #[test]
@@ -94,6 +309,89 @@ mod test {
         }, b"the input"), Err(ParseError::Error(&b"input"[..], "my error")));
     }
 
+    #[test]
+    fn offset() {
+        let input = b"the input";
+        let r     = parse_only(|i| {
+            let buf = i.buffer();
+
+            i.replace(&buf[4..]).err::<(), _>("my error")
+        }, input);
+
+        assert_eq!(r.unwrap_err().offset(input), Some(4));
+        assert_eq!(ParseError::Error(&input[9..], ()).offset(input), Some(9));
+        assert_eq!(ParseError::Error(&input[1..3], ()).offset(input), Some(1));
+        assert_eq!(ParseError::Error(&b"other"[..], ()).offset(input), None);
+        assert_eq!(ParseError::Incomplete::<u8, ()>(1).offset(input), None);
+    }
+
+    #[test]
+    fn display() {
+        use std::io;
+
+        let e: ParseError<u8, _> = ParseError::Error(&b"a"[..], "my error");
+
+        assert_eq!(e.to_string(), "parse error: my error");
+        assert_eq!(ParseError::Incomplete::<u8, &str>(2).to_string(), "incomplete input, 2 more items required");
+
+        let e: io::Error = ParseError::Error(&b"a"[..], "my error").into();
+
+        assert_eq!(e.kind(), io::ErrorKind::InvalidData);
+        assert_eq!(e.to_string(), "parse error: my error");
+
+        let e: io::Error = ParseError::Incomplete::<u8, &str>(2).into();
+
+        assert_eq!(e.kind(), io::ErrorKind::UnexpectedEof);
+    }
+
+    #[test]
+    fn collecting_errors() {
+        use {Error, any, eof, string, take_remainder, token};
+        use combinators::{length_value, many};
+
+        let r = parse_collecting_errors(|i, errors| many(i, |i| errors.recover(i,
+            |i| string(i, b"ab").bind(|i, s| token(i, b';').map(|_| s)),
+            |i| token(i, b';'))), b"ab;x;abc;ab");
+
+        assert_eq!(r, (Ok(vec![Some(&b"ab"[..]), None, None]), vec![
+            (3, Error::expected(b'x')),
+            (7, Error::expected(b';')),
+        ]));
+
+        let r = parse_collecting_errors(|i, errors| errors.recover(i, |i| token(i, b'a'), |i| token(i, b';'))
+                                        .bind(|i, t| eof(i).map(|_| t)), b"b;c");
+
+        assert_eq!(r, (Err(ParseError::Error(&b"c"[..], Error::unexpected())), vec![(0, Error::expected(b'a'))]));
+
+        // Offsets inside a window of the input are relative to the start of the complete input
+        let r = parse_collecting_errors(|i, errors| length_value(i, |i| any(i).map(|n| n as usize),
+            |i| errors.recover(i, |i| token(i, b'b'), take_remainder)), b"\x02abcd");
+
+        assert_eq!(r, (Ok(None), vec![(1, Error::expected(b'b'))]));
+    }
+
+    #[test]
+    fn diagnostics() {
+        use {Error, any, take, token};
+        use combinators::{length_value, many, or};
+
+        let r = parse_with_diagnostics(|i, d| many(i, |i| or(i,
+            |i| token(i, b'a'),
+            |i| d.warn(i, "deprecated").then(|i| token(i, b'b')))), b"abac");
+
+        assert_eq!(r, (Ok(vec![b'a', b'b', b'a']), vec![(1, "deprecated"), (3, "deprecated")]));
+
+        let r = parse_with_diagnostics(|i, d| d.warn(i, ()).then(|i| token(i, b'a')), b"b");
+
+        assert_eq!(r, (Err(ParseError::Error(&b"b"[..], Error::expected(b'a'))), vec![(0, ())]));
+
+        // Offsets inside a window of the input are relative to the start of the complete input
+        let r = parse_with_diagnostics(|i, d| length_value(i, |i| any(i).map(|n| n as usize),
+            |i| d.warn::<Error<u8>>(i, "w").then(|i| take(i, 2))), b"\x02abcd");
+
+        assert_eq!(r, (Ok(&b"ab"[..]), vec![(1, "w")]));
+    }
+
     #[test]
     fn incomplete() {
         assert_eq!(parse_only(|i| i.incomplete::<(), ()>(23), b"the input"), Err(ParseError::Incomplete(23)));
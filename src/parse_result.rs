@@ -1,5 +1,6 @@
 use parsers::Error;
 use input::Input;
+use primitives::InputBuffer;
 
 /// Result for dealing with the basic parsers when parsing a stream of `u8`.
 pub type U8Result<'a, T>        = ParseResult<'a, u8, T, Error<u8>>;
@@ -180,6 +181,35 @@ impl<'a, I, T, E> ParseResult<'a, I, T, E> {
         self.bind(|i, _| f(i))
     }
 
+    /// Sequentially composes the result with a parse action ``f``, keeping the value of this
+    /// result and discarding the value produced by ``f``.
+    ///
+    /// This is the counterpart of ``then`` which keeps the value produced by ``f`` instead.
+    ///
+    /// # Relation to ``bind``
+    ///
+    /// ```text
+    /// ParseResult::skip(g)  ≡  ParseResult::bind(|i, t| g(i).map(|_| t))
+    /// ```
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use chomp::{parse_only, token};
+    /// use chomp::ascii::decimal;
+    ///
+    /// let r = parse_only(|i| decimal::<u32>(i).skip(|i| token(i, b';')), b"123;");
+    ///
+    /// assert_eq!(r, Ok(123));
+    /// ```
+    #[inline]
+    pub fn skip<F, U, V>(self, f: F) -> ParseResult<'a, I, T, V>
+      where F: FnOnce(Input<'a, I>) -> ParseResult<'a, I, U, V>,
+            U: 'a,
+            V: From<E> {
+        self.bind(|i, t| f(i).map(|_| t))
+    }
+
     /// Applies the function `f` on the contained data if the parser is in a success state.
     ///
     /// # Example
@@ -224,6 +254,36 @@ impl<'a, I, T, E> ParseResult<'a, I, T, E> {
         }
     }
 
+    /// Applies the fallible function `f` on the contained data if the parser is in a success
+    /// state. If `f` fails the error is converted into `E` and the parser will be in an error
+    /// state at the current position.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use std::str;
+    ///
+    /// use chomp::{Error, ParseError, parse_only, take_while1};
+    ///
+    /// let p = |i| take_while1(i, |c| c != b' ').map_res(|b| str::from_utf8(b).map_err(|_| Error::unexpected()));
+    ///
+    /// assert_eq!(parse_only(&p, b"test abc"), Ok("test"));
+    /// assert_eq!(parse_only(&p, b"\xff abc"), Err(ParseError::Error(b" abc", Error::unexpected())));
+    /// ```
+    #[inline]
+    pub fn map_res<U, V, F>(self, f: F) -> ParseResult<'a, I, U, E>
+      where F: FnOnce(T) -> Result<U, V>,
+            E: From<V> {
+        match self.0 {
+            State::Data(i, t)    => match f(t) {
+                Ok(u)  => ParseResult(State::Data(i, u)),
+                Err(e) => ParseResult(State::Error(i.buffer(), From::from(e))),
+            },
+            State::Error(i, e)   => ParseResult(State::Error(i, e)),
+            State::Incomplete(n) => ParseResult(State::Incomplete(n)),
+        }
+    }
+
     /// Calls the function `f` with a reference of the contained data if the parser is in a success
     /// state.
     ///
@@ -247,6 +307,53 @@ impl<'a, I, T, E> ParseResult<'a, I, T, E> {
 
         self
     }
+
+    /// Calls the function `f` with a reference of the contained error if the parser is in an
+    /// error state.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use chomp::{parse_only, token};
+    ///
+    /// let mut errors = Vec::new();
+    ///
+    /// let r = parse_only(|i| token(i, b'a').inspect_err(|e| errors.push(e.clone())), b"b");
+    ///
+    /// assert!(r.is_err());
+    /// assert_eq!(errors.len(), 1);
+    /// ```
+    #[inline]
+    pub fn inspect_err<F>(self, f: F) -> ParseResult<'a, I, T, E>
+      where F: FnOnce(&E) {
+        if let State::Error(_, ref e) = self.0 {
+            f(e)
+        }
+
+        self
+    }
+}
+
+impl<'a, I, T> ParseResult<'a, I, T, Error<I>> {
+    /// Replaces the contained error with an error expecting the token `t` if the parser is in an
+    /// error state.
+    ///
+    /// Useful for reporting a meaningful token from parsers which otherwise yield unexpected
+    /// errors, like `satisfy`.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use chomp::{Error, ParseError, parse_only, satisfy};
+    ///
+    /// let r = parse_only(|i| satisfy(i, |c| c == b'a' || c == b'A').expected(b'a'), b"b");
+    ///
+    /// assert_eq!(r, Err(ParseError::Error(b"b", Error::expected(b'a'))));
+    /// ```
+    #[inline]
+    pub fn expected(self, t: I) -> ParseResult<'a, I, T, Error<I>> {
+        self.map_err(|_| Error::expected(t))
+    }
 }
 
 /// **Primitive:** Consumes the `ParseResult` and exposes the internal state.
@@ -381,6 +488,70 @@ mod test {
         assert_eq!(n2, 1);
     }
 
+    #[test]
+    fn parse_result_skip() {
+        use primitives::IntoInner;
+
+        let r1 = input::new(DEFAULT, b"test").ret::<u32, ()>(23).skip(|i| i.ret::<_, ()>("skipped"));
+        let r2 = input::new(DEFAULT, b"test").ret::<u32, ()>(23).skip(|i| i.err::<u32, _>(()));
+        let r3 = input::new(DEFAULT, b"test").err::<u32, ()>(()).skip(|i| i.ret::<_, ()>("skipped"));
+        let r4 = input::new(DEFAULT, b"test").ret::<u32, ()>(23).skip(|i| i.incomplete::<u32, ()>(2));
+
+        assert_eq!(r1.into_inner(), State::Data(input::new(DEFAULT, b"test"), 23));
+        assert_eq!(r2.into_inner(), State::Error(b"test", ()));
+        assert_eq!(r3.into_inner(), State::Error(b"test", ()));
+        assert_eq!(r4.into_inner(), State::Incomplete(2));
+    }
+
+    #[test]
+    fn parse_result_map_res() {
+        use primitives::IntoInner;
+
+        let r1 = input::new(DEFAULT, b"test").ret::<u32, &str>(23).map_res(|n| if n > 10 { Ok(n * 2) } else { Err("too small") });
+        let r2 = input::new(DEFAULT, b"test").ret::<u32, &str>(3).map_res(|n| if n > 10 { Ok(n * 2) } else { Err("too small") });
+        let r3 = input::new(DEFAULT, b"test").err::<u32, &str>("err").map_res(|n| if n > 10 { Ok(n * 2) } else { Err("too small") });
+        let r4 = input::new(DEFAULT, b"test").incomplete::<u32, &str>(2).map_res(|n| if n > 10 { Ok(n * 2) } else { Err("too small") });
+
+        assert_eq!(r1.into_inner(), State::Data(input::new(DEFAULT, b"test"), 46));
+        assert_eq!(r2.into_inner(), State::Error(b"test", "too small"));
+        assert_eq!(r3.into_inner(), State::Error(b"test", "err"));
+        assert_eq!(r4.into_inner(), State::Incomplete(2));
+    }
+
+    #[test]
+    fn parse_result_inspect_err() {
+        use primitives::IntoInner;
+
+        let mut n1 = 0;
+        let mut n2 = 0;
+        let i1     = input::new(DEFAULT, b"test ").err::<u32, u32>(23);
+        let i2     = input::new(DEFAULT, b"test ").ret::<u32, u32>(23);
+
+        let r1 = i1.inspect_err(|e: &u32| {
+            assert_eq!(e, &23);
+
+            n1 += 1;
+        });
+        let r2 = i2.inspect_err(|_: &u32| n2 += 1);
+
+        assert_eq!(r1.into_inner(), State::Error(b"test ", 23));
+        assert_eq!(n1, 1);
+        assert_eq!(r2.into_inner(), State::Data(input::new(DEFAULT, b"test "), 23));
+        assert_eq!(n2, 0);
+    }
+
+    #[test]
+    fn parse_result_expected() {
+        use parsers::Error;
+        use primitives::IntoInner;
+
+        let r1 = input::new(DEFAULT, b"test").err::<(), _>(Error::unexpected()).expected(b'a');
+        let r2 = input::new(DEFAULT, b"test").ret::<_, Error<u8>>(1).expected(b'a');
+
+        assert_eq!(r1.into_inner(), State::Error(b"test", Error::expected(b'a')));
+        assert_eq!(r2.into_inner(), State::Data(input::new(DEFAULT, b"test"), 1));
+    }
+
     #[test]
     fn input_propagation() {
         let mut n1_calls = 0;
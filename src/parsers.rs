@@ -1,5 +1,6 @@
 //! Basic parsers.
 
+use std::cmp;
 use std::mem;
 
 use input::Input;
@@ -13,6 +14,55 @@ pub use self::error::Error;
 #[cfg(feature="backtrace")]
 pub use debugtrace::StackFrame;
 
+/// Hooks used by combinators which create, merge or annotate errors, implemented by `Error`.
+///
+/// Implement this trait for a custom error type to use it with those combinators, eg.
+/// `combinators::context`, `combinators::or_merged` and `combinators::length_value`.
+pub trait ParseErrorKind<I>: Sized {
+    /// Creates an error expecting the token `t`.
+    fn expected(t: I) -> Self;
+
+    /// Creates an error for an unexpected token.
+    fn unexpected() -> Self;
+
+    /// Merges `other`, which failed at the same position as `self`, into this error.
+    fn merge(self, other: Self) -> Self;
+
+    /// Attaches the context label `label` describing the rule which was being parsed.
+    fn add_context(self, label: &'static str) -> Self;
+
+    /// Records the absolute offset at which the error occurred, called by combinators which
+    /// know the position of the error, eg. `combinators::context`.
+    ///
+    /// Ignores the offset by default.
+    #[inline]
+    fn at_position(self, _offset: usize) -> Self {
+        self
+    }
+}
+
+impl<I: PartialEq> ParseErrorKind<I> for Error<I> {
+    #[inline]
+    fn expected(t: I) -> Self {
+        Error::expected(t)
+    }
+
+    #[inline]
+    fn unexpected() -> Self {
+        Error::unexpected()
+    }
+
+    #[inline]
+    fn merge(self, other: Self) -> Self {
+        Error::merge(self, other)
+    }
+
+    #[inline]
+    fn add_context(self, label: &'static str) -> Self {
+        Error::add_context(self, label)
+    }
+}
+
 /// Matches any item, returning it if present.
 ///
 /// If the buffer length is 0 this parser is considered incomplete.
@@ -88,6 +138,32 @@ pub fn satisfy_with<I: Copy, T: Clone, F, P>(i: Input<I>, f: F, p: P) -> SimpleR
     }
 }
 
+/// Reads a single token, applying the transformation `f` to it. If `f` returns `Some` the token is
+/// consumed and the value is returned, if it returns `None` an unexpected error is produced.
+///
+/// If the buffer length is 0 this parser is considered incomplete.
+///
+/// ```
+/// use chomp::{parse_only, satisfy_map};
+///
+/// let r = parse_only(|i| satisfy_map(i, |c| (c as char).to_digit(16)), b"f0");
+///
+/// assert_eq!(r, Ok(15));
+/// ```
+#[inline]
+pub fn satisfy_map<I: Copy, T, F>(i: Input<I>, f: F) -> SimpleResult<I, T>
+  where F: FnOnce(I) -> Option<T> {
+    let b = i.buffer();
+
+    match b.first().cloned() {
+        Some(c) => match f(c) {
+            Some(t) => i.replace(&b[1..]).ret(t),
+            None    => i.err(Error::unexpected()),
+        },
+        None    => i.incomplete(1),
+    }
+}
+
 /// Matches a single token, returning the match on success.
 ///
 /// If the buffer length is 0 this parser is considered incomplete.
@@ -128,6 +204,29 @@ pub fn not_token<I: Copy + PartialEq>(i: Input<I>, t: I) -> SimpleResult<I, I> {
     }
 }
 
+/// Matches a single token as long as it is not contained in `set`, returning the match on success.
+///
+/// If the buffer length is 0 this parser is considered incomplete. On failure an unexpected error
+/// carrying the offending token is reported.
+///
+/// ```
+/// use chomp::{ParseError, Error, parse_only, none_of};
+///
+/// assert_eq!(parse_only(|i| none_of(i, b"\"\\"), b"abc"), Ok(b'a'));
+///
+/// assert_eq!(parse_only(|i| none_of(i, b"\"\\"), b"\"abc"), Err(ParseError::Error(b"\"abc", Error::rejected(b'"'))));
+/// ```
+#[inline]
+pub fn none_of<'a, I: Copy + PartialEq>(i: Input<'a, I>, set: &[I]) -> SimpleResult<'a, I, I> {
+    let b = i.buffer();
+
+    match b.first() {
+        None                          => i.incomplete(1),
+        Some(&c) if !set.contains(&c) => i.replace(&b[1..]).ret(c),
+        Some(&c)                      => i.err(Error::rejected(c)),
+    }
+}
+
 /// Matches any item but does not consume it, on success it gives ``Some`` but if no input remains
 /// ``None`` is produced.
 ///
@@ -164,6 +263,26 @@ pub fn peek_next<I: Copy>(i: Input<I>) -> SimpleResult<I, I> {
     }
 }
 
+/// Matches the token ``t`` but does not consume it, returning the match on success.
+///
+/// If the buffer length is 0 this parser is considered incomplete.
+///
+/// ```
+/// use chomp::{parse_only, peek_token};
+///
+/// assert_eq!(parse_only(|i| peek_token(i, b'a'), b"abc"), Ok(b'a'));
+///
+/// assert!(parse_only(|i| peek_token(i, b'b'), b"abc").is_err());
+/// ```
+#[inline]
+pub fn peek_token<I: Copy + PartialEq>(i: Input<I>, t: I) -> SimpleResult<I, I> {
+    match i.buffer().first().cloned() {
+        None              => i.incomplete(1),
+        Some(c) if t == c => i.ret(c),
+        Some(_)           => i.err(Error::expected(t)),
+    }
+}
+
 /// Matches ``num`` items no matter what they are, returning a slice of the matched items.
 ///
 /// If the buffer length is less than ``num`` this parser is considered incomplete.
@@ -216,10 +335,9 @@ pub fn take_while<I: Copy, F>(i: Input<I>, f: F) -> SimpleResult<I, &[I]>
         // TODO: Should this following 1 be something else, seeing as take_while1 is potentially
         // infinite?
         None    => if i.is_last_slice() {
-            // Last slice and we have just read everything of it, replace with zero-sized slice:
-            // Hack to avoid branch and overflow, does not matter where this zero-sized slice is
-            // allocated
-            i.replace(&b[..0]).ret(b)
+            // Last slice and we have just read everything of it, replace with the zero-sized
+            // slice at the end of the buffer to keep the offset
+            i.replace(&b[b.len()..]).ret(b)
         } else {
             i.incomplete(1)
         },
@@ -250,16 +368,55 @@ pub fn take_while1<I: Copy, F>(i: Input<I>, f: F) -> SimpleResult<I, &[I]>
         // TODO: Should this following 1 be something else, seeing as take_while1 is potentially
         // infinite?
         None    => if b.len() > 0 && i.is_last_slice() {
-            // Last slice and we have just read everything of it, replace with zero-sized slice:
-            // Hack to avoid branch and overflow, does not matter where this zero-sized slice is
-            // allocated
-            i.replace(&b[..0]).ret(b)
+            // Last slice and we have just read everything of it, replace with the zero-sized
+            // slice at the end of the buffer to keep the offset
+            i.replace(&b[b.len()..]).ret(b)
         } else {
             i.incomplete(1)
         },
     }
 }
 
+/// Matches at least ``min`` and at most ``max`` items for which ``f`` returns true, returning a
+/// slice of all the matched items.
+///
+/// If fewer than ``max`` items were matched before the end of the buffer this parser is considered
+/// incomplete unless it is the last slice of input. If fewer than ``min`` items were matched an
+/// error will be returned at the first item which did not match.
+///
+/// # Panics
+///
+/// Will panic if ``max`` is smaller than ``min``.
+///
+/// ```
+/// use chomp::{parse_only, take_while_m_n};
+/// use chomp::ascii::is_hex_digit;
+///
+/// assert_eq!(parse_only(|i| take_while_m_n(i, 2, 4, is_hex_digit), b"1f2e3d"), Ok(&b"1f2e"[..]));
+/// assert_eq!(parse_only(|i| take_while_m_n(i, 2, 4, is_hex_digit), b"1f g"), Ok(&b"1f"[..]));
+/// assert!(parse_only(|i| take_while_m_n(i, 2, 4, is_hex_digit), b"1 g").is_err());
+/// ```
+#[inline]
+pub fn take_while_m_n<'a, I: Copy, F>(i: Input<'a, I>, min: usize, max: usize, f: F) -> SimpleResult<'a, I, &'a [I]>
+  where F: Fn(I) -> bool {
+    assert!(min <= max);
+
+    let b = i.buffer();
+    let m = cmp::min(max, b.len());
+
+    match b[..m].iter().position(|c| f(*c) == false) {
+        Some(n) if n < min => i.replace(&b[n..]).err(Error::unexpected()),
+        Some(n)            => i.replace(&b[n..]).ret(&b[..n]),
+        None if m == max   => i.replace(&b[m..]).ret(&b[..m]),
+        None               => if m >= min && i.is_last_slice() {
+            // See take_while for the zero-sized slice
+            i.replace(&b[m..]).ret(b)
+        } else {
+            i.incomplete(cmp::max(min.saturating_sub(m), 1))
+        },
+    }
+}
+
 /// Matches all items until ``f`` returns true, all items to that point will be returned as a slice
 /// upon success.
 ///
@@ -286,6 +443,39 @@ pub fn take_till<I: Copy, F>(i: Input<I>, f: F) -> SimpleResult<I, &[I]>
     }
 }
 
+/// Matches all items until the sequence ``s`` is found, all items up to (but not including) ``s``
+/// will be returned as a slice upon success.
+///
+/// If ``s`` cannot be found the parser will be considered to be incomplete, requesting enough
+/// additional items to complete ``s`` if the end of the buffer matches the start of it.
+///
+/// ```
+/// use chomp::{parse_only, take_until};
+///
+/// let r = parse_only(|i| take_until(i, b"\r\n\r\n"), b"Host: example.com\r\n\r\nbody");
+///
+/// assert_eq!(r, Ok(&b"Host: example.com"[..]));
+/// ```
+#[inline]
+pub fn take_until<'a, 'b, I: Copy + PartialEq>(i: Input<'a, I>, s: &'b [I])
+    -> SimpleResult<'a, I, &'a [I]> {
+    let b = i.buffer();
+
+    if s.is_empty() {
+        return i.ret(&b[..0]);
+    }
+
+    match b.windows(s.len()).position(|w| w == s) {
+        Some(n) => i.replace(&b[n..]).ret(&b[..n]),
+        None    => {
+            // Longest end of the buffer which is the start of the sequence
+            let m = (1..cmp::min(s.len(), b.len() + 1)).rev().find(|&m| b.ends_with(&s[..m])).unwrap_or(0);
+
+            i.incomplete(s.len() - m)
+        },
+    }
+}
+
 /// The predicate consumes and transforms a state argument, this parser will match everything until
 /// the predicate returns `None`.
 ///
@@ -327,7 +517,6 @@ pub fn scan<I: Copy, S, F>(i: Input<I>, s: S, mut f: F) -> SimpleResult<I, &[I]>
 /// assert_eq!(parse_only(p, b"/*test*of*scan*/ foo"), Ok((&b"/*test*of*scan*"[..], b'*')));
 /// ```
 #[inline]
-// TODO: Remove Copy bound on S
 pub fn run_scanner<I: Copy, S: Copy, F>(i: Input<I>, s: S, mut f: F) -> SimpleResult<I, (&[I], S)>
   where F: FnMut(S, I) -> Option<S> {
     let b         = i.buffer();
@@ -341,6 +530,44 @@ pub fn run_scanner<I: Copy, S: Copy, F>(i: Input<I>, s: S, mut f: F) -> SimpleRe
     }
 }
 
+/// Like `run_scanner` but the predicate updates the state in place, matching everything until the
+/// predicate returns `false`. Useful for states which are not `Copy`, eg. a `Vec` collecting data.
+///
+/// ```
+/// use chomp::{parse_only, run_scanner_mut};
+///
+/// let p = |i| run_scanner_mut(i, Vec::new(), |s: &mut Vec<u8>, c| c != b';' && { s.push(c); true });
+///
+/// assert_eq!(parse_only(p, b"ab;c"), Ok((&b"ab"[..], vec![b'a', b'b'])));
+/// ```
+#[inline]
+pub fn run_scanner_mut<'a, I: Copy, S, F>(i: Input<'a, I>, s: S, mut f: F) -> SimpleResult<'a, I, (&'a [I], S)>
+  where F: FnMut(&mut S, I) -> bool {
+    let b         = i.buffer();
+    let mut state = s;
+
+    match b.iter().position(|&c| !f(&mut state, c)) {
+        Some(n) => i.replace(&b[n..]).ret((&b[0..n], state)),
+        None    => i.incomplete(1),
+    }
+}
+
+/// Returns the absolute offset of the current position in the input without consuming anything,
+/// counted in items from the start of the input.
+///
+/// ```
+/// use chomp::{parse_only, take};
+/// use chomp::parsers::offset;
+///
+/// assert_eq!(parse_only(|i| take(i, 3).then(offset), b"abcd"), Ok(3));
+/// ```
+#[inline]
+pub fn offset<I>(i: Input<I>) -> SimpleResult<I, usize> {
+    let n = i.offset();
+
+    i.ret(n)
+}
+
 /// Matches the remainder of the buffer and returns it, always succeeds.
 ///
 /// ```
@@ -351,11 +578,9 @@ pub fn run_scanner<I: Copy, S: Copy, F>(i: Input<I>, s: S, mut f: F) -> SimpleRe
 #[inline]
 pub fn take_remainder<I: Copy>(i: Input<I>) -> SimpleResult<I, &[I]> {
     let b = i.buffer();
-    // Last slice and we have just read everything of it, replace with zero-sized slice:
-    //
-    // Hack to avoid branch and overflow, does not matter where this zero-sized slice is
-    // allocated as long as it is the same origin slice
-    i.replace(&b[..0]).ret(b)
+    // Last slice and we have just read everything of it, replace with the zero-sized slice at the
+    // end of the buffer to keep the offset
+    i.replace(&b[b.len()..]).ret(b)
 }
 
 /// Matches the given slice against the parser, returning the matched slice upon success.
@@ -371,6 +596,26 @@ pub fn take_remainder<I: Copy>(i: Input<I>) -> SimpleResult<I, &[I]> {
 #[inline]
 pub fn string<'a, 'b, I: Copy + PartialEq>(i: Input<'a, I>, s: &'b [I])
     -> SimpleResult<'a, I, &'a [I]> {
+    string_by(i, s, |a, b| a == b)
+}
+
+/// Matches the given slice against the parser using ``f`` to compare the items, returning the
+/// matched slice upon success.
+///
+/// ``f`` is called with the item of ``s`` first and the item of the input second. If the length of
+/// the contained data is shorter than the given slice this parser is considered incomplete.
+///
+/// ```
+/// use chomp::{parse_only, string_by};
+///
+/// let r = parse_only(|i| string_by(i, b"abc", |a, b| a.eq_ignore_ascii_case(&b)), b"AbCdef");
+///
+/// assert_eq!(r, Ok(&b"AbC"[..]));
+/// ```
+#[inline]
+pub fn string_by<'a, 'b, I: Copy, F>(i: Input<'a, I>, s: &'b [I], f: F)
+    -> SimpleResult<'a, I, &'a [I]>
+  where F: Fn(I, I) -> bool {
     let b = i.buffer();
 
     if s.len() > b.len() {
@@ -380,7 +625,7 @@ pub fn string<'a, 'b, I: Copy + PartialEq>(i: Input<'a, I>, s: &'b [I])
     let d = &b[..s.len()];
 
     for j in 0..s.len() {
-        if s[j] != d[j] {
+        if !f(s[j], d[j]) {
             return i.replace(&b[j..]).err(Error::expected(d[j]))
         }
     }
@@ -388,14 +633,124 @@ pub fn string<'a, 'b, I: Copy + PartialEq>(i: Input<'a, I>, s: &'b [I])
     i.replace(&b[s.len()..]).ret(d)
 }
 
+/// Set of keywords matched by the `keywords` parser, stored as a trie so that the input only has
+/// to be scanned once.
+///
+/// Build it once and reuse it for every call to `keywords`.
+#[derive(Clone, Debug)]
+pub struct Keywords<I, T> {
+    /// Nodes of the trie, the first node is the root
+    nodes: Vec<KeywordNode<I, T>>,
+}
+
+/// Node in the keyword trie.
+#[derive(Clone, Debug)]
+struct KeywordNode<I, T> {
+    /// Transitions to child nodes, sorted on the item
+    edges: Vec<(I, usize)>,
+    /// Value of the keyword ending at this node, if any
+    value: Option<T>,
+}
+
+impl<I: Copy + Ord, T: Clone> Keywords<I, T> {
+    /// Creates a new set of keywords associated with values, the last value is used if a keyword
+    /// occurs more than once.
+    pub fn new(words: &[(&[I], T)]) -> Self {
+        let mut nodes: Vec<KeywordNode<I, T>> = vec![KeywordNode { edges: Vec::new(), value: None }];
+
+        for &(word, ref value) in words {
+            let mut node = 0;
+
+            for &c in word {
+                node = match nodes[node].edges.binary_search_by(|&(e, _)| e.cmp(&c)) {
+                    Ok(e)  => nodes[node].edges[e].1,
+                    Err(e) => {
+                        let n = nodes.len();
+
+                        nodes[node].edges.insert(e, (c, n));
+                        nodes.push(KeywordNode { edges: Vec::new(), value: None });
+
+                        n
+                    },
+                };
+            }
+
+            nodes[node].value = Some(value.clone());
+        }
+
+        Keywords { nodes: nodes }
+    }
+}
+
+/// Matches the longest keyword of `k`, returning the value associated with it.
+///
+/// If the end of the buffer is reached while a longer keyword could still match this parser is
+/// considered incomplete unless it is the last slice of input. If no keyword matches, an error
+/// expecting any of the items which could have continued a keyword is returned at the first item
+/// which did not.
+///
+/// ```
+/// use chomp::parse_only;
+/// use chomp::parsers::{Keywords, keywords};
+///
+/// let k = Keywords::new(&[(&b"for"[..], 1), (&b"foreach"[..], 2), (&b"fn"[..], 3)]);
+///
+/// assert_eq!(parse_only(|i| keywords(i, &k), b"foreach x"), Ok(2));
+/// assert_eq!(parse_only(|i| keywords(i, &k), b"fore x"), Ok(1));
+/// assert!(parse_only(|i| keywords(i, &k), b"fx").is_err());
+/// ```
+#[inline]
+pub fn keywords<'a, I: Copy + Ord, T: Clone>(i: Input<'a, I>, k: &Keywords<I, T>) -> SimpleResult<'a, I, T> {
+    let b        = i.buffer();
+    let mut node = &k.nodes[0];
+    // Length and value of the longest keyword matched so far
+    let mut last = node.value.as_ref().map(|v| (0, v));
+
+    for (j, c) in b.iter().enumerate() {
+        match node.edges.binary_search_by(|&(e, _)| e.cmp(c)) {
+            Ok(e)  => {
+                node = &k.nodes[node.edges[e].1];
+
+                if let Some(ref v) = node.value {
+                    last = Some((j + 1, v));
+                }
+            },
+            Err(_) => return match last {
+                Some((n, v)) => i.replace(&b[n..]).ret(v.clone()),
+                None         => {
+                    let err = node.edges.iter().fold(None, |err: Option<Error<I>>, &(e, _)| Some(match err {
+                        Some(err) => err.merge(Error::expected(e)),
+                        None      => Error::expected(e),
+                    }));
+
+                    i.replace(&b[j..]).err(err.unwrap_or_else(Error::unexpected))
+                },
+            },
+        }
+    }
+
+    match last {
+        Some((n, v)) if node.edges.is_empty() || i.is_last_slice() => i.replace(&b[n..]).ret(v.clone()),
+        None if node.edges.is_empty() => i.err(Error::unexpected()),
+        _                             => i.incomplete(1),
+    }
+}
+
 /// Matches the end of the input.
 ///
+/// Only succeeds if the buffer is empty and it is the last slice of input, otherwise an
+/// unexpected error is returned. Use this to make sure a parser consumes all of its input.
+///
 /// ```
 /// use chomp::{parse_only, token, eof};
 ///
 /// let r = parse_only(|i| token(i, b'a').then(eof), b"a");
 ///
 /// assert_eq!(r, Ok(()));
+///
+/// let r = parse_only(|i| token(i, b'a').then(eof), b"ab");
+///
+/// assert!(r.is_err());
 /// ```
 #[inline]
 pub fn eof<I>(i: Input<I>) -> SimpleResult<I, ()> {
@@ -416,7 +771,9 @@ mod error {
     #[cfg(feature="noop_error")]
     use std::marker::PhantomData;
     #[cfg(not(feature="noop_error"))]
-    use std::ops::Deref;
+    use std::mem;
+    #[cfg(not(feature="noop_error"))]
+    use std::slice;
 
     /// Empty type to eat the generic without printing
     #[cfg(feature="noop_error")]
@@ -431,26 +788,66 @@ mod error {
         }
     }
 
-    /// `Some(T)` if it expected a specific token, `None` if it encountered something unexpected.
+    /// What the parser expected at the point of failure.
     #[cfg(not(feature="noop_error"))]
     #[derive(Clone, Eq, PartialEq, Ord, PartialOrd, Hash)]
-    struct Expected<I>(Option<I>);
+    enum Expected<I> {
+        /// Encountered something unexpected.
+        Unexpected,
+        /// Encountered a specific token which was not acceptable.
+        Rejected(I),
+        /// Expected a specific token.
+        Token(I),
+        /// Expected any one of multiple tokens, created when merging errors.
+        OneOf(Vec<I>),
+    }
 
     #[cfg(not(feature="noop_error"))]
-    impl<I> Deref for Expected<I> {
-        type Target = Option<I>;
-
-        fn deref(&self) -> &Option<I> {
-            &self.0
+    impl<I: PartialEq> Expected<I> {
+        fn merge(self, other: Expected<I>) -> Expected<I> {
+            match (self, other) {
+                (Expected::Unexpected, e) | (e, Expected::Unexpected)   => e,
+                (Expected::Rejected(_), e) | (e, Expected::Rejected(_)) => e,
+                (Expected::Token(a), Expected::Token(b)) => if a == b {
+                    Expected::Token(a)
+                } else {
+                    Expected::OneOf(vec![a, b])
+                },
+                (Expected::Token(a), Expected::OneOf(mut v)) => {
+                    if ! v.contains(&a) {
+                        v.insert(0, a);
+                    }
+
+                    Expected::OneOf(v)
+                },
+                (Expected::OneOf(mut v), Expected::Token(b)) => {
+                    if ! v.contains(&b) {
+                        v.push(b);
+                    }
+
+                    Expected::OneOf(v)
+                },
+                (Expected::OneOf(mut v), Expected::OneOf(w)) => {
+                    for b in w {
+                        if ! v.contains(&b) {
+                            v.push(b);
+                        }
+                    }
+
+                    Expected::OneOf(v)
+                },
+            }
         }
     }
 
     #[cfg(not(feature="noop_error"))]
     impl<I: fmt::Debug> fmt::Debug for Expected<I> {
         fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
-            match self.0 {
-                Some(ref c) => write!(f, "Expected({:?})", c),
-                None        => write!(f, "Unexpected"),
+            match *self {
+                Expected::Unexpected      => write!(f, "Unexpected"),
+                Expected::Rejected(ref c) => write!(f, "Unexpected({:?})", c),
+                Expected::Token(ref c)    => write!(f, "Expected({:?})", c),
+                Expected::OneOf(ref v)    => write!(f, "ExpectedOneOf({:?})", v),
             }
         }
     }
@@ -463,7 +860,17 @@ mod error {
     ///
     /// This is coupled with the state found in the error state of the `ParseResult` type.
     #[derive(Clone, Debug, Eq, PartialEq, Ord, PartialOrd, Hash)]
-    pub struct Error<I>(Trace<Expected<I>>);
+    pub struct Error<I>(Trace<Expected<I>>, Context);
+
+    /// Empty type to eat the context labels without storing them
+    #[cfg(feature="noop_error")]
+    #[derive(Clone, Debug, Eq, PartialEq, Ord, PartialOrd, Hash)]
+    struct Context;
+
+    /// Stack of context labels, innermost first.
+    #[cfg(not(feature="noop_error"))]
+    #[derive(Clone, Debug, Eq, PartialEq, Ord, PartialOrd, Hash)]
+    struct Context(Vec<&'static str>);
 
     #[cfg(feature="noop_error")]
     impl<I> fmt::Display for Error<I>
@@ -473,14 +880,20 @@ mod error {
         }
     }
 
+    /// Displays the context labels outermost first, followed by what was expected, eg.
+    /// `in object -> in key-value pair -> expected 58`.
     #[cfg(not(feature="noop_error"))]
     impl<I> fmt::Display for Error<I>
       where I: fmt::Debug {
         fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
-            match self.0.as_ref() {
-                Some(ref c) => write!(f, "expected {:?}", *c),
-                None        => write!(f, "unexpected"),
-            }
+            let r = (self.1).0.iter().rev().fold(Ok(()), |r, label| r.and_then(|_| write!(f, "in {} -> ", label)));
+
+            r.and_then(|_| match *self.0 {
+                Expected::Unexpected      => write!(f, "unexpected"),
+                Expected::Rejected(ref c) => write!(f, "unexpected {:?}", c),
+                Expected::Token(ref c)    => write!(f, "expected {:?}", c),
+                Expected::OneOf(ref v)    => write!(f, "expected one of {:?}", v),
+            })
         }
     }
 
@@ -494,21 +907,23 @@ mod error {
     #[cfg(not(feature="noop_error"))]
     impl<I: any::Any + fmt::Debug> error::Error for Error<I> {
         fn description(&self) -> &str {
-            match self.0.as_ref() {
-                Some(_) => "expected a certain token, received another",
-                None    => "received an unexpected token",
+            match *self.0 {
+                Expected::Unexpected  => "received an unexpected token",
+                Expected::Rejected(_) => "received an unexpected token",
+                Expected::Token(_)    => "expected a certain token, received another",
+                Expected::OneOf(_)    => "expected one of a set of tokens, received another",
             }
         }
     }
 
     #[cfg(feature="noop_error")]
     macro_rules! create_error {
-        ($_e:expr) => { Error(Trace::new(Expected(PhantomData))) }
+        ($_e:expr) => { Error(Trace::new(Expected(PhantomData)), Context) }
     }
 
     #[cfg(not(feature="noop_error"))]
     macro_rules! create_error {
-        ($e:expr) => { Error(Trace::new(Expected($e))) }
+        ($e:expr) => { Error(Trace::new($e), Context(Vec::new())) }
     }
 
     impl<I> Error<I> {
@@ -517,7 +932,7 @@ mod error {
         /// Should be used when the error value is not important.
         #[inline(always)]
         pub fn new() -> Self {
-            create_error!(None)
+            create_error!(Expected::Unexpected)
         }
 
         /// Creates a new Unexpected error.
@@ -526,7 +941,15 @@ mod error {
         /// provided predicate is provided.
         #[inline(always)]
         pub fn unexpected() -> Self {
-            create_error!(None)
+            create_error!(Expected::Unexpected)
+        }
+
+        /// Creates a new Unexpected error carrying the rejected token.
+        ///
+        /// Should be used when a specific token was not acceptable, as in the case of `none_of`.
+        #[inline(always)]
+        pub fn rejected(_i: I) -> Self {
+            create_error!(Expected::Rejected(_i))
         }
 
         /// Creates a new Expected error.
@@ -534,7 +957,7 @@ mod error {
         /// Should be used when a specific token was expected.
         #[inline(always)]
         pub fn expected(_i: I) -> Self {
-            create_error!(Some(_i))
+            create_error!(Expected::Token(_i))
         }
 
         /// Returns `Some(&I)` if a specific token was expected, `None` otherwise.
@@ -547,10 +970,124 @@ mod error {
         }
 
         /// Returns `Some(&I)` if a specific token was expected, `None` otherwise.
+        ///
+        /// Merged errors expecting more than one token will also yield `None`, use
+        /// `expected_tokens` to obtain all of them.
         #[inline]
         #[cfg(not(feature="noop_error"))]
         pub fn expected_token(&self) -> Option<&I> {
-            self.0.as_ref()
+            match *self.0 {
+                Expected::Token(ref c) => Some(c),
+                _                      => None,
+            }
+        }
+
+        /// Returns `Some(&I)` if a specific token was rejected, `None` otherwise.
+        ///
+        /// Will always yield `None` since `noop_error` is enabled.
+        #[inline]
+        #[cfg(feature="noop_error")]
+        pub fn rejected_token(&self) -> Option<&I> {
+            None
+        }
+
+        /// Returns `Some(&I)` if a specific token was rejected, `None` otherwise.
+        #[inline]
+        #[cfg(not(feature="noop_error"))]
+        pub fn rejected_token(&self) -> Option<&I> {
+            match *self.0 {
+                Expected::Rejected(ref c) => Some(c),
+                _                         => None,
+            }
+        }
+
+        /// Returns all the tokens which were expected, empty if something unexpected was
+        /// encountered.
+        ///
+        /// Will always yield an empty slice since `noop_error` is enabled.
+        #[inline]
+        #[cfg(feature="noop_error")]
+        pub fn expected_tokens(&self) -> &[I] {
+            &[]
+        }
+
+        /// Returns all the tokens which were expected, empty if something unexpected was
+        /// encountered.
+        #[inline]
+        #[cfg(not(feature="noop_error"))]
+        pub fn expected_tokens(&self) -> &[I] {
+            match *self.0 {
+                Expected::Unexpected   => &[],
+                Expected::Rejected(_)  => &[],
+                Expected::Token(ref c) => slice::from_ref(c),
+                Expected::OneOf(ref v) => v,
+            }
+        }
+
+        /// Merges the expected tokens of `other` into this error, used when multiple alternatives
+        /// fail at the same position.
+        ///
+        /// Unexpected errors carry no information about acceptable tokens and will be discarded in
+        /// favour of the other error.
+        ///
+        /// Will always yield `self` since `noop_error` is enabled.
+        #[inline]
+        #[cfg(feature="noop_error")]
+        pub fn merge(self, _other: Self) -> Self
+          where I: PartialEq {
+            self
+        }
+
+        /// Merges the expected tokens of `other` into this error, used when multiple alternatives
+        /// fail at the same position.
+        ///
+        /// Unexpected errors carry no information about acceptable tokens and will be discarded in
+        /// favour of the other error.
+        #[inline]
+        #[cfg(not(feature="noop_error"))]
+        pub fn merge(mut self, other: Self) -> Self
+          where I: PartialEq {
+            let e = mem::replace(&mut *self.0, Expected::Unexpected);
+
+            *self.0 = e.merge(other.0.unwrap());
+
+            self
+        }
+
+        /// Attaches the context label `label` to this error, describing the rule which was being
+        /// parsed when the error occurred.
+        ///
+        /// Will always yield `self` since `noop_error` is enabled.
+        #[inline]
+        #[cfg(feature="noop_error")]
+        pub fn add_context(self, _label: &'static str) -> Self {
+            self
+        }
+
+        /// Attaches the context label `label` to this error, describing the rule which was being
+        /// parsed when the error occurred.
+        #[inline]
+        #[cfg(not(feature="noop_error"))]
+        pub fn add_context(mut self, label: &'static str) -> Self {
+            (self.1).0.push(label);
+
+            self
+        }
+
+        /// Returns the stack of context labels attached to this error, innermost first.
+        ///
+        /// Will always yield an empty slice since `noop_error` is enabled.
+        #[inline]
+        #[cfg(feature="noop_error")]
+        pub fn context(&self) -> &[&'static str] {
+            &[]
+        }
+
+        /// Returns the stack of context labels attached to this error, innermost first.
+        #[inline]
+        #[cfg(not(feature="noop_error"))]
+        pub fn context(&self) -> &[&'static str] {
+            &(self.1).0
         }
 
         /// Returns a stack-trace to where the error was created.
@@ -618,6 +1155,35 @@ mod test {
         assert_eq!(token(new(DEFAULT, b"bb"), b'a').into_inner(), State::Error(b"bb", Error::expected(b'a')));
     }
 
+    #[test]
+    fn not_token_test() {
+        assert_eq!(not_token(new(DEFAULT, b""), b'a').into_inner(), State::Incomplete(1));
+        assert_eq!(not_token(new(DEFAULT, b"ba"), b'a').into_inner(), State::Data(new(DEFAULT, b"a"), b'b'));
+        assert_eq!(not_token(new(DEFAULT, b"ab"), b'a').into_inner(), State::Error(b"ab", Error::unexpected()));
+    }
+
+    #[test]
+    fn none_of_test() {
+        assert_eq!(none_of(new(DEFAULT, b""), b"ab").into_inner(), State::Incomplete(1));
+        assert_eq!(none_of(new(DEFAULT, b"ca"), b"ab").into_inner(), State::Data(new(DEFAULT, b"a"), b'c'));
+        assert_eq!(none_of(new(DEFAULT, b"ca"), b"").into_inner(), State::Data(new(DEFAULT, b"a"), b'c'));
+        assert_eq!(none_of(new(DEFAULT, b"bc"), b"ab").into_inner(), State::Error(b"bc", Error::rejected(b'b')));
+    }
+
+    #[test]
+    #[cfg(not(feature="noop_error"))]
+    fn none_of_rejected_token() {
+        match none_of(new(DEFAULT, b"bc"), b"ab").into_inner() {
+            State::Error(b, e) => {
+                assert_eq!(b, &b"bc"[..]);
+                assert_eq!(e.rejected_token(), Some(&b'b'));
+                assert_eq!(e.expected_tokens(), &[][..]);
+                assert_eq!(e.to_string(), "unexpected 98");
+            },
+            s => panic!("Expected error, got {:?}", s),
+        }
+    }
+
     #[test]
     fn take_test() {
         assert_eq!(take(new(DEFAULT, b"a"), 1).into_inner(), State::Data(new(DEFAULT, b""), &b"a"[..]));
@@ -649,6 +1215,50 @@ mod test {
         assert_eq!(take_while1(new(END_OF_INPUT, b"acc"), |c| c != b'b').into_inner(), State::Data(new(END_OF_INPUT, b""), &b"acc"[..]));
     }
 
+    #[test]
+    fn take_while_m_n_test() {
+        let f = |c| c == b'a';
+
+        assert_eq!(take_while_m_n(new(DEFAULT, b"aaab"), 1, 2, f).into_inner(), State::Data(new(DEFAULT, b"ab"), &b"aa"[..]));
+        assert_eq!(take_while_m_n(new(DEFAULT, b"aab"), 1, 3, f).into_inner(), State::Data(new(DEFAULT, b"b"), &b"aa"[..]));
+        assert_eq!(take_while_m_n(new(DEFAULT, b"b"), 0, 3, f).into_inner(), State::Data(new(DEFAULT, b"b"), &b""[..]));
+        assert_eq!(take_while_m_n(new(DEFAULT, b"ab"), 0, 0, f).into_inner(), State::Data(new(DEFAULT, b"ab"), &b""[..]));
+        assert_eq!(take_while_m_n(new(DEFAULT, b"aa"), 2, 2, f).into_inner(), State::Data(new(DEFAULT, b""), &b"aa"[..]));
+        assert_eq!(take_while_m_n(new(DEFAULT, b"abaa"), 2, 3, f).into_inner(), State::Error(&b"baa"[..], Error::unexpected()));
+        assert_eq!(take_while_m_n(new(DEFAULT, b"aa"), 1, 3, f).into_inner(), State::Incomplete(1));
+        assert_eq!(take_while_m_n(new(DEFAULT, b"a"), 3, 4, f).into_inner(), State::Incomplete(2));
+        assert_eq!(take_while_m_n(new(DEFAULT, b""), 0, 4, f).into_inner(), State::Incomplete(1));
+
+        assert_eq!(take_while_m_n(new(END_OF_INPUT, b"aa"), 1, 3, f).into_inner(), State::Data(new(END_OF_INPUT, b""), &b"aa"[..]));
+        assert_eq!(take_while_m_n(new(END_OF_INPUT, b""), 0, 3, f).into_inner(), State::Data(new(END_OF_INPUT, b""), &b""[..]));
+        assert_eq!(take_while_m_n(new(END_OF_INPUT, b"a"), 3, 4, f).into_inner(), State::Incomplete(2));
+    }
+
+    #[test]
+    #[should_panic]
+    fn take_while_m_n_range() {
+        let _ = take_while_m_n(new(DEFAULT, b"a"), 2, 1, |c| c == b'a');
+    }
+
+    #[test]
+    fn scan_test() {
+        let f = |s: usize, c| if c == b'a' && s < 2 { Some(s + 1) } else { None };
+
+        assert_eq!(scan(new(DEFAULT, b"aaab"), 0, f).into_inner(), State::Data(new(DEFAULT, b"ab"), &b"aa"[..]));
+        assert_eq!(scan(new(DEFAULT, b"b"), 0, f).into_inner(), State::Data(new(DEFAULT, b"b"), &b""[..]));
+        assert_eq!(scan(new(DEFAULT, b"a"), 0, f).into_inner(), State::Incomplete(1));
+
+        assert_eq!(run_scanner(new(DEFAULT, b"aaab"), 0, f).into_inner(), State::Data(new(DEFAULT, b"ab"), (&b"aa"[..], 2)));
+        assert_eq!(run_scanner(new(DEFAULT, b"b"), 0, f).into_inner(), State::Data(new(DEFAULT, b"b"), (&b""[..], 0)));
+        assert_eq!(run_scanner(new(DEFAULT, b"a"), 0, f).into_inner(), State::Incomplete(1));
+
+        let g = |s: &mut Vec<u8>, c| c != b';' && { s.push(c); true };
+
+        assert_eq!(run_scanner_mut(new(DEFAULT, b"ab;"), Vec::new(), g).into_inner(), State::Data(new(DEFAULT, b";"), (&b"ab"[..], vec![b'a', b'b'])));
+        assert_eq!(run_scanner_mut(new(DEFAULT, b";"), Vec::new(), g).into_inner(), State::Data(new(DEFAULT, b";"), (&b""[..], vec![])));
+        assert_eq!(run_scanner_mut(new(DEFAULT, b"ab"), Vec::new(), g).into_inner(), State::Incomplete(1));
+    }
+
     #[test]
     fn peek_next_test() {
         assert_eq!(peek_next(new(DEFAULT, b"abc")).into_inner(), State::Data(new(DEFAULT, b"abc"), b'a'));
@@ -657,6 +1267,15 @@ mod test {
         assert_eq!(peek_next(new(END_OF_INPUT, b"")).into_inner(), State::Incomplete(1));
     }
 
+    #[test]
+    fn peek_token_test() {
+        assert_eq!(peek_token(new(DEFAULT, b"abc"), b'a').into_inner(), State::Data(new(DEFAULT, b"abc"), b'a'));
+        assert_eq!(peek_token(new(END_OF_INPUT, b"abc"), b'a').into_inner(), State::Data(new(END_OF_INPUT, b"abc"), b'a'));
+        assert_eq!(peek_token(new(DEFAULT, b"abc"), b'b').into_inner(), State::Error(b"abc", Error::expected(b'b')));
+        assert_eq!(peek_token(new(DEFAULT, b""), b'a').into_inner(), State::Incomplete(1));
+        assert_eq!(peek_token(new(END_OF_INPUT, b""), b'a').into_inner(), State::Incomplete(1));
+    }
+
     #[test]
     fn satisfy_with_test() {
         let mut m1 = 0;
@@ -672,6 +1291,17 @@ mod test {
         assert_eq!(n2, 0);
     }
 
+    #[test]
+    fn satisfy_map_test() {
+        assert_eq!(satisfy_map(new(DEFAULT, b"abc"), |c| if c == b'a' { Some(1) } else { None }).into_inner(), State::Data(new(DEFAULT, b"bc"), 1));
+        assert_eq!(satisfy_map(new(DEFAULT, b"abc"), |c| if c == b'b' { Some(1) } else { None }).into_inner(), State::Error(b"abc", Error::unexpected()));
+
+        let mut n = 0;
+        let r: State<_, (), _> = satisfy_map(new(END_OF_INPUT, b""), |_| { n += 1; None }).into_inner();
+        assert_eq!(r, State::Incomplete(1));
+        assert_eq!(n, 0);
+    }
+
     #[test]
     fn string_test() {
         assert_eq!(string(new(DEFAULT, b"abc"), b"a").into_inner(), State::Data(new(DEFAULT, b"bc"), &b"a"[..]));
@@ -689,6 +1319,69 @@ mod test {
         assert_eq!(string(new(END_OF_INPUT, b"abc"), b"ac").into_inner(), State::Error(b"bc", Error::expected(b'b')));
     }
 
+    #[test]
+    fn take_until_test() {
+        assert_eq!(take_until(new(DEFAULT, b"abcd"), b"cd").into_inner(), State::Data(new(DEFAULT, b"cd"), &b"ab"[..]));
+        assert_eq!(take_until(new(DEFAULT, b"cdab"), b"cd").into_inner(), State::Data(new(DEFAULT, b"cdab"), &b""[..]));
+        assert_eq!(take_until(new(DEFAULT, b"abcd"), b"").into_inner(), State::Data(new(DEFAULT, b"abcd"), &b""[..]));
+        assert_eq!(take_until(new(DEFAULT, b"a\r\r\n\r\n"), b"\r\n\r\n").into_inner(), State::Data(new(DEFAULT, b"\r\n\r\n"), &b"a\r"[..]));
+        assert_eq!(take_until(new(DEFAULT, b"abc"), b"cd").into_inner(), State::Incomplete(1));
+        assert_eq!(take_until(new(DEFAULT, b"abc"), b"de").into_inner(), State::Incomplete(2));
+        assert_eq!(take_until(new(DEFAULT, b""), b"de").into_inner(), State::Incomplete(2));
+        assert_eq!(take_until(new(DEFAULT, b"a\r\n\r"), b"\r\n\r\n").into_inner(), State::Incomplete(1));
+        assert_eq!(take_until(new(DEFAULT, b"a\r\n"), b"\r\n\r\n").into_inner(), State::Incomplete(2));
+        assert_eq!(take_until(new(END_OF_INPUT, b"abc"), b"cd").into_inner(), State::Incomplete(1));
+    }
+
+    #[test]
+    fn string_by_test() {
+        let f = |a: u8, b: u8| a.to_ascii_lowercase() == b.to_ascii_lowercase();
+
+        assert_eq!(string_by(new(DEFAULT, b"aBc"), b"Ab", f).into_inner(), State::Data(new(DEFAULT, b"c"), &b"aB"[..]));
+        assert_eq!(string_by(new(DEFAULT, b"aBc"), b"abcd", f).into_inner(), State::Incomplete(1));
+        assert_eq!(string_by(new(DEFAULT, b"aBc"), b"ac", f).into_inner(), State::Error(b"Bc", Error::expected(b'B')));
+        assert_eq!(string_by(new(END_OF_INPUT, b"aBc"), b"abc", f).into_inner(), State::Data(new(END_OF_INPUT, b""), &b"aBc"[..]));
+    }
+
+    #[test]
+    fn keywords_test() {
+        let k = Keywords::new(&[(&b"for"[..], 1), (&b"foreach"[..], 2), (&b"fn"[..], 3), (&b"fn"[..], 4)]);
+
+        assert_eq!(keywords(new(DEFAULT, b"for "), &k).into_inner(), State::Data(new(DEFAULT, b" "), 1));
+        assert_eq!(keywords(new(DEFAULT, b"foreach "), &k).into_inner(), State::Data(new(DEFAULT, b" "), 2));
+        assert_eq!(keywords(new(DEFAULT, b"forea "), &k).into_inner(), State::Data(new(DEFAULT, b"ea "), 1));
+        assert_eq!(keywords(new(DEFAULT, b"fn"), &k).into_inner(), State::Data(new(DEFAULT, b""), 4));
+        assert_eq!(keywords(new(DEFAULT, b"fo "), &k).into_inner(), State::Error(b" ", Error::expected(b'r')));
+        assert_eq!(keywords(new(DEFAULT, b"x"), &k).into_inner(), State::Error(b"x", Error::expected(b'f')));
+        #[cfg(not(feature="noop_error"))]
+        assert_eq!(keywords(new(DEFAULT, b"fx"), &k).map_err(|e| e.expected_tokens().to_vec()).into_inner(), State::Error(&b"x"[..], vec![b'n', b'o']));
+        assert_eq!(keywords(new(DEFAULT, b"for"), &k).into_inner(), State::Incomplete(1));
+        assert_eq!(keywords(new(DEFAULT, b"fo"), &k).into_inner(), State::Incomplete(1));
+        assert_eq!(keywords(new(DEFAULT, b""), &k).into_inner(), State::Incomplete(1));
+
+        assert_eq!(keywords(new(END_OF_INPUT, b"for"), &k).into_inner(), State::Data(new(END_OF_INPUT, b""), 1));
+        assert_eq!(keywords(new(END_OF_INPUT, b"forea"), &k).into_inner(), State::Data(new(END_OF_INPUT, b"ea"), 1));
+        assert_eq!(keywords(new(END_OF_INPUT, b"fo"), &k).into_inner(), State::Incomplete(1));
+
+        let k = Keywords::new(&[(&b""[..], 1), (&b"a"[..], 2)]);
+
+        assert_eq!(keywords(new(DEFAULT, b"b"), &k).into_inner(), State::Data(new(DEFAULT, b"b"), 1));
+        assert_eq!(keywords(new(DEFAULT, b"ab"), &k).into_inner(), State::Data(new(DEFAULT, b"b"), 2));
+
+        let k: Keywords<u8, ()> = Keywords::new(&[]);
+
+        assert_eq!(keywords(new(DEFAULT, b"a"), &k).into_inner(), State::Error(b"a", Error::unexpected()));
+        assert_eq!(keywords(new(DEFAULT, b""), &k).into_inner(), State::Error(b"", Error::unexpected()));
+    }
+
+    #[test]
+    fn eof_test() {
+        assert_eq!(eof(new(END_OF_INPUT, b"")).into_inner(), State::Data(new(END_OF_INPUT, b""), ()));
+        assert_eq!(eof(new(END_OF_INPUT, b"a")).into_inner(), State::Error(&b"a"[..], Error::unexpected()));
+        assert_eq!(eof(new(DEFAULT, b"a")).into_inner(), State::Error(&b"a"[..], Error::unexpected()));
+        assert_eq!(eof(new(DEFAULT, b"")).into_inner(), State::Error(&b""[..], Error::unexpected()));
+    }
+
     #[test]
     #[cfg(not(feature = "noop_error"))]
     fn error_test() {
@@ -698,6 +1391,21 @@ mod test {
         assert_eq!(e.expected_token(), None);
         let e = Error::expected(b'a');
         assert_eq!(e.expected_token(), Some(&b'a'));
+        assert_eq!(e.expected_tokens(), &[b'a'][..]);
+        let e = Error::expected(b'a').merge(Error::expected(b'b'));
+        assert_eq!(e.expected_token(), None);
+        assert_eq!(e.expected_tokens(), &[b'a', b'b'][..]);
+        let e = Error::expected(b'a').merge(Error::expected(b'a'));
+        assert_eq!(e, Error::expected(b'a'));
+        let e = Error::unexpected().merge(Error::expected(b'a'));
+        assert_eq!(e, Error::expected(b'a'));
+        let e = Error::expected(b'a').merge(Error::unexpected());
+        assert_eq!(e, Error::expected(b'a'));
+        let e = Error::expected(b'a').add_context("inner").add_context("outer");
+        assert_eq!(e.context(), &["inner", "outer"][..]);
+        assert_eq!(format!("{}", e), "in outer -> in inner -> expected 97");
+        assert_eq!(format!("{}", Error::<u8>::unexpected()), "unexpected");
+        assert!(e != Error::expected(b'a'));
     }
 
     #[test]
@@ -0,0 +1,135 @@
+//! Rendering of parse errors for display to users.
+//!
+//! The rendered messages only contain the expected tokens and context labels if the `noop_error`
+//! feature is disabled.
+//!
+//! # Example
+//!
+//! ```
+//! use chomp::{parse_only, token};
+//! use chomp::combinators::context;
+//! use chomp::report::report;
+//!
+//! let input = b"[1,\n 2;]";
+//! let p     = |i| context(i, "array", |i| token(i, b'[').then(|i| token(i, b'1'))
+//!                                                     .then(|i| token(i, b','))
+//!                                                     .then(|i| token(i, b'\n'))
+//!                                                     .then(|i| token(i, b' '))
+//!                                                     .then(|i| token(i, b'2'))
+//!                                                     .then(|i| token(i, b',')));
+//!
+//! let e = parse_only(p, input).unwrap_err();
+//!
+//! if let chomp::ParseError::Error(_, ref err) = e {
+//!     let r = report(input, e.offset(input).unwrap(), err);
+//!
+//!     // error: in array -> expected ',', found ';'
+//!     assert!(r.ends_with(" --> line 2, column 3
+//!   |
+//! 2 |  2;]
+//!   |   ^
+//! "));
+//! }
+//! ```
+
+use std::ascii::escape_default;
+use std::cmp;
+
+use ascii::{LineEnding, LineIndex};
+use parsers::Error;
+
+/// Renders `error` which occurred at `offset` in `input` as a message containing the context of
+/// the error, the expected tokens, and the offending line with a caret marking the position.
+///
+/// Lines and columns start at 1, columns are counted in bytes.
+pub fn report(input: &[u8], offset: usize, error: &Error<u8>) -> String {
+    let offset = cmp::min(offset, input.len());
+    let pos    = LineIndex::new(input, LineEnding::Lf).position(offset);
+    let start  = offset - (pos.column - 1);
+    let end    = input[start..].iter().position(|&c| c == b'\n').map(|n| start + n).unwrap_or(input.len());
+    let line   = &input[start..end];
+    let line   = if line.last() == Some(&b'\r') { &line[..line.len() - 1] } else { line };
+    let number = pos.line.to_string();
+    let gutter = " ".repeat(number.len());
+
+    let mut s = String::from("error: ");
+
+    for label in error.context().iter().rev() {
+        s.push_str("in ");
+        s.push_str(label);
+        s.push_str(" -> ");
+    }
+
+    let tokens = error.expected_tokens();
+
+    if tokens.is_empty() {
+        s.push_str("unexpected");
+    } else {
+        s.push_str("expected ");
+
+        for (n, &t) in tokens.iter().enumerate() {
+            if n > 0 {
+                s.push_str(if n + 1 == tokens.len() { " or " } else { ", " });
+            }
+
+            s.push_str(&quote(t));
+        }
+    }
+
+    match input.get(offset) {
+        Some(&c) => { s.push_str(", found "); s.push_str(&quote(c)); },
+        None     => s.push_str(", found end of input"),
+    }
+
+    // Keep tabs in the padding of the caret so that it lines up with the line above
+    let pad: String = line[..cmp::min(pos.column - 1, line.len())].iter().map(|&c| if c == b'\t' { '\t' } else { ' ' }).collect();
+
+    s.push_str(&format!("\n{} --> line {}, column {}\n", &gutter[1..], pos.line, pos.column));
+    s.push_str(&format!("{} |\n", gutter));
+    s.push_str(&format!("{} | {}\n", number, String::from_utf8_lossy(line)));
+    s.push_str(&format!("{} | {}^\n", gutter, pad));
+
+    s
+}
+
+/// Quotes the byte `c`, escaping it if it is not printable.
+fn quote(c: u8) -> String {
+    let e: String = escape_default(c).map(|c| c as char).collect();
+
+    format!("'{}'", e)
+}
+
+#[cfg(test)]
+mod test {
+    use super::report;
+    use parsers::Error;
+
+    #[test]
+    #[cfg(not(feature = "noop_error"))]
+    fn report_test() {
+        assert_eq!(report(b"abc", 1, &Error::expected(b'x')), "error: expected 'x', found 'b'
+ --> line 1, column 2
+  |
+1 | abc
+  |  ^
+");
+        assert_eq!(report(b"a\r\n\tbc\nd", 5, &Error::unexpected()), "error: unexpected, found 'c'
+ --> line 2, column 3
+  |
+2 | \tbc
+  | \t ^
+");
+        assert_eq!(report(b"ab\n", 3, &Error::expected(b'a').merge(Error::expected(b'b')).merge(Error::expected(b'\n'))), "error: expected 'a', 'b' or '\\n', found end of input
+ --> line 2, column 1
+  |
+2 | 
+  | ^
+");
+        assert_eq!(report(b"", 5, &Error::expected(b'a').add_context("inner").add_context("outer")), "error: in outer -> in inner -> expected 'a', found end of input
+ --> line 1, column 1
+  |
+1 | 
+  | ^
+");
+    }
+}
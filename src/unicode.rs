@@ -0,0 +1,886 @@
+//! Parsers for dealing with `char` tokens, eg. text which has already been decoded from UTF-8.
+//!
+//! These mirror the parsers in `chomp::parsers` but are specialized for `Input<char>`, which
+//! avoids having to use the byte-oriented parsers on text.
+//!
+//! For UTF-8 encoded `Input<u8>` there are also parsers like `take_while_str` which decode the
+//! characters while matching and return validated `&str` slices.
+//!
+//! Unicode character class predicates like `is_alphabetic` and `is_xid_start` are available when
+//! the `unicode` feature is enabled, and parsers operating on extended grapheme clusters like
+//! `take_grapheme` are available when the `graphemes` feature is enabled. Case-insensitive
+//! matching using full Unicode case folding, like `string_str_caseless`, is available when the
+//! `case_folding` feature is enabled.
+//!
+//! # Example
+//!
+//! ```
+//! use chomp::parse_only;
+//! use chomp::unicode::{char_, take_while1_char};
+//!
+//! let input: Vec<char> = "häst=1".chars().collect();
+//!
+//! let r = parse_only(|i| take_while1_char(i, char::is_alphabetic).skip(|i| char_(i, '=')), &input);
+//!
+//! assert_eq!(r, Ok(&['h', 'ä', 's', 't'][..]));
+//! ```
+
+use std::str;
+#[cfg(feature = "case_folding")]
+use std::iter;
+
+#[cfg(feature = "unicode")]
+use unicode_xid::UnicodeXID;
+#[cfg(feature = "graphemes")]
+use unicode_segmentation::UnicodeSegmentation;
+#[cfg(feature = "case_folding")]
+use caseless::Caseless;
+
+use {Input, SimpleResult, U8Result};
+use primitives::InputBuffer;
+use parsers::{any, satisfy, take_while, take_while1, token, Error};
+
+/// Unicode alphabetic predicate, see `char::is_alphabetic`.
+#[cfg(feature = "unicode")]
+#[inline]
+pub fn is_alphabetic(c: char) -> bool {
+    c.is_alphabetic()
+}
+
+/// Unicode numeric predicate, see `char::is_numeric`.
+#[cfg(feature = "unicode")]
+#[inline]
+pub fn is_numeric(c: char) -> bool {
+    c.is_numeric()
+}
+
+/// Unicode alphanumeric predicate, see `char::is_alphanumeric`.
+#[cfg(feature = "unicode")]
+#[inline]
+pub fn is_alphanumeric(c: char) -> bool {
+    c.is_alphanumeric()
+}
+
+/// Unicode whitespace predicate, see `char::is_whitespace`.
+#[cfg(feature = "unicode")]
+#[inline]
+pub fn is_whitespace(c: char) -> bool {
+    c.is_whitespace()
+}
+
+/// Predicate matching characters with the `XID_Start` property, ie. characters which can start
+/// an identifier.
+///
+/// ```
+/// use chomp::parse_only;
+/// use chomp::unicode::{is_xid_start, is_xid_continue, take_while_str};
+///
+/// let r = parse_only(|i| take_while_str(i, is_xid_start), "ünï2".as_bytes());
+///
+/// assert_eq!(r, Ok("ünï"));
+/// ```
+#[cfg(feature = "unicode")]
+#[inline]
+pub fn is_xid_start(c: char) -> bool {
+    UnicodeXID::is_xid_start(c)
+}
+
+/// Predicate matching characters with the `XID_Continue` property, ie. characters which can be
+/// used in an identifier after the first character.
+#[cfg(feature = "unicode")]
+#[inline]
+pub fn is_xid_continue(c: char) -> bool {
+    UnicodeXID::is_xid_continue(c)
+}
+
+/// Matches any character, returning the matched character.
+///
+/// If the buffer length is 0 this parser is considered incomplete.
+///
+/// ```
+/// use chomp::parse_only;
+/// use chomp::unicode::any_char;
+///
+/// assert_eq!(parse_only(any_char, &['ö', 'a']), Ok('ö'));
+/// ```
+#[inline]
+pub fn any_char(i: Input<char>) -> SimpleResult<char, char> {
+    any(i)
+}
+
+/// Matches the character `c`.
+///
+/// If the buffer length is 0 this parser is considered incomplete.
+///
+/// ```
+/// use chomp::parse_only;
+/// use chomp::unicode::char_;
+///
+/// assert_eq!(parse_only(|i| char_(i, 'ö'), &['ö', 'a']), Ok('ö'));
+/// ```
+#[inline]
+pub fn char_(i: Input<char>, c: char) -> SimpleResult<char, char> {
+    token(i, c)
+}
+
+/// Matches a character using `f`, the character is returned if `f` yields true, otherwise this
+/// parser fails.
+///
+/// If the buffer length is 0 this parser is considered incomplete.
+///
+/// ```
+/// use chomp::parse_only;
+/// use chomp::unicode::satisfy_char;
+///
+/// assert_eq!(parse_only(|i| satisfy_char(i, char::is_uppercase), &['Ö', 'a']), Ok('Ö'));
+/// ```
+#[inline]
+pub fn satisfy_char<F>(i: Input<char>, f: F) -> SimpleResult<char, char>
+  where F: FnOnce(char) -> bool {
+    satisfy(i, f)
+}
+
+/// Matches the characters of the string `s`, returning the matched slice upon success.
+///
+/// If the length of the contained data is shorter than the number of characters in `s` this
+/// parser is considered incomplete.
+///
+/// ```
+/// use chomp::parse_only;
+/// use chomp::unicode::string_str;
+///
+/// let input: Vec<char> = "åäö!".chars().collect();
+///
+/// assert_eq!(parse_only(|i| string_str(i, "åäö"), &input), Ok(&['å', 'ä', 'ö'][..]));
+/// ```
+#[inline]
+pub fn string_str<'a>(i: Input<'a, char>, s: &str) -> SimpleResult<'a, char, &'a [char]> {
+    match_tokens(i, s.chars().count(), s.chars(), |a, b| a == b)
+}
+
+/// Matches the characters of the string `s` ignoring ASCII case, returning the matched slice upon
+/// success.
+///
+/// Non-ASCII characters have to match exactly. If the length of the contained data is shorter
+/// than the number of characters in `s` this parser is considered incomplete.
+///
+/// ```
+/// use chomp::parse_only;
+/// use chomp::unicode::string_str_ignore_ascii_case;
+///
+/// let input: Vec<char> = "SeLeCt".chars().collect();
+///
+/// assert_eq!(parse_only(|i| string_str_ignore_ascii_case(i, "select"), &input), Ok(&input[..]));
+/// ```
+#[inline]
+pub fn string_str_ignore_ascii_case<'a>(i: Input<'a, char>, s: &str) -> SimpleResult<'a, char, &'a [char]> {
+    match_tokens(i, s.chars().count(), s.chars(), |a, b| a.eq_ignore_ascii_case(&b))
+}
+
+/// Matches the string `s` using Unicode default case folding, returning the matched slice upon
+/// success.
+///
+/// Both `s` and the input are case folded before comparison, which means that a single character
+/// can match multiple characters, eg. `'ß'` matches `"SS"`. The match has to end on a character
+/// boundary of the input, otherwise an unexpected error is reported at that character.
+///
+/// If the input ends before all of `s` has been matched this parser is considered incomplete,
+/// requesting at least one more character since the number of characters folding to the remainder
+/// of `s` is unknown.
+///
+/// ```
+/// use chomp::parse_only;
+/// use chomp::unicode::string_str_caseless;
+///
+/// let input: Vec<char> = "STRASSE".chars().collect();
+///
+/// assert_eq!(parse_only(|i| string_str_caseless(i, "straße"), &input), Ok(&input[..]));
+/// ```
+#[cfg(feature = "case_folding")]
+#[inline]
+pub fn string_str_caseless<'a>(i: Input<'a, char>, s: &str) -> SimpleResult<'a, char, &'a [char]> {
+    let b     = i.buffer();
+    let mut s = s.chars().default_case_fold().peekable();
+
+    for (j, &c) in b.iter().enumerate() {
+        if s.peek().is_none() {
+            return i.replace(&b[j..]).ret(&b[..j]);
+        }
+
+        for f in iter::once(c).default_case_fold() {
+            match s.next() {
+                Some(e) if e == f => (),
+                Some(e)           => return i.replace(&b[j..]).err(Error::expected(e)),
+                None              => return i.replace(&b[j..]).err(Error::unexpected()),
+            }
+        }
+    }
+
+    match s.peek() {
+        None    => i.replace(&b[b.len()..]).ret(b),
+        Some(_) => i.incomplete(1),
+    }
+}
+
+/// Matches the keyword `s` using Unicode default case folding, only if it is not directly followed
+/// by an alphanumeric character or `'_'`.
+///
+/// If the keyword is followed by such a character an unexpected error is reported at that
+/// character. If the keyword is at the end of the buffer and the buffer is not the last slice
+/// this parser is considered incomplete since the following character is still unknown.
+///
+/// ```
+/// use chomp::parse_only;
+/// use chomp::unicode::keyword_caseless;
+///
+/// let input: Vec<char> = "Größe int".chars().collect();
+///
+/// assert_eq!(parse_only(|i| keyword_caseless(i, "GRÖSSE"), &input), Ok(&input[..5]));
+/// ```
+#[cfg(feature = "case_folding")]
+#[inline]
+pub fn keyword_caseless<'a>(i: Input<'a, char>, s: &str) -> SimpleResult<'a, char, &'a [char]> {
+    string_str_caseless(i, s).bind(|i, m| {
+        let b = i.buffer();
+
+        match b.first() {
+            None                                        => if i.is_last_slice() {
+                i.ret(m)
+            } else {
+                i.incomplete(1)
+            },
+            Some(&c) if c.is_alphanumeric() || c == '_' => i.err(Error::unexpected()),
+            Some(_)                                     => i.ret(m),
+        }
+    })
+}
+
+/// Matches the UTF-8 encoding of the string `s`, returning the matched string slice upon success.
+///
+/// If the length of the contained data is shorter than the encoding of `s` this parser is
+/// considered incomplete.
+///
+/// ```
+/// use chomp::parse_only;
+/// use chomp::unicode::string_utf8;
+///
+/// assert_eq!(parse_only(|i| string_utf8(i, "åäö"), "åäö!".as_bytes()), Ok("åäö"));
+/// ```
+#[inline]
+pub fn string_utf8<'a>(i: Input<'a, u8>, s: &str) -> U8Result<'a, &'a str> {
+    match_tokens(i, s.len(), s.bytes(), |a, b| a == b).map(to_str)
+}
+
+/// Matches the UTF-8 encoding of the string `s` ignoring ASCII case, returning the matched string
+/// slice upon success.
+///
+/// Non-ASCII characters have to match exactly. If the length of the contained data is shorter
+/// than the encoding of `s` this parser is considered incomplete.
+///
+/// ```
+/// use chomp::parse_only;
+/// use chomp::unicode::string_utf8_ignore_ascii_case;
+///
+/// assert_eq!(parse_only(|i| string_utf8_ignore_ascii_case(i, "straße"), "STRAßE".as_bytes()), Ok("STRAßE"));
+/// ```
+#[inline]
+pub fn string_utf8_ignore_ascii_case<'a>(i: Input<'a, u8>, s: &str) -> U8Result<'a, &'a str> {
+    // Only ASCII bytes can differ from s, so the matched slice is always valid UTF-8
+    match_tokens(i, s.len(), s.bytes(), |a, b| a.eq_ignore_ascii_case(&b)).map(to_str)
+}
+
+/// Internal parser matching the `n` tokens yielded by `s` using the comparison `f`, returning the
+/// matched slice.
+#[inline]
+fn match_tokens<'a, I, S, F>(i: Input<'a, I>, n: usize, s: S, f: F) -> SimpleResult<'a, I, &'a [I]>
+  where I: Copy,
+        S: Iterator<Item=I>,
+        F: Fn(I, I) -> bool {
+    let b = i.buffer();
+
+    if n > b.len() {
+        return i.incomplete(n - b.len());
+    }
+
+    for (j, c) in s.enumerate() {
+        if !f(b[j], c) {
+            return i.replace(&b[j..]).err(Error::expected(c));
+        }
+    }
+
+    i.replace(&b[n..]).ret(&b[..n])
+}
+
+/// Matches all characters satisfying `f`, returning the matched slice.
+///
+/// Matches zero-length, see `take_while` for details on incomplete input.
+///
+/// ```
+/// use chomp::parse_only;
+/// use chomp::unicode::take_while_char;
+///
+/// let input: Vec<char> = "ab12".chars().collect();
+///
+/// assert_eq!(parse_only(|i| take_while_char(i, char::is_alphabetic), &input), Ok(&['a', 'b'][..]));
+/// ```
+#[inline]
+pub fn take_while_char<'a, F>(i: Input<'a, char>, f: F) -> SimpleResult<'a, char, &'a [char]>
+  where F: Fn(char) -> bool {
+    take_while(i, f)
+}
+
+/// Matches all characters satisfying `f`, returning the matched slice, requires at least one
+/// character to match.
+///
+/// See `take_while1` for details on incomplete input.
+///
+/// ```
+/// use chomp::parse_only;
+/// use chomp::unicode::take_while1_char;
+///
+/// let input: Vec<char> = "ab12".chars().collect();
+///
+/// assert_eq!(parse_only(|i| take_while1_char(i, char::is_alphabetic), &input), Ok(&['a', 'b'][..]));
+/// ```
+#[inline]
+pub fn take_while1_char<'a, F>(i: Input<'a, char>, f: F) -> SimpleResult<'a, char, &'a [char]>
+  where F: Fn(char) -> bool {
+    take_while1(i, f)
+}
+
+/// Matches all UTF-8 encoded characters satisfying `f`, returning the matched string slice.
+///
+/// Matches zero-length. If no failure can be found the parser will be considered to be incomplete
+/// as there might be more input which needs to be matched, this includes a character which is
+/// split at the end of the buffer. An invalid UTF-8 sequence results in an unexpected error at
+/// the start of the sequence.
+///
+/// ```
+/// use chomp::parse_only;
+/// use chomp::unicode::take_while_str;
+///
+/// let r = parse_only(|i| take_while_str(i, char::is_alphabetic), "häst=1".as_bytes());
+///
+/// assert_eq!(r, Ok("häst"));
+/// ```
+#[inline]
+pub fn take_while_str<'a, F>(i: Input<'a, u8>, f: F) -> U8Result<'a, &'a str>
+  where F: Fn(char) -> bool {
+    let b = i.buffer();
+
+    match scan_str(b, f) {
+        (n, Scan::Stop)          => i.replace(&b[n..]).ret(to_str(&b[..n])),
+        (n, Scan::End)           => if i.is_last_slice() {
+            i.replace(&b[n..]).ret(to_str(b))
+        } else {
+            i.incomplete(1)
+        },
+        (n, Scan::Incomplete(m)) => if i.is_last_slice() {
+            i.replace(&b[n..]).err(Error::unexpected())
+        } else {
+            i.incomplete(m)
+        },
+        (n, Scan::Invalid)       => i.replace(&b[n..]).err(Error::unexpected()),
+    }
+}
+
+/// Matches all UTF-8 encoded characters satisfying `f`, returning the matched string slice,
+/// requires at least one character to match.
+///
+/// Behaves like `take_while_str` except that an error is returned if zero characters matched.
+///
+/// ```
+/// use chomp::parse_only;
+/// use chomp::unicode::take_while1_str;
+///
+/// let r = parse_only(|i| take_while1_str(i, char::is_alphabetic), "ö1".as_bytes());
+///
+/// assert_eq!(r, Ok("ö"));
+/// ```
+#[inline]
+pub fn take_while1_str<'a, F>(i: Input<'a, u8>, f: F) -> U8Result<'a, &'a str>
+  where F: Fn(char) -> bool {
+    let b = i.buffer();
+
+    match scan_str(b, f) {
+        (0, Scan::Stop)          => i.err(Error::unexpected()),
+        (n, Scan::Stop)          => i.replace(&b[n..]).ret(to_str(&b[..n])),
+        (n, Scan::End)           => if n > 0 && i.is_last_slice() {
+            i.replace(&b[n..]).ret(to_str(b))
+        } else {
+            i.incomplete(1)
+        },
+        (n, Scan::Incomplete(m)) => if i.is_last_slice() {
+            i.replace(&b[n..]).err(Error::unexpected())
+        } else {
+            i.incomplete(m)
+        },
+        (n, Scan::Invalid)       => i.replace(&b[n..]).err(Error::unexpected()),
+    }
+}
+
+/// Matches all UTF-8 encoded characters until `f` returns true, returning the matched string
+/// slice.
+///
+/// If no character satisfying `f` can be found the parser will be considered to be incomplete,
+/// like `take_till`. An invalid UTF-8 sequence results in an unexpected error at the start of the
+/// sequence.
+///
+/// ```
+/// use chomp::parse_only;
+/// use chomp::unicode::take_till_str;
+///
+/// let r = parse_only(|i| take_till_str(i, |c| c == '→'), "a→b".as_bytes());
+///
+/// assert_eq!(r, Ok("a"));
+/// ```
+#[inline]
+pub fn take_till_str<'a, F>(i: Input<'a, u8>, f: F) -> U8Result<'a, &'a str>
+  where F: Fn(char) -> bool {
+    let b = i.buffer();
+
+    match scan_str(b, |c| !f(c)) {
+        (n, Scan::Stop)          => i.replace(&b[n..]).ret(to_str(&b[..n])),
+        (_, Scan::End)           => i.incomplete(1),
+        (n, Scan::Incomplete(m)) => if i.is_last_slice() {
+            i.replace(&b[n..]).err(Error::unexpected())
+        } else {
+            i.incomplete(m)
+        },
+        (n, Scan::Invalid)       => i.replace(&b[n..]).err(Error::unexpected()),
+    }
+}
+
+/// Matches a single extended grapheme cluster of UTF-8 encoded text, returning it as a string
+/// slice.
+///
+/// Since a grapheme cluster can consist of multiple characters, a cluster which reaches the end
+/// of the buffer is considered incomplete unless it is the last slice. An invalid UTF-8 sequence
+/// at the start of the input results in an unexpected error, an invalid sequence following the
+/// cluster ends it.
+///
+/// If the buffer length is 0 this parser is considered incomplete.
+///
+/// ```
+/// use chomp::parse_only;
+/// use chomp::unicode::take_grapheme;
+///
+/// // "e" followed by a combining acute accent
+/// assert_eq!(parse_only(take_grapheme, "e\u{301}x".as_bytes()), Ok("e\u{301}"));
+/// ```
+#[cfg(feature = "graphemes")]
+#[inline]
+pub fn take_grapheme<'a>(i: Input<'a, u8>) -> U8Result<'a, &'a str> {
+    take_graphemes(i, 1, |_| true)
+}
+
+/// Matches extended grapheme clusters of UTF-8 encoded text while `f` returns true, returning the
+/// matched string slice.
+///
+/// Matches zero-length. A cluster which reaches the end of the buffer is considered incomplete
+/// unless it is the last slice, see `take_grapheme`.
+///
+/// ```
+/// use chomp::parse_only;
+/// use chomp::unicode::take_while_grapheme;
+///
+/// let r = parse_only(|i| take_while_grapheme(i, |g| g != "\u{1F1F8}\u{1F1EA}"), "a\u{1F1F8}\u{1F1EA}".as_bytes());
+///
+/// assert_eq!(r, Ok("a"));
+/// ```
+#[cfg(feature = "graphemes")]
+#[inline]
+pub fn take_while_grapheme<'a, F>(i: Input<'a, u8>, f: F) -> U8Result<'a, &'a str>
+  where F: Fn(&str) -> bool {
+    take_graphemes(i, usize::max_value(), f)
+}
+
+/// Internal parser matching at most `max` grapheme clusters satisfying `f`, matching exactly
+/// `max` clusters is required if `max` is 1.
+#[cfg(feature = "graphemes")]
+#[inline]
+fn take_graphemes<'a, F>(i: Input<'a, u8>, max: usize, f: F) -> U8Result<'a, &'a str>
+  where F: Fn(&str) -> bool {
+    let b         = i.buffer();
+    let (n, scan) = scan_str(b, |_| true);
+    let s         = to_str(&b[..n]);
+
+    // If the valid text reaches the end of the buffer the last cluster might continue in the
+    // following data
+    let needed = match scan {
+        Scan::Stop                                 => unreachable!(),
+        Scan::End if i.is_last_slice()             => None,
+        Scan::End                                  => Some(1),
+        Scan::Incomplete(m) if !i.is_last_slice()  => Some(m),
+        Scan::Incomplete(_) | Scan::Invalid        => None,
+    };
+
+    if n == 0 {
+        return match needed {
+            Some(m)          => i.incomplete(m),
+            None if max == 1 => if b.is_empty() { i.incomplete(1) } else { i.err(Error::unexpected()) },
+            None             => i.ret(s),
+        };
+    }
+
+    let mut len = 0;
+
+    for g in s.graphemes(true).take(max) {
+        if !f(g) {
+            break;
+        }
+
+        len += g.len();
+    }
+
+    match needed {
+        Some(m) if len == n => i.incomplete(m),
+        _                   => i.replace(&b[len..]).ret(&s[..len]),
+    }
+}
+
+/// How `utf8_char` handles invalid UTF-8 sequences.
+#[derive(Copy, Clone, Debug, Eq, PartialEq, Hash)]
+pub enum InvalidUtf8 {
+    /// Fail with an unexpected error at the start of the invalid sequence.
+    Error,
+    /// Yield the replacement character `U+FFFD` in place of the first byte of the invalid
+    /// sequence and continue with the following byte.
+    Replace,
+}
+
+/// Decodes a single UTF-8 encoded character from a byte input.
+///
+/// This allows text to be parsed character by character from any `Input<u8>`, including
+/// streaming sources, without first converting the whole input to a `String`. A character which
+/// is split at the end of the buffer results in an incomplete state requesting the missing bytes,
+/// unless it is the last slice in which case it is handled as an invalid sequence. Invalid
+/// sequences are handled according to `policy`.
+///
+/// If the buffer length is 0 this parser is considered incomplete.
+///
+/// ```
+/// use chomp::parse_only;
+/// use chomp::combinators::many;
+/// use chomp::unicode::{utf8_char, InvalidUtf8};
+///
+/// let r: Result<String, _> = parse_only(|i| many(i, |i| utf8_char(i, InvalidUtf8::Error)), "ö→a".as_bytes());
+/// assert_eq!(r, Ok("ö→a".to_owned()));
+///
+/// let r: Result<String, _> = parse_only(|i| many(i, |i| utf8_char(i, InvalidUtf8::Replace)), b"a\xffb");
+/// assert_eq!(r, Ok("a\u{FFFD}b".to_owned()));
+/// ```
+#[inline]
+pub fn utf8_char(i: Input<u8>, policy: InvalidUtf8) -> U8Result<char> {
+    let b = i.buffer();
+
+    if b.is_empty() {
+        return i.incomplete(1);
+    }
+
+    match (decode_utf8(b), policy) {
+        (Decoded::Char(c, n), _)                          => i.replace(&b[n..]).ret(c),
+        (Decoded::Incomplete(n), _) if !i.is_last_slice() => i.incomplete(n),
+        (_, InvalidUtf8::Error)                           => i.err(Error::unexpected()),
+        (_, InvalidUtf8::Replace)                         => i.replace(&b[1..]).ret('\u{FFFD}'),
+    }
+}
+
+/// Reason for `scan_str` to stop.
+enum Scan {
+    /// A character did not satisfy the predicate.
+    Stop,
+    /// The end of the buffer was reached.
+    End,
+    /// The buffer ends in the middle of a character, the number of missing bytes is included.
+    Incomplete(usize),
+    /// An invalid UTF-8 sequence was found.
+    Invalid,
+}
+
+/// Result of decoding a single UTF-8 encoded character.
+enum Decoded {
+    /// A character and the number of bytes it was encoded with.
+    Char(char, usize),
+    /// The buffer is a valid, but too short, start of a character, the number of missing bytes is
+    /// included.
+    Incomplete(usize),
+    /// The buffer does not start with a valid UTF-8 sequence.
+    Invalid,
+}
+
+/// Internal function decoding the UTF-8 encoded character at the start of `b`, `b` must not be
+/// empty.
+#[inline]
+fn decode_utf8(b: &[u8]) -> Decoded {
+    let n = match b[0] {
+        c if c < 0x80               => return Decoded::Char(c as char, 1),
+        c if 0xC2 <= c && c <= 0xDF => 2,
+        c if 0xE0 <= c && c <= 0xEF => 3,
+        c if 0xF0 <= c && c <= 0xF4 => 4,
+        _                           => return Decoded::Invalid,
+    };
+
+    if b.len() < n {
+        // All of the available trailing bytes need to be continuation bytes
+        return if b[1..].iter().all(|&c| c & 0xC0 == 0x80) {
+            Decoded::Incomplete(n - b.len())
+        } else {
+            Decoded::Invalid
+        };
+    }
+
+    // Let the standard library validate overlong encodings and surrogates
+    match str::from_utf8(&b[..n]).ok().and_then(|s| s.chars().next()) {
+        Some(c) => Decoded::Char(c, n),
+        None    => Decoded::Invalid,
+    }
+}
+
+/// Internal function finding the length of the longest UTF-8 encoded prefix of `b` where all
+/// characters satisfy `f`, together with the reason it stopped.
+#[inline]
+fn scan_str<F>(b: &[u8], f: F) -> (usize, Scan)
+  where F: Fn(char) -> bool {
+    let mut n = 0;
+
+    while n < b.len() {
+        match decode_utf8(&b[n..]) {
+            Decoded::Char(c, m) => if f(c) {
+                n += m;
+            } else {
+                return (n, Scan::Stop);
+            },
+            Decoded::Incomplete(m) => return (n, Scan::Incomplete(m)),
+            Decoded::Invalid       => return (n, Scan::Invalid),
+        }
+    }
+
+    (n, Scan::End)
+}
+
+/// Internal function converting a slice which has already been validated by `scan_str` into a
+/// string slice.
+#[inline]
+fn to_str(b: &[u8]) -> &str {
+    // scan_str has validated all the characters in the slice
+    unsafe { str::from_utf8_unchecked(b) }
+}
+
+#[cfg(test)]
+mod test {
+    use super::{any_char, char_, satisfy_char, string_str, take_while_char, take_while1_char,
+                take_while_str, take_while1_str, take_till_str, utf8_char, InvalidUtf8,
+                string_str_ignore_ascii_case, string_utf8, string_utf8_ignore_ascii_case};
+
+    use primitives::input::{new, DEFAULT, END_OF_INPUT};
+    use primitives::{IntoInner, State};
+    use parsers::Error;
+
+    #[cfg(feature = "unicode")]
+    #[test]
+    fn predicates() {
+        use super::{is_alphabetic, is_numeric, is_alphanumeric, is_whitespace, is_xid_start,
+                    is_xid_continue};
+
+        assert!(is_alphabetic('ä'));
+        assert!(is_alphabetic('λ'));
+        assert!(!is_alphabetic('1'));
+        assert!(is_numeric('١'));
+        assert!(!is_numeric('a'));
+        assert!(is_alphanumeric('é'));
+        assert!(is_alphanumeric('٣'));
+        assert!(!is_alphanumeric('-'));
+        assert!(is_whitespace('\u{a0}'));
+        assert!(is_whitespace('\u{2003}'));
+        assert!(!is_whitespace('_'));
+        assert!(is_xid_start('ü'));
+        assert!(!is_xid_start('_'));
+        assert!(!is_xid_start('1'));
+        assert!(is_xid_continue('_'));
+        assert!(is_xid_continue('1'));
+        assert!(!is_xid_continue('-'));
+    }
+
+    #[test]
+    fn char_parsers() {
+        let s: Vec<char> = "öa".chars().collect();
+
+        assert_eq!(any_char(new(END_OF_INPUT, &s)).into_inner(), State::Data(new(END_OF_INPUT, &s[1..]), 'ö'));
+        assert_eq!(any_char(new(DEFAULT, &[])).into_inner(), State::Incomplete(1));
+        assert_eq!(char_(new(END_OF_INPUT, &s), 'ö').into_inner(), State::Data(new(END_OF_INPUT, &s[1..]), 'ö'));
+        assert_eq!(char_(new(END_OF_INPUT, &s), 'o').into_inner(), State::Error(&s[..], Error::expected('o')));
+        assert_eq!(satisfy_char(new(END_OF_INPUT, &s), char::is_alphabetic).into_inner(), State::Data(new(END_OF_INPUT, &s[1..]), 'ö'));
+        assert_eq!(satisfy_char(new(END_OF_INPUT, &s), char::is_numeric).into_inner(), State::Error(&s[..], Error::unexpected()));
+    }
+
+    #[test]
+    fn string_str_test() {
+        let s: Vec<char> = "ηλιος".chars().collect();
+
+        assert_eq!(string_str(new(END_OF_INPUT, &s), "").into_inner(), State::Data(new(END_OF_INPUT, &s[..]), &s[..0]));
+        assert_eq!(string_str(new(END_OF_INPUT, &s), "ηλ").into_inner(), State::Data(new(END_OF_INPUT, &s[2..]), &s[..2]));
+        assert_eq!(string_str(new(END_OF_INPUT, &s), "ηλιος").into_inner(), State::Data(new(END_OF_INPUT, &s[5..]), &s[..]));
+        assert_eq!(string_str(new(END_OF_INPUT, &s), "ηα").into_inner(), State::Error(&s[1..], Error::expected('α')));
+        assert_eq!(string_str(new(END_OF_INPUT, &s), "ηλιοςς").into_inner(), State::Incomplete(1));
+        assert_eq!(string_str(new(DEFAULT, &s[..1]), "ηλ").into_inner(), State::Incomplete(1));
+    }
+
+    #[test]
+    fn string_str_ignore_ascii_case_test() {
+        let s: Vec<char> = "SeLÄct".chars().collect();
+
+        assert_eq!(string_str_ignore_ascii_case(new(END_OF_INPUT, &s), "sElÄCT").into_inner(), State::Data(new(END_OF_INPUT, &s[6..]), &s[..]));
+        assert_eq!(string_str_ignore_ascii_case(new(END_OF_INPUT, &s), "sel").into_inner(), State::Data(new(END_OF_INPUT, &s[3..]), &s[..3]));
+        assert_eq!(string_str_ignore_ascii_case(new(END_OF_INPUT, &s), "selä").into_inner(), State::Error(&s[3..], Error::expected('ä')));
+        assert_eq!(string_str_ignore_ascii_case(new(DEFAULT, &s[..2]), "sel").into_inner(), State::Incomplete(1));
+    }
+
+    #[test]
+    fn string_utf8_test() {
+        let s = "åäö!".as_bytes();
+
+        assert_eq!(string_utf8(new(END_OF_INPUT, s), "åä").into_inner(), State::Data(new(END_OF_INPUT, &s[4..]), "åä"));
+        assert_eq!(string_utf8(new(END_OF_INPUT, s), "åa").into_inner(), State::Error(&s[2..], Error::expected(b'a')));
+        assert_eq!(string_utf8(new(END_OF_INPUT, s), "åäö!?").into_inner(), State::Incomplete(1));
+        assert_eq!(string_utf8(new(DEFAULT, &s[..3]), "åä").into_inner(), State::Incomplete(1));
+
+        let s = "STRAßE;".as_bytes();
+
+        assert_eq!(string_utf8_ignore_ascii_case(new(END_OF_INPUT, s), "straße").into_inner(), State::Data(new(END_OF_INPUT, &s[7..]), "STRAßE"));
+        assert_eq!(string_utf8_ignore_ascii_case(new(END_OF_INPUT, s), "strasse").into_inner(), State::Error(&s[4..], Error::expected(b's')));
+        assert_eq!(string_utf8_ignore_ascii_case(new(DEFAULT, &s[..5]), "straße").into_inner(), State::Incomplete(2));
+    }
+
+    #[test]
+    fn take_while_char_test() {
+        let s: Vec<char> = "ab1".chars().collect();
+
+        assert_eq!(take_while_char(new(END_OF_INPUT, &s), char::is_alphabetic).into_inner(), State::Data(new(END_OF_INPUT, &s[2..]), &s[..2]));
+        assert_eq!(take_while_char(new(END_OF_INPUT, &s), char::is_numeric).into_inner(), State::Data(new(END_OF_INPUT, &s[..]), &s[..0]));
+        assert_eq!(take_while_char(new(DEFAULT, &s[..2]), char::is_alphabetic).into_inner(), State::Incomplete(1));
+        assert_eq!(take_while1_char(new(END_OF_INPUT, &s), char::is_alphabetic).into_inner(), State::Data(new(END_OF_INPUT, &s[2..]), &s[..2]));
+        assert_eq!(take_while1_char(new(END_OF_INPUT, &s), char::is_numeric).into_inner(), State::Error(&s[..], Error::unexpected()));
+    }
+
+    #[test]
+    fn take_while_str_test() {
+        let s = "häst=1".as_bytes();
+
+        assert_eq!(take_while_str(new(END_OF_INPUT, s), char::is_alphabetic).into_inner(), State::Data(new(END_OF_INPUT, &s[5..]), "häst"));
+        assert_eq!(take_while_str(new(END_OF_INPUT, s), char::is_numeric).into_inner(), State::Data(new(END_OF_INPUT, s), ""));
+        assert_eq!(take_while_str(new(END_OF_INPUT, &s[..5]), char::is_alphabetic).into_inner(), State::Data(new(END_OF_INPUT, &s[5..5]), "häst"));
+        assert_eq!(take_while_str(new(END_OF_INPUT, "€".as_bytes()), |_| true).into_inner(), State::Data(new(END_OF_INPUT, b""), "€"));
+        assert_eq!(take_while_str(new(DEFAULT, &s[..5]), char::is_alphabetic).into_inner(), State::Incomplete(1));
+
+        // Split multi-byte character
+        assert_eq!(take_while_str(new(DEFAULT, &s[..2]), char::is_alphabetic).into_inner(), State::Incomplete(1));
+        assert_eq!(take_while_str(new(DEFAULT, &"€".as_bytes()[..1]), |_| true).into_inner(), State::Incomplete(2));
+        assert_eq!(take_while_str(new(END_OF_INPUT, &s[..2]), char::is_alphabetic).into_inner(), State::Error(&s[1..2], Error::unexpected()));
+
+        // Invalid sequences
+        assert_eq!(take_while_str(new(END_OF_INPUT, b"a\xffb"), |_| true).into_inner(), State::Error(&b"\xffb"[..], Error::unexpected()));
+        assert_eq!(take_while_str(new(DEFAULT, b"a\xc3a"), |_| true).into_inner(), State::Error(&b"\xc3a"[..], Error::unexpected()));
+        assert_eq!(take_while_str(new(END_OF_INPUT, b"\xc0\x80"), |_| true).into_inner(), State::Error(&b"\xc0\x80"[..], Error::unexpected()));
+        assert_eq!(take_while_str(new(END_OF_INPUT, b"\xed\xa0\x80"), |_| true).into_inner(), State::Error(&b"\xed\xa0\x80"[..], Error::unexpected()));
+        assert_eq!(take_while_str(new(END_OF_INPUT, b"a\xffb"), |c| c == 'b').into_inner(), State::Data(new(END_OF_INPUT, b"a\xffb"), ""));
+    }
+
+    #[test]
+    fn take_while1_str_test() {
+        let s = "öl1".as_bytes();
+
+        assert_eq!(take_while1_str(new(END_OF_INPUT, s), char::is_alphabetic).into_inner(), State::Data(new(END_OF_INPUT, &s[3..]), "öl"));
+        assert_eq!(take_while1_str(new(END_OF_INPUT, s), char::is_numeric).into_inner(), State::Error(s, Error::unexpected()));
+        assert_eq!(take_while1_str(new(END_OF_INPUT, b""), char::is_numeric).into_inner(), State::Incomplete(1));
+        assert_eq!(take_while1_str(new(DEFAULT, &s[..3]), char::is_alphabetic).into_inner(), State::Incomplete(1));
+        assert_eq!(take_while1_str(new(DEFAULT, &s[..1]), char::is_alphabetic).into_inner(), State::Incomplete(1));
+        assert_eq!(take_while1_str(new(END_OF_INPUT, &s[..3]), char::is_alphabetic).into_inner(), State::Data(new(END_OF_INPUT, &s[3..3]), "öl"));
+    }
+
+    #[test]
+    fn take_till_str_test() {
+        let s = "a→b".as_bytes();
+
+        assert_eq!(take_till_str(new(END_OF_INPUT, s), |c| c == '→').into_inner(), State::Data(new(END_OF_INPUT, &s[1..]), "a"));
+        assert_eq!(take_till_str(new(END_OF_INPUT, s), |c| c == 'a').into_inner(), State::Data(new(END_OF_INPUT, s), ""));
+        assert_eq!(take_till_str(new(END_OF_INPUT, s), |c| c == 'c').into_inner(), State::Incomplete(1));
+        assert_eq!(take_till_str(new(DEFAULT, &s[..2]), |c| c == 'b').into_inner(), State::Incomplete(2));
+        assert_eq!(take_till_str(new(END_OF_INPUT, b"a\xff"), |c| c == 'b').into_inner(), State::Error(&b"\xff"[..], Error::unexpected()));
+    }
+
+    #[cfg(feature = "graphemes")]
+    #[test]
+    fn grapheme_test() {
+        use super::{take_grapheme, take_while_grapheme};
+
+        let s = "e\u{301}\u{1F1F8}\u{1F1EA}a".as_bytes();
+
+        assert_eq!(take_grapheme(new(END_OF_INPUT, s)).into_inner(), State::Data(new(END_OF_INPUT, &s[3..]), "e\u{301}"));
+        assert_eq!(take_grapheme(new(END_OF_INPUT, &s[3..])).into_inner(), State::Data(new(END_OF_INPUT, &s[11..]), "\u{1F1F8}\u{1F1EA}"));
+        assert_eq!(take_grapheme(new(END_OF_INPUT, &s[11..])).into_inner(), State::Data(new(END_OF_INPUT, &s[12..]), "a"));
+        assert_eq!(take_grapheme(new(END_OF_INPUT, b"")).into_inner(), State::Incomplete(1));
+        assert_eq!(take_grapheme(new(END_OF_INPUT, b"\xff")).into_inner(), State::Error(&b"\xff"[..], Error::unexpected()));
+        assert_eq!(take_grapheme(new(END_OF_INPUT, b"a\xff")).into_inner(), State::Data(new(END_OF_INPUT, &b"\xff"[..]), "a"));
+        assert_eq!(take_grapheme(new(DEFAULT, b"")).into_inner(), State::Incomplete(1));
+        assert_eq!(take_grapheme(new(DEFAULT, &s[..1])).into_inner(), State::Incomplete(1));
+        assert_eq!(take_grapheme(new(DEFAULT, &s[..2])).into_inner(), State::Incomplete(1));
+        assert_eq!(take_grapheme(new(DEFAULT, &s[..4])).into_inner(), State::Incomplete(3));
+        assert_eq!(take_grapheme(new(DEFAULT, &s[..7])).into_inner(), State::Data(new(DEFAULT, &s[3..7]), "e\u{301}"));
+
+        assert_eq!(take_while_grapheme(new(END_OF_INPUT, s), |g| g != "a").into_inner(), State::Data(new(END_OF_INPUT, &s[11..]), "e\u{301}\u{1F1F8}\u{1F1EA}"));
+        assert_eq!(take_while_grapheme(new(END_OF_INPUT, s), |_| true).into_inner(), State::Data(new(END_OF_INPUT, &s[12..]), "e\u{301}\u{1F1F8}\u{1F1EA}a"));
+        assert_eq!(take_while_grapheme(new(END_OF_INPUT, s), |_| false).into_inner(), State::Data(new(END_OF_INPUT, s), ""));
+        assert_eq!(take_while_grapheme(new(END_OF_INPUT, b""), |_| true).into_inner(), State::Data(new(END_OF_INPUT, b""), ""));
+        assert_eq!(take_while_grapheme(new(DEFAULT, s), |_| true).into_inner(), State::Incomplete(1));
+        assert_eq!(take_while_grapheme(new(DEFAULT, s), |g| g != "a").into_inner(), State::Data(new(DEFAULT, &s[11..]), "e\u{301}\u{1F1F8}\u{1F1EA}"));
+    }
+
+    #[cfg(feature = "case_folding")]
+    #[test]
+    fn caseless_test() {
+        use super::{string_str_caseless, keyword_caseless};
+
+        let s: Vec<char> = "STRASSE straße".chars().collect();
+
+        assert_eq!(string_str_caseless(new(END_OF_INPUT, &s), "Straße").into_inner(), State::Data(new(END_OF_INPUT, &s[7..]), &s[..7]));
+        assert_eq!(string_str_caseless(new(END_OF_INPUT, &s[8..]), "STRASSE").into_inner(), State::Data(new(END_OF_INPUT, &s[14..]), &s[8..]));
+        assert_eq!(string_str_caseless(new(END_OF_INPUT, &s), "").into_inner(), State::Data(new(END_OF_INPUT, &s[..]), &s[..0]));
+        assert_eq!(string_str_caseless(new(DEFAULT, &s[..5]), "strasse").into_inner(), State::Incomplete(1));
+        assert_eq!(string_str_caseless(new(END_OF_INPUT, &s), "stre").into_inner(), State::Error(&s[3..], Error::expected('e')));
+        // Match ends in the middle of the folding of 'ß'
+        assert_eq!(string_str_caseless(new(END_OF_INPUT, &s[8..]), "stras").into_inner(), State::Error(&s[12..], Error::unexpected()));
+
+        assert_eq!(keyword_caseless(new(END_OF_INPUT, &s), "strasse").into_inner(), State::Data(new(END_OF_INPUT, &s[7..]), &s[..7]));
+        assert_eq!(keyword_caseless(new(END_OF_INPUT, &s), "stras").into_inner(), State::Error(&s[5..], Error::unexpected()));
+        assert_eq!(keyword_caseless(new(DEFAULT, &s[8..]), "strasse").into_inner(), State::Incomplete(1));
+        assert_eq!(keyword_caseless(new(END_OF_INPUT, &s[8..]), "strasse").into_inner(), State::Data(new(END_OF_INPUT, &s[14..]), &s[8..]));
+    }
+
+    #[test]
+    fn utf8_char_test() {
+        let s = "aö→😀".as_bytes();
+
+        for &p in &[InvalidUtf8::Error, InvalidUtf8::Replace] {
+            assert_eq!(utf8_char(new(END_OF_INPUT, s), p).into_inner(), State::Data(new(END_OF_INPUT, &s[1..]), 'a'));
+            assert_eq!(utf8_char(new(END_OF_INPUT, &s[1..]), p).into_inner(), State::Data(new(END_OF_INPUT, &s[3..]), 'ö'));
+            assert_eq!(utf8_char(new(END_OF_INPUT, &s[3..]), p).into_inner(), State::Data(new(END_OF_INPUT, &s[6..]), '→'));
+            assert_eq!(utf8_char(new(END_OF_INPUT, &s[6..]), p).into_inner(), State::Data(new(END_OF_INPUT, &s[10..]), '😀'));
+
+            assert_eq!(utf8_char(new(DEFAULT, b""), p).into_inner(), State::Incomplete(1));
+            assert_eq!(utf8_char(new(END_OF_INPUT, b""), p).into_inner(), State::Incomplete(1));
+            assert_eq!(utf8_char(new(DEFAULT, &s[1..2]), p).into_inner(), State::Incomplete(1));
+            assert_eq!(utf8_char(new(DEFAULT, &s[6..7]), p).into_inner(), State::Incomplete(3));
+            assert_eq!(utf8_char(new(DEFAULT, &s[6..9]), p).into_inner(), State::Incomplete(1));
+        }
+
+        assert_eq!(utf8_char(new(END_OF_INPUT, &s[6..8]), InvalidUtf8::Error).into_inner(), State::Error(&s[6..8], Error::unexpected()));
+        assert_eq!(utf8_char(new(END_OF_INPUT, b"\xff"), InvalidUtf8::Error).into_inner(), State::Error(&b"\xff"[..], Error::unexpected()));
+        assert_eq!(utf8_char(new(DEFAULT, b"\xc3a"), InvalidUtf8::Error).into_inner(), State::Error(&b"\xc3a"[..], Error::unexpected()));
+        assert_eq!(utf8_char(new(DEFAULT, b"\xed\xa0\x80"), InvalidUtf8::Error).into_inner(), State::Error(&b"\xed\xa0\x80"[..], Error::unexpected()));
+
+        assert_eq!(utf8_char(new(END_OF_INPUT, &s[6..8]), InvalidUtf8::Replace).into_inner(), State::Data(new(END_OF_INPUT, &s[7..8]), '\u{FFFD}'));
+        assert_eq!(utf8_char(new(END_OF_INPUT, b"\xff"), InvalidUtf8::Replace).into_inner(), State::Data(new(END_OF_INPUT, b""), '\u{FFFD}'));
+        assert_eq!(utf8_char(new(DEFAULT, b"\xc3a"), InvalidUtf8::Replace).into_inner(), State::Data(new(DEFAULT, b"a"), '\u{FFFD}'));
+    }
+}